@@ -0,0 +1,127 @@
+/*!
+This crate provides the `#[derive(Tabular)]` macro for [colonnade](https://docs.rs/colonnade).
+Use it via colonnade's `derive` feature rather than directly.
+
+The macro implements `colonnade::ToRow` and `colonnade::Tabular` for a struct with named fields,
+each field becoming a column. Fields must implement `Display`; a field whose type implements
+`colonnade::ToCell`, such as a primitive number, is converted by it, so numbers are right-aligned.
+Fields may be annotated:
+
+* `#[tabular(header = "Size")]` - the column header; by default this is the field name
+* `#[tabular(align = "right")]` - the column alignment: `left`, `right`, `center`, or `justify`
+* `#[tabular(skip)]` - leave the field out of the table
+*/
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, LitStr};
+
+/// Derive `colonnade::ToRow` and `colonnade::Tabular` for a struct with named fields.
+#[proc_macro_derive(Tabular, attributes(tabular))]
+pub fn derive_tabular(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match tabular(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+// the configuration of a single column
+struct Column {
+    ident: syn::Ident,
+    header: String,
+    align: Option<TokenStream2>,
+}
+
+fn tabular(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    &input.ident,
+                    "Tabular can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "Tabular can only be derived for structs",
+            ))
+        }
+    };
+    let mut columns = vec![];
+    for field in fields {
+        let ident = field.ident.clone().unwrap();
+        let mut header = ident.to_string();
+        let mut align = None;
+        let mut skip = false;
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("tabular")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    skip = true;
+                    Ok(())
+                } else if meta.path.is_ident("header") {
+                    header = meta.value()?.parse::<LitStr>()?.value();
+                    Ok(())
+                } else if meta.path.is_ident("align") {
+                    let value = meta.value()?.parse::<LitStr>()?;
+                    align = Some(match value.value().as_str() {
+                        "left" => quote!(::colonnade::Alignment::Left),
+                        "right" => quote!(::colonnade::Alignment::Right),
+                        "center" => quote!(::colonnade::Alignment::Center),
+                        "justify" => quote!(::colonnade::Alignment::Justify),
+                        _ => {
+                            return Err(Error::new_spanned(
+                                value,
+                                "expected left, right, center, or justify",
+                            ))
+                        }
+                    });
+                    Ok(())
+                } else {
+                    Err(meta.error("expected header, align, or skip"))
+                }
+            })?;
+        }
+        if !skip {
+            columns.push(Column {
+                ident,
+                header,
+                align,
+            });
+        }
+    }
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let cells = columns.iter().map(|c| {
+        let ident = &c.ident;
+        quote! {{
+            use ::colonnade::__private::{ViaDisplay as _, ViaToCell as _};
+            (&::colonnade::__private::Field(&self.#ident)).cell()
+        }}
+    });
+    let headers = columns.iter().map(|c| &c.header);
+    let alignments = columns.iter().map(|c| match &c.align {
+        Some(align) => quote!(::std::option::Option::Some(#align)),
+        None => quote!(::std::option::Option::None),
+    });
+    Ok(quote! {
+        impl #impl_generics ::colonnade::ToRow for #name #ty_generics #where_clause {
+            fn to_row(&self) -> ::std::vec::Vec<::colonnade::Cell<'_>> {
+                ::std::vec![#(#cells),*]
+            }
+        }
+        impl #impl_generics ::colonnade::Tabular for #name #ty_generics #where_clause {
+            fn headers() -> ::std::vec::Vec<::std::string::String> {
+                ::std::vec![#(::std::string::String::from(#headers)),*]
+            }
+            fn alignments() -> ::std::vec::Vec<::std::option::Option<::colonnade::Alignment>> {
+                ::std::vec![#(#alignments),*]
+            }
+        }
+    })
+}
@@ -42,7 +42,6 @@ fn main() {
     colonnade.columns[3].vertical_alignment(VerticalAlignment::Bottom);
 
     // now print out the table
-    let mut style_toggle = 0;
     for (row_num, row) in colonnade.macerate(&text).unwrap().iter().enumerate() {
         for line in row {
             if line.len() > 1 {
@@ -59,7 +58,7 @@ fn main() {
                         (colors.1, colors.0)
                     };
                     let style = Style::new().fg(fg).on(bg);
-                    let style = match (cell_num + style_toggle) % 5 {
+                    let style = match (cell_num + row_num) % 5 {
                         0 => style,
                         1 => style.italic(),
                         2 => style.bold(),
@@ -72,6 +71,5 @@ fn main() {
             }
             println!();
         }
-        style_toggle += 1;
     }
 }
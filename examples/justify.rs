@@ -13,7 +13,7 @@ fn main() {
         ["one line", "more"],
         ["This is a bunch of text so we can see what happens with non final lines. The last line shouldn't be justified.", "Let's see what it looks like when there are two columns."],
     ];
-    for line in colonnade.tabulate(&data).unwrap() {
+    for line in colonnade.tabulate(data).unwrap() {
         println!("{}", line);
     }
 }
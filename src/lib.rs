@@ -10,7 +10,7 @@ used by adding `colonnade` to your dependencies in your project's `Cargo.toml`.
 
 ```toml
 [dependencies]
-colonnade = "1"
+colonnade = "2"
 ```
 
 # Example
@@ -80,18 +80,82 @@ Colonnade to treat the non-breaking space character `\u{00A0}` like a non-space
 To require `nbsp`, specify your Colonnade dependency like so in your Cargo.toml:
 
 ```toml
-two_timer = { version = "^1.3.0", features = ["nbsp"] }
+colonnade = { version = "2", features = ["nbsp"] }
 ```
 
 or
 
 ```toml
 [dependencies.colonnade]
-version  = "^1.3.0"
+version  = "2"
 features = ["nbsp"]
 ```
 
 This feature has a dependency on the `regex` and `lazy_static` crates.
+
+If column widths come out other than you expect, the `trace` feature emits a
+[`tracing`](https://docs.rs/tracing) event at the `DEBUG` level for each step of the layout:
+the initial widths, each shrinking pass, forcible truncation, dropped columns, and the
+distribution of surplus space. Subscribe to the `colonnade` target to see them.
+
+The `terminal` feature adds [`Colonnade::for_terminal`](struct.Colonnade.html#method.for_terminal),
+which sizes the viewport to the width of the terminal, and lets [`terminal_width`](fn.terminal_width.html)
+consult the terminal. Both respect the `COLUMNS` environment variable, so output is reproducible in
+scripts and CI. The feature has a dependency on the `terminal_size` crate.
+
+[`AutoStream`](struct.AutoStream.html), re-exported from the `anstream` crate, strips the colors
+and text attributes of styled cells from what is written to it when the output is not a terminal
+or `NO_COLOR` is set, so the same code produces plain text when piped. [`Color`](enum.Color.html)
+and [`Style`](struct.Style.html) convert to and from the types of the `anstyle` crate.
+
+The `derive` feature provides `#[derive(Tabular)]`, which implements [`ToRow`](trait.ToRow.html) and
+[`Tabular`](trait.Tabular.html) for a struct with named fields, each field a column. Fields may be
+annotated with `#[tabular(header = "Size", align = "right")]`, or `#[tabular(skip)]` to leave them out.
+
+```rust
+# #[cfg(feature = "derive")]
+# mod demo {
+use colonnade::Tabular;
+
+#[derive(Tabular)]
+struct File {
+    name: String,
+    #[tabular(header = "Size", align = "right")]
+    size: u64,
+    #[tabular(skip)]
+    inode: u64,
+}
+# }
+```
+
+The `json` feature adds [`JsonTable`](struct.JsonTable.html), which tabulates a `serde_json::Value`
+array of objects, taking its columns from the union of the objects' keys. The feature has a dependency
+on the `serde_json` crate.
+
+The `serde` feature implements `Serialize` and `Deserialize` for
+[`ColonnadeBuilder`](struct.ColonnadeBuilder.html) and [`ColumnConfig`](struct.ColumnConfig.html),
+so the appearance of a table -- its margins, padding, alignments, widths, and priorities -- can be
+kept in a configuration file in any format serde supports. The feature has a dependency on the
+`serde` crate.
+
+The `ansi_term`, `owo-colors`, and `yansi` features implement [`Styler`](trait.Styler.html) for the
+styles of the crates of the same names, so
+[`Colonnade::tabulate_styled`](struct.Colonnade.html#method.tabulate_styled) can color cells with
+whichever an application already uses.
+
+The `unicode-width` feature adds
+[`Colonnade::width_profile`](struct.Colonnade.html#method.width_profile), which measures emoji and
+East Asian characters as a particular terminal renders them. The feature has a dependency on the
+`unicode-width` crate.
+
+The `chrono` feature adds [`DateTimeFormat`](struct.DateTimeFormat.html) and
+[`Column::datetime_format`](struct.Column.html#method.datetime_format), which parse the dates and
+times in a column and display them in a consistent format. The feature has a dependency on the
+`chrono` crate.
+
+The `collation` feature adds [`SortKey::collator`](struct.SortKey.html#method.collator), which sorts
+the text in a column by the rules of a locale rather than by the order of its bytes. The feature has
+a dependency on the `icu_collator` and `icu_provider` crates.
 */
 extern crate strip_ansi_escapes;
 extern crate unicode_segmentation;
@@ -102,758 +166,2605 @@ extern crate lazy_static;
 extern crate regex;
 #[cfg(feature = "nbsp")]
 use regex::Regex;
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::{HashMap, VecDeque};
 use std::fmt;
+use std::marker::PhantomData;
+use std::ops::RangeInclusive;
+use std::sync::Arc;
 use unicode_segmentation::UnicodeSegmentation;
 
+// emit a layout event when the trace feature is enabled
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "trace")]
+        tracing::debug!(target: "colonnade", $($arg)*);
+    };
+}
+
+/// The product of [`Colonnade::macerate`](struct.Colonnade.html#method.macerate): rows of lines of
+/// `(margin, text)` pieces.
+pub type Maceration = Vec<Vec<Vec<(String, String)>>>;
+
+/// A piece of a line of a table as handed to the callback of
+/// [`Colonnade::macerate_with`](struct.Colonnade.html#method.macerate_with): the whitespace
+/// margin preceding some text and the text itself, along with their display widths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Piece<'a> {
+    /// the margin preceding the text
+    pub margin: &'a str,
+    /// the text of a column, or of a separator or note spanning the table
+    pub text: &'a str,
+    /// the display width of the text, ignoring any ANSI escape sequences
+    pub width: usize,
+    /// the width of the column the text belongs to, padding included, if it belongs to one
+    pub column_width: Option<usize>,
+    /// what part of the table the piece is
+    pub kind: PieceKind,
+}
+
+/// What part of a table a [`Piece`](struct.Piece.html) is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PieceKind {
+    /// the text of a column of a row, with the margin preceding it
+    Cell,
+    /// space following a column, such as a right margin
+    Margin,
+    /// the marker counting the columns left out for lack of space
+    Marker,
+    /// a line separating rows, such as a rule drawn by [borders](struct.Borders.html); the line
+    /// is in the margin of the piece
+    Separator,
+    /// an empty row standing for a separator; the line is in the margin of the piece
+    Rule,
+    /// a row spanning the table, such as the header of a group of rows
+    Group,
+    /// a line of a row displayed as a record
+    Record,
+    /// a note following the table, such as the number of rows left out
+    Note,
+}
+
 /// All the things that can go wrong when laying out tabular data.
+///
+/// The enum is non-exhaustive, so matching on it requires a wildcard arm and new kinds of error
+/// may be added without a breaking change.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum ColonnadeError {
     /// The data to display is inconsistent with the spec.
     /// The tuple values are the index of the data row, its length, and the expected length.
     InconsistentColumns(usize, usize, usize), // row, row length, spec length
-    /// The column index provided is outside the columns available.
-    OutOfBounds,
+    /// The column index provided is outside the columns available. The tuple values are the
+    /// offending index and the name given for the column, if there is one.
+    OutOfBounds(usize, Option<String>), // column, name
     /// The column count parameter given to the constructor was 0.
     InsufficientColumns,
-    /// The minimum space required by the columns is greater than the viewport.
-    InsufficientSpace,
-    /// The minimum and maximum width of a column conflict. The stored parameter is the column index.
-    MinGreaterThanMax(usize), // column
+    /// The minimum space required by the columns is greater than the viewport. The stored parameter
+    /// accounts for the space required.
+    InsufficientSpace(SpaceDiagnostics),
+    /// The minimum and maximum width of a column conflict. The tuple values are the column index and
+    /// the column's [name](struct.Column.html#method.name), if it has one.
+    MinGreaterThanMax(usize, Option<String>), // column, name
+    /// Data does not fit the widths of an already adjusted layout and the overflow policy is
+    /// `OverflowPolicy::Error`. The tuple values are the indices of the offending row and column.
+    Overflow(usize, usize), // row, column
+    /// In [`strict`](struct.Colonnade.html#method.strict) mode, a width computation during layout
+    /// or rendering would have overflowed or underflowed. The stored parameter names the computation.
+    Arithmetic(&'static str),
+    /// A JSON value given to [`JsonTable`](struct.JsonTable.html) is not an array of objects. The
+    /// stored parameter describes the problem.
+    Json(String),
+    /// Writing to the output of [`macerate_with`](struct.Colonnade.html#method.macerate_with) failed.
+    Write,
+    /// The name given to [`Colonnade::format`](struct.Colonnade.html#method.format) is not one
+    /// of the formats supported. The stored parameter is the name.
+    UnsupportedFormat(String),
+    /// A setting refers to a column by a [name](struct.Column.html#method.name) no column has.
+    /// The stored parameter is the name.
+    UnknownColumn(String),
+    /// A [width ratio](struct.Colonnade.html#method.width_ratio) is not a positive number or
+    /// relates a column to itself. The tuple values are the names of the two columns and the ratio.
+    InvalidRatio(String, String, f64), // column, of, ratio
 }
 
 impl std::fmt::Display for ColonnadeError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:?}", self)
+        match self {
+            ColonnadeError::InsufficientSpace(diagnostics) => {
+                write!(
+                    f,
+                    "InsufficientSpace: need at least {} columns, have {}",
+                    diagnostics.required, diagnostics.viewport
+                )?;
+                let widest = diagnostics
+                    .columns
+                    .iter()
+                    .rev()
+                    .max_by_key(|c| c.margin.saturating_add(c.minimum));
+                match widest {
+                    Some(c) => write!(
+                        f,
+                        "; the widest is {} at {}",
+                        column_label(c.index, &c.name),
+                        c.margin.saturating_add(c.minimum)
+                    ),
+                    None => Ok(()),
+                }
+            }
+            ColonnadeError::MinGreaterThanMax(index, name) => write!(
+                f,
+                "MinGreaterThanMax: the minimum width of {} is greater than its maximum",
+                column_label(*index, name)
+            ),
+            ColonnadeError::OutOfBounds(index, name) => {
+                write!(f, "OutOfBounds: there is no {}", column_label(*index, name))
+            }
+            ColonnadeError::UnsupportedFormat(name) => {
+                write!(f, "UnsupportedFormat: no table format is named {:?}", name)
+            }
+            ColonnadeError::UnknownColumn(name) => {
+                write!(f, "UnknownColumn: no column is named {:?}", name)
+            }
+            ColonnadeError::InvalidRatio(column, of, ratio) => write!(
+                f,
+                "InvalidRatio: the width of {:?} cannot be {} times that of {:?}",
+                column, ratio, of
+            ),
+            _ => write!(f, "{:?}", self),
+        }
     }
 }
 
-impl std::error::Error for ColonnadeError {}
+// a column described for an error message by its index and its name, if it has one
+fn column_label(index: usize, name: &Option<String>) -> String {
+    match name {
+        Some(name) => format!("column {} ({})", index, name),
+        None => format!("column {}", index),
+    }
+}
 
-/// Alignments left-to-right one can apply to columns of text.
-#[derive(Debug, Clone)]
-pub enum Alignment {
-    /// Left justification -- the default alignment
-    Left,
-    /// Right justification
-    Right,
-    /// Centering
-    Center,
-    /// Justified to both margins; the last line in a column is left-justified
-    Justify,
+/// The space a column requires, as reported by `ColonnadeError::InsufficientSpace`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnSpace {
+    /// the index of the column
+    pub index: usize,
+    /// the name of the column, if it has one
+    pub name: Option<String>,
+    /// the width of the margins to the left and right of the column
+    pub margin: usize,
+    /// the column's horizontal padding
+    pub padding: usize,
+    /// the narrowest the column can be, including its padding
+    pub minimum: usize,
 }
 
-/// Vertical alignments of text within a column.
+/// An account of the space required by the displayed columns, as reported by `ColonnadeError::InsufficientSpace`.
 #[derive(Debug, Clone, PartialEq)]
-pub enum VerticalAlignment {
-    /// the default vertical alignment
-    Top,
-    Middle,
-    Bottom,
+pub struct SpaceDiagnostics {
+    /// the width of the viewport
+    pub viewport: usize,
+    /// the narrowest viewport in which the columns could be laid out
+    pub required: usize,
+    /// the space required by each displayed column
+    pub columns: Vec<ColumnSpace>,
 }
 
-/// A struct holding formatting information for a particular column.
-#[derive(Debug, Clone)]
-pub struct Column {
-    index: usize,
-    alignment: Alignment,
-    vertical_alignment: VerticalAlignment,
-    left_margin: usize,
-    /// the width of the column excluding any left margin
-    pub width: usize,
-    priority: usize,
-    min_width: Option<usize>,
-    max_width: Option<usize>,
-    padding_left: usize,
-    padding_right: usize,
-    padding_top: usize,
-    padding_bottom: usize,
-    hyphenate: bool,
-    adjusted: bool,
+impl std::error::Error for ColonnadeError {}
+
+/// A value to display in a table cell.
+///
+/// Rows of data are converted into cells by [`ToRow`](trait.ToRow.html). A cell may borrow its
+/// text from the data it was made from rather than copying it; see
+/// [`into_owned`](#method.into_owned).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cell<'a> {
+    text: Cow<'a, str>,
+    numeric: bool,
+    clamped: bool,
+    // whether the first word is pinned to the left edge and the rest right-aligned
+    pinned: bool,
+    // a drawing fitted to the column's width after layout
+    graphic: Option<Graphic>,
+    // the number in the cell before any formatting, if it holds one
+    value: Option<f64>,
+    style: Option<Style>,
+    // the depth of a node in a tree
+    depth: Option<usize>,
+    // the guides drawn before the first and following lines of a tree node
+    guides: Option<(String, String)>,
+    // whether the cell is a group header spanning the table
+    spanning: bool,
+    // the index of the group of rows the cell's row belongs to, if rows are grouped
+    group: Option<usize>,
+    // whether the cell is in a row of subtotals or totals
+    total: bool,
+    // whether the cell is in an empty row standing for a separator
+    rule: bool,
+    // whether the cell's text is written right to left
+    rtl: bool,
+    // whether the text is already broken into lines at its line breaks
+    broken: bool,
+    // overrides the vertical alignment of the column
+    vertical_alignment: Option<VerticalAlignment>,
 }
 
-impl Column {
-    fn default(index: usize) -> Column {
-        Column {
-            index: index,
-            alignment: Alignment::Left,
-            vertical_alignment: VerticalAlignment::Top,
-            left_margin: 1,
-            width: 0, // claimed width
-            priority: usize::max_value(),
-            min_width: None,
-            max_width: None,
-            padding_left: 0,
-            padding_right: 0,
-            padding_top: 0,
-            padding_bottom: 0,
-            hyphenate: true,
-            adjusted: false,
-        }
-    }
-    fn horizontal_padding(&self) -> usize {
-        self.padding_left + self.padding_right
-    }
-    fn vertical_padding(&self) -> usize {
-        self.padding_top + self.padding_bottom
-    }
-    fn minimum_width(&self) -> usize {
-        let w1 = self.horizontal_padding();
-        let w2 = self.min_width.unwrap_or(w1);
-        if w2 > w1 {
-            w2
-        } else {
-            w1
-        }
-    }
-    fn effective_width(&self) -> usize {
-        let w = if self.max_width.unwrap_or(self.width) < self.width {
-            self.max_width.unwrap()
-        } else {
-            self.width
-        };
-        let m = self.minimum_width();
-        if m > w {
-            m
-        } else {
-            w
-        }
-    }
-    fn inner_width(&self) -> usize {
-        self.width - self.padding_right
-    }
-    fn hyphenating(&self) -> bool {
-        self.hyphenate && self.inner_width() > 1
-    }
-    fn is_shrinkable(&self) -> bool {
-        self.minimum_width() < self.width
-    }
-    // shrink as close to width as possible
-    fn shrink(&mut self, width: usize) {
-        let m = self.minimum_width();
-        self.width = if m > width { m } else { width }
-    }
-    // attempt to shrink by decrease amount
-    // returns whether there was any shrinkage
-    fn shrink_by(&mut self, decrease: usize) -> bool {
-        if self.is_shrinkable() {
-            // you can't shrink all the way to 0
-            let decrease = if decrease >= self.width {
-                1
-            } else {
-                self.width - decrease
-            };
-            let before = self.width;
-            self.shrink(decrease);
-            before != self.width
-        } else {
-            false
+// drawings whose size depends on the width of their column
+#[derive(Debug, Clone, PartialEq)]
+enum Graphic {
+    Sparkline(Vec<f64>),
+    Progress(f64, ProgressStyle),
+}
+
+impl Graphic {
+    // the width the graphic would like before layout
+    fn natural_width(&self) -> usize {
+        match self {
+            Graphic::Sparkline(values) => values.len(),
+            Graphic::Progress(..) => 17, // [##########] 100%
         }
     }
-    fn is_expandable(&self) -> bool {
-        self.max_width.unwrap_or(usize::max_value()) > self.width
-    }
-    // expands column as much as possible to fit width and as much as necessary to match min_width
-    fn expand(&mut self, width: usize) -> bool {
-        if width <= self.width {
-            return false;
-        }
-        let change = if self.max_width.unwrap_or(width) < width {
-            self.max_width.unwrap()
-        } else if self.minimum_width() > width {
-            self.minimum_width()
-        } else {
-            width
-        };
-        let changed = self.width != change;
-        if changed {
-            self.width = change
+    // render the graphic at the given width
+    fn draw(&self, width: usize) -> String {
+        match self {
+            Graphic::Progress(fraction, style) => {
+                let percent = format!("{:.0}%", fraction * 100.0);
+                // leave room for the widest percentage so the bars in a column are of a length
+                let bar = width.saturating_sub(7);
+                if bar == 0 {
+                    return percent;
+                }
+                let filled = (fraction * bar as f64).round() as usize;
+                let mut text = String::new();
+                text.push(style.open);
+                text += &style.fill.to_string().repeat(filled);
+                text += &style.empty.to_string().repeat(bar - filled);
+                text.push(style.close);
+                text + " " + &percent
+            }
+            Graphic::Sparkline(values) => {
+                const TICKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+                let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+                let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+                let width = width.max(1).min(values.len());
+                // average the values into as many buckets as there is room for
+                (0..width)
+                    .map(|j| {
+                        let bucket =
+                            &values[j * values.len() / width..(j + 1) * values.len() / width];
+                        let mean = bucket.iter().sum::<f64>() / bucket.len() as f64;
+                        let level = if max > min {
+                            ((mean - min) / (max - min) * 7.0).round() as usize
+                        } else {
+                            0
+                        };
+                        TICKS[level.min(7)]
+                    })
+                    .collect()
+            }
         }
-        changed
-    }
-    fn expand_by(&mut self, increase: usize) -> bool {
-        self.expand(self.width + increase)
     }
-    fn outer_width(&self) -> usize {
-        self.left_margin + self.effective_width()
-    }
-    fn blank_line(&self) -> String {
-        " ".repeat(self.width)
-    }
-    fn margin(&self) -> String {
-        " ".repeat(self.left_margin)
-    }
-    /// Assign a particular priority to the column.
+}
+
+impl<'a> Cell<'a> {
+    /// Construct a cell displaying the given value.
     ///
-    /// Priority determines the order in which columns give up space when the viewport lacks sufficient
-    /// space to display all columns without wrapping. Lower priority columns give up space first.
+    /// # Arguments
+    ///
+    /// * `value` - The value to display.
+    pub fn new<T: ToString>(value: T) -> Cell<'a> {
+        Cell::from(value.to_string())
+    }
+    /// Construct a cell displaying text already broken into lines. The column is laid out to fit
+    /// the longest line, and each line begins a line of the cell rather than being run together
+    /// with its neighbors. Only a line wider than the column is wrapped further.
     ///
     /// # Arguments
     ///
-    /// * `priority` - The column's priority. Lower numbers confer higher priority; 0 is the highest priority.
+    /// * `lines` - The lines of text to display.
     ///
     /// # Example
     ///
     /// ```rust
     /// # extern crate colonnade;
-    /// # use colonnade::Colonnade;
+    /// # use colonnade::{Cell, Colonnade};
     /// # use std::error::Error;
     /// # fn demo() -> Result<(), Box<dyn Error>> {
-    /// let mut colonnade = Colonnade::new(4, 100)?;
-    /// // assign all columns the highest priority
-    /// colonnade.priority(0);
-    /// // now demote the last column
-    /// colonnade.columns[3].priority(1);
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// let poem = Cell::lines(&["roses are red", "violets blue"]);
+    /// let data = vec![vec![Cell::new("poem"), poem]];
+    /// assert_eq!(
+    ///     vec!["poem roses are red", "     violets blue "],
+    ///     colonnade.tabulate(&data)?
+    /// );
     /// # Ok(()) }
     /// ```
-    pub fn priority(&mut self, priority: usize) -> &mut Self {
-        self.adjusted = false;
-        self.priority = priority;
-        self
+    pub fn lines<I, S>(lines: I) -> Cell<'a>
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        Cell::from(lines.into_iter().map(|l| l.to_string()).collect::<Vec<_>>())
     }
-    /// Assign the same maximum width to all columns. By default columns have no maximum width.
+    /// Construct a cell displaying a sequence of numbers as a one-line sparkline, `▁▂▅▇▆▃`. If
+    /// the column is laid out narrower than the sequence, neighboring numbers are averaged to fit.
+    /// Non-finite numbers are ignored.
     ///
     /// # Arguments
     ///
-    /// * `max_width` - The common maximum width.
-    ///
-    /// # Errors
-    ///
-    /// * `ColonnadeError::MinGreaterThanMax` - Assigning a maximum width in conflict with some assigned minimum width.
-    /// * `ColonnadeError::OutOfBounds` - Attemping to assign a maximum width to a column that does not exist.
+    /// * `values` - The numbers to draw.
     ///
     /// # Example
     ///
     /// ```rust
     /// # extern crate colonnade;
-    /// # use colonnade::Colonnade;
+    /// # use colonnade::{Cell, Colonnade};
     /// # use std::error::Error;
     /// # fn demo() -> Result<(), Box<dyn Error>> {
-    /// let mut colonnade = Colonnade::new(4, 100)?;
-    /// // assign the first column a maximum width of 20
-    /// colonnade.columns[0].max_width(20)?;
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// let data = vec![vec![Cell::new("load"), Cell::sparkline(&[1, 2, 5, 7, 6, 3])]];
+    /// assert_eq!(vec!["load ▁▂▆█▇▃"], colonnade.tabulate(&data)?);
+    /// colonnade.columns[1].fixed_width(3)?;
+    /// assert_eq!(vec!["load ▂▇▅"], colonnade.tabulate(&data)?);
     /// # Ok(()) }
     /// ```
-    pub fn max_width(&mut self, max_width: usize) -> Result<&mut Self, ColonnadeError> {
-        if self.min_width.unwrap_or(max_width) > max_width {
-            Err(ColonnadeError::MinGreaterThanMax(self.index))
-        } else {
-            self.max_width = Some(max_width);
-            self.adjusted = false;
-            Ok(self)
+    pub fn sparkline<T: Into<f64> + Copy>(values: &[T]) -> Cell<'a> {
+        let values: Vec<f64> = values
+            .iter()
+            .map(|&v| v.into())
+            .filter(|v: &f64| v.is_finite())
+            .collect();
+        if values.is_empty() {
+            return Cell::new("");
         }
+        Cell::graphic(Graphic::Sparkline(values))
     }
-    /// Assign a particular minimum width to a particular column. By default columns have no minimum width.
+    /// Construct a cell displaying a progress bar, `[#####.....] 50%`, sized to the width of its
+    /// column after layout.
     ///
     /// # Arguments
     ///
-    /// * `min_width` - The common minimum width.
-    ///
-    /// # Errors
-    ///
-    /// * `ColonnadeError::MinGreaterThanMax` - Assigning a maximum width in conflict with some assigned minimum width.
+    /// * `fraction` - The fraction complete, from 0 to 1.
     ///
     /// # Example
     ///
     /// ```rust
     /// # extern crate colonnade;
-    /// # use colonnade::Colonnade;
+    /// # use colonnade::{Cell, Colonnade};
     /// # use std::error::Error;
     /// # fn demo() -> Result<(), Box<dyn Error>> {
-    /// let mut colonnade = Colonnade::new(4, 100)?;
-    /// // assign the first column a minimum width of 20
-    /// colonnade.columns[0].min_width(20)?;
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// colonnade.columns[1].fixed_width(12)?;
+    /// let data = vec![
+    ///     vec![Cell::new("download"), Cell::progress(0.4)],
+    ///     vec![Cell::new("install"), Cell::progress(1.0)],
+    /// ];
+    /// assert_eq!(
+    ///     vec!["download [##...]  40%", "install  [#####] 100%"],
+    ///     colonnade.tabulate(&data)?
+    /// );
     /// # Ok(()) }
     /// ```
-    pub fn min_width(&mut self, min_width: usize) -> Result<&mut Self, ColonnadeError> {
-        if self.max_width.unwrap_or(min_width) < min_width {
-            return Err(ColonnadeError::MinGreaterThanMax(self.index));
-        }
-        self.width = min_width;
-        self.min_width = Some(min_width);
-        self.adjusted = false;
-        Ok(self)
+    pub fn progress(fraction: f64) -> Cell<'a> {
+        let fraction = if fraction.is_nan() {
+            0.0
+        } else {
+            fraction.clamp(0.0, 1.0)
+        };
+        let mut cell = Cell::graphic(Graphic::Progress(fraction, ProgressStyle::default()));
+        // the bar is pinned to the left and the percentage right-aligned
+        cell.pinned = true;
+        cell.numeric = true;
+        cell
     }
-    /// Assign a particular maximum and minimum width to a particular column. By default columns have neither a maximum nor a minimum width.
+    /// Set the characters with which a [progress bar](#method.progress) is drawn. This has no
+    /// effect on other cells.
     ///
     /// # Arguments
     ///
-    /// * `width` - The common width.
-    ///
-    /// # Errors
-    ///
-    /// This method is a convenience method which assigns the column in question the same maximum and minimum width. Therefore
-    /// the errors thrown are those thrown by [`max_width`](#method.max_width) and [`min_width`](#method.min_width).
+    /// * `style` - The characters of the progress bar.
     ///
     /// # Example
     ///
     /// ```rust
     /// # extern crate colonnade;
-    /// # use colonnade::Colonnade;
-    /// # use std::error::Error;
-    /// # fn demo() -> Result<(), Box<dyn Error>> {
-    /// let mut colonnade = Colonnade::new(4, 100)?;
-    /// // assign the first column a width of 20
-    /// colonnade.columns[0].fixed_width(20)?;
-    /// # Ok(()) }
+    /// # use colonnade::{Cell, ProgressStyle};
+    /// let cell = Cell::progress(0.25).progress_style(ProgressStyle::new('|', '█', '░', '|'));
     /// ```
-    pub fn fixed_width(&mut self, width: usize) -> Result<&mut Self, ColonnadeError> {
-        self.min_width = None;
-        self.max_width = None;
-        match self.min_width(width) {
-            Err(e) => return Err(e),
-            Ok(_) => (),
+    pub fn progress_style(mut self, style: ProgressStyle) -> Cell<'a> {
+        if let Some(Graphic::Progress(_, s)) = &mut self.graphic {
+            *s = style;
         }
-        match self.max_width(width) {
-            Err(e) => return Err(e),
-            Ok(_) => (),
+        if let Some(graphic) = &self.graphic {
+            self.text = graphic.draw(graphic.natural_width()).into();
         }
-        Ok(self)
+        self
     }
-    /// Remove maximum or minimum column widths from a particular column.
+    /// Construct a cell displaying a node in a tree. Consecutive node cells in a column are drawn
+    /// as a hierarchy, each node's label preceded by `├─`, `└─`, and `│ ` guides connecting it to
+    /// its parent and siblings. A node's parent is the nearest preceding node of lesser depth.
+    /// The guides continue through the wrapped lines of a label.
+    ///
+    /// # Arguments
+    ///
+    /// * `depth` - The depth of the node; the roots of the tree have depth 0.
+    /// * `label` - The text to display.
     ///
     /// # Example
     ///
     /// ```rust
     /// # extern crate colonnade;
-    /// # use colonnade::Colonnade;
+    /// # use colonnade::{Cell, Colonnade};
     /// # use std::error::Error;
     /// # fn demo() -> Result<(), Box<dyn Error>> {
-    /// let mut colonnade = Colonnade::new(4, 100)?;
-    /// // initially assign all columns a width of 20
-    /// colonnade.fixed_width(20);
-    /// // but we want the first column to be flexible
-    /// colonnade.columns[0].clear_limits();
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// let data = vec![
+    ///     vec![Cell::node(0, "src"), Cell::new("")],
+    ///     vec![Cell::node(1, "lib.rs"), Cell::new("5.1K")],
+    ///     vec![Cell::node(1, "bin"), Cell::new("")],
+    ///     vec![Cell::node(2, "main.rs"), Cell::new("87")],
+    ///     vec![Cell::node(0, "Cargo.toml"), Cell::new("12")],
+    /// ];
+    /// assert_eq!(
+    ///     vec![
+    ///         "src             ",
+    ///         "├─lib.rs    5.1K",
+    ///         "└─bin           ",
+    ///         "  └─main.rs 87  ",
+    ///         "Cargo.toml  12  ",
+    ///     ],
+    ///     colonnade.tabulate(&data)?
+    /// );
     /// # Ok(()) }
     /// ```
-    pub fn clear_limits(&mut self) -> &mut Self {
-        self.max_width = None;
-        self.min_width = None;
-        self.adjusted = false;
+    pub fn node<T: ToString>(depth: usize, label: T) -> Cell<'a> {
+        let mut cell = Cell::new(label);
+        cell.depth = Some(depth);
+        cell
+    }
+    // the text by which the cell is measured during layout
+    fn measure(&self, widths: &Widths) -> Cow<'_, str> {
+        if self.spanning {
+            // group headers take no part in the layout of columns
+            return Cow::Borrowed("");
+        }
+        match &self.guides {
+            None if self.broken => {
+                // the widest line
+                let longest = self.text.split('\n').max_by_key(|l| true_width(l, widths));
+                Cow::Borrowed(longest.unwrap_or(""))
+            }
+            None => Cow::Borrowed(&self.text),
+            Some((first, _)) => {
+                // the guides are as wide as the label's longest word and don't break
+                let mut words = to_words(&self.text);
+                let longest = (0..words.len())
+                    .max_by_key(|&i| true_width(words[i], widths))
+                    .map(|i| words.remove(i))
+                    .unwrap_or("");
+                let mut measure = "-".repeat(true_width(first, widths));
+                measure += longest;
+                for w in words {
+                    measure += " ";
+                    measure += w;
+                }
+                Cow::Owned(measure)
+            }
+        }
+    }
+    // the label of a tree node wrapped to the given width and preceded by its guides
+    fn branch(&self, width: usize, widths: &Widths) -> Option<Vec<String>> {
+        let (first, rest) = self.guides.as_ref()?;
+        let lines = wrap(
+            &self.text,
+            width.saturating_sub(true_width(first, widths)),
+            widths,
+        )
+        .into_iter()
+        .enumerate()
+        .map(|(i, line)| format!("{}{}", if i == 0 { first } else { rest }, line))
+        .collect();
+        Some(lines)
+    }
+    // the lines of text already broken into lines, each wrapped to the given width if too wide
+    fn broken_lines(&self, width: usize, widths: &Widths) -> Option<Vec<String>> {
+        if !self.broken || self.guides.is_some() {
+            return None;
+        }
+        let lines = self
+            .text
+            .split('\n')
+            .flat_map(|line| {
+                if true_width(line, widths) <= width {
+                    vec![line.to_string()]
+                } else {
+                    wrap(line, width, widths)
+                }
+            })
+            .collect();
+        Some(lines)
+    }
+    /// Copy any text the cell borrows, so it no longer depends on the data it was made from.
+    pub fn into_owned(self) -> Cell<'static> {
+        Cell {
+            text: Cow::Owned(self.text.into_owned()),
+            numeric: self.numeric,
+            clamped: self.clamped,
+            pinned: self.pinned,
+            graphic: self.graphic,
+            value: self.value,
+            style: self.style,
+            depth: self.depth,
+            guides: self.guides,
+            spanning: self.spanning,
+            group: self.group,
+            total: self.total,
+            rule: self.rule,
+            rtl: self.rtl,
+            broken: self.broken,
+            vertical_alignment: self.vertical_alignment,
+        }
+    }
+    // a copy of the cell borrowing its text
+    fn borrowed(&self) -> Cell<'_> {
+        Cell {
+            text: Cow::Borrowed(&self.text),
+            numeric: self.numeric,
+            clamped: self.clamped,
+            pinned: self.pinned,
+            graphic: self.graphic.clone(),
+            value: self.value,
+            style: self.style.clone(),
+            depth: self.depth,
+            guides: self.guides.clone(),
+            spanning: self.spanning,
+            group: self.group,
+            total: self.total,
+            rule: self.rule,
+            rtl: self.rtl,
+            broken: self.broken,
+            vertical_alignment: self.vertical_alignment.clone(),
+        }
+    }
+    // a cell displaying a graphic at its natural width until it is fitted to its column
+    fn graphic(graphic: Graphic) -> Cell<'a> {
+        let mut cell = Cell::from(graphic.draw(graphic.natural_width()));
+        cell.graphic = Some(graphic);
+        cell
+    }
+    /// Mark the cell as holding a number. Unless its column has an explicit
+    /// [`alignment`](struct.Column.html#method.alignment), a numeric cell is right-aligned.
+    ///
+    /// # Arguments
+    ///
+    /// * `numeric` - Whether the cell holds a number.
+    pub fn numeric(mut self, numeric: bool) -> Cell<'a> {
+        self.numeric = numeric;
         self
     }
-    /// Assign a particular column a particular alignment. The default alignment is left.
+    /// Align the cell vertically within its row regardless of the
+    /// [vertical alignment](struct.Column.html#method.vertical_alignment) of its column, so, for
+    /// instance, one tall cell may be centered while the rest of its column is top-aligned.
     ///
     /// # Arguments
     ///
-    /// * `alignment` - The desired alignment.
+    /// * `vertical_alignment` - The desired alignment.
     ///
     /// # Example
     ///
     /// ```rust
     /// # extern crate colonnade;
-    /// # use colonnade::{Alignment,Colonnade};
+    /// # use colonnade::{Cell, Colonnade, VerticalAlignment};
     /// # use std::error::Error;
     /// # fn demo() -> Result<(), Box<dyn Error>> {
-    /// let mut colonnade = Colonnade::new(4, 100)?;
-    /// // the first column should be right-aligned (it's numeric)
-    /// colonnade.columns[0].alignment(Alignment::Right);
+    /// let mut colonnade = Colonnade::new(2, 9)?;
+    /// let data = vec![
+    ///     vec![Cell::new("a"), Cell::new("one two three")],
+    ///     vec![Cell::new("b").vertical_alignment(VerticalAlignment::Middle), Cell::new("four five six")],
+    /// ];
+    /// assert_eq!(
+    ///     vec!["a  one   ", "   two   ", "   three ", "   four  ", "b  five  ", "   six   "],
+    ///     colonnade.tabulate(&data)?
+    /// );
     /// # Ok(()) }
     /// ```
-    pub fn alignment(&mut self, alignment: Alignment) -> &mut Self {
-        self.alignment = alignment;
+    pub fn vertical_alignment(mut self, vertical_alignment: VerticalAlignment) -> Cell<'a> {
+        self.vertical_alignment = Some(vertical_alignment);
         self
     }
-    /// Assign a particular column a particular vertical alignment. The default alignment is top.
+    /// Style the cell. This is the topmost layer of the styles which are
+    /// [resolved](struct.Colonnade.html#method.default_style) into the style of a cell.
     ///
     /// # Arguments
     ///
-    /// * `vertical_alignment` - The desired alignment.
+    /// * `style` - The style of the cell.
     ///
     /// # Example
     ///
     /// ```rust
     /// # extern crate colonnade;
-    /// # use colonnade::{Alignment,VerticalAlignment,Colonnade};
+    /// # use colonnade::{Cell, Colonnade, Style};
     /// # use std::error::Error;
     /// # fn demo() -> Result<(), Box<dyn Error>> {
-    /// let mut colonnade = Colonnade::new(4, 100)?;
-    /// // the first column should be right-aligned (it's numeric)
-    /// colonnade.columns[0].vertical_alignment(VerticalAlignment::Middle);
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// let data = vec![vec![Cell::new("disk"), Cell::new("full").style(Style::new().bold(true))]];
+    /// assert_eq!(vec!["disk \u{1b}[1mfull\u{1b}[0m"], colonnade.tabulate(&data)?);
     /// # Ok(()) }
     /// ```
-    pub fn vertical_alignment(&mut self, vertical_alignment: VerticalAlignment) -> &mut Self {
-        self.vertical_alignment = vertical_alignment;
+    pub fn style(mut self, style: Style) -> Cell<'a> {
+        self.style = Some(style);
         self
     }
-    /// Assign a particular column a particular left margin. The left margin is a number of blank spaces
-    /// before the content of the column. By default the first column has a left margin of 0
-    /// and the other columns have a left margin of 1.
+    /// The text the cell displays.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+/// The characters with which a [progress bar](struct.Cell.html#method.progress) is drawn. None
+/// should be whitespace, where the bar might be broken.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProgressStyle {
+    open: char,
+    fill: char,
+    empty: char,
+    close: char,
+}
+
+impl ProgressStyle {
+    /// Construct a progress bar style.
     ///
     /// # Arguments
     ///
-    /// * `left_margin` - The width in blank spaces of the desired margin.
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// # extern crate colonnade;
-    /// # use colonnade::{Alignment,Colonnade};
-    /// # use std::error::Error;
-    /// # fn demo() -> Result<(), Box<dyn Error>> {
-    /// let mut colonnade = Colonnade::new(4, 100)?;
-    /// colonnade.columns[0].left_margin(2);
-    /// # Ok(()) }
-    /// ```
-    pub fn left_margin(&mut self, left_margin: usize) -> &mut Self {
-        self.left_margin = left_margin;
-        self.adjusted = false;
+    /// * `open` - The left end of the bar.
+    /// * `fill` - The character filling the completed part of the bar.
+    /// * `empty` - The character filling the remainder of the bar.
+    /// * `close` - The right end of the bar.
+    pub fn new(open: char, fill: char, empty: char, close: char) -> ProgressStyle {
+        ProgressStyle {
+            open,
+            fill,
+            empty,
+            close,
+        }
+    }
+}
+
+/// `[#####.....]`
+impl Default for ProgressStyle {
+    fn default() -> Self {
+        ProgressStyle::new('[', '#', '.', ']')
+    }
+}
+
+/// Text becomes the text of a cell without being copied, borrowed or owned as it is given.
+impl<'a> From<Cow<'a, str>> for Cell<'a> {
+    fn from(text: Cow<'a, str>) -> Cell<'a> {
+        Cell {
+            text,
+            numeric: false,
+            clamped: false,
+            pinned: false,
+            graphic: None,
+            value: None,
+            style: None,
+            depth: None,
+            guides: None,
+            spanning: false,
+            group: None,
+            total: false,
+            rule: false,
+            rtl: false,
+            broken: false,
+            vertical_alignment: None,
+        }
+    }
+}
+
+/// A `String` becomes the text of a cell without being copied.
+impl From<String> for Cell<'_> {
+    fn from(text: String) -> Self {
+        Cell::from(Cow::Owned(text))
+    }
+}
+
+/// A cell borrows the text of a `&str` rather than copying it.
+impl<'a> From<&'a str> for Cell<'a> {
+    fn from(text: &'a str) -> Cell<'a> {
+        Cell::from(Cow::Borrowed(text))
+    }
+}
+
+/// A cell borrows the text of a `&String` rather than copying it.
+impl<'a> From<&'a String> for Cell<'a> {
+    fn from(text: &'a String) -> Cell<'a> {
+        Cell::from(text.as_str())
+    }
+}
+
+/// Lines of text become a cell displaying them with their line breaks.
+///
+/// See [`Cell::lines`](struct.Cell.html#method.lines).
+impl From<Vec<String>> for Cell<'_> {
+    fn from(lines: Vec<String>) -> Self {
+        let mut cell = Cell::from(lines.join("\n"));
+        cell.broken = true;
+        cell
+    }
+}
+
+/// `None` becomes an empty cell, which is displayed as the column's
+/// [placeholder](struct.Column.html#method.empty_placeholder), if any.
+impl<T: ToCell> From<Option<T>> for Cell<'_> {
+    fn from(value: Option<T>) -> Self {
+        value.to_cell().into_owned()
+    }
+}
+
+/// The conversion of a single value into a cell of a tuple row.
+///
+/// Primitive numbers become [`numeric`](struct.Cell.html#method.numeric) cells, so by default
+/// they are right-aligned; strings, characters, and booleans become plain text. Implement
+/// `ToCell` to put your own types in tuple rows, marking a newtype around a number as numeric.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate colonnade;
+/// # use colonnade::{Cell, Colonnade, ToCell};
+/// # use std::error::Error;
+/// struct Meters(f64);
+///
+/// impl ToCell for Meters {
+///     fn to_cell(&self) -> Cell<'_> {
+///         Cell::new(format!("{}m", self.0)).numeric(true)
+///     }
+/// }
+///
+/// # fn demo() -> Result<(), Box<dyn Error>> {
+/// let mut colonnade = Colonnade::new(2, 80)?;
+/// let lines = colonnade.tabulate([("Eiffel", Meters(330.0)), ("Big Ben", Meters(96.0))])?;
+/// assert_eq!(vec!["Eiffel  330m", "Big Ben  96m"], lines);
+/// # Ok(()) }
+/// ```
+pub trait ToCell {
+    /// Convert the value into a cell, which may borrow its text from the value.
+    fn to_cell(&self) -> Cell<'_>;
+}
+
+macro_rules! numeric_cell {
+    ($($t:ty),+) => {
+        $(
+            impl ToCell for $t {
+                fn to_cell(&self) -> Cell<'_> {
+                    Cell::new(self).numeric(true)
+                }
+            }
+        )+
+    };
+}
+
+numeric_cell!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
+macro_rules! text_cell {
+    ($($t:ty),+) => {
+        $(
+            impl ToCell for $t {
+                fn to_cell(&self) -> Cell<'_> {
+                    Cell::new(self)
+                }
+            }
+        )+
+    };
+}
+
+text_cell!(char, bool);
+
+// text is borrowed rather than copied
+macro_rules! borrowed_cell {
+    ($($t:ty),+) => {
+        $(
+            impl ToCell for $t {
+                fn to_cell(&self) -> Cell<'_> {
+                    Cell::from(&self[..])
+                }
+            }
+        )+
+    };
+}
+
+borrowed_cell!(str, String, Cow<'_, str>);
+
+impl ToCell for Cell<'_> {
+    fn to_cell(&self) -> Cell<'_> {
+        self.borrowed()
+    }
+}
+
+/// `None` becomes an empty cell.
+impl<T: ToCell> ToCell for Option<T> {
+    fn to_cell(&self) -> Cell<'_> {
+        match self {
+            Some(value) => value.to_cell(),
+            None => Cell::new(""),
+        }
+    }
+}
+
+impl<T: ToCell + ?Sized> ToCell for &T {
+    fn to_cell(&self) -> Cell<'_> {
+        (**self).to_cell()
+    }
+}
+
+impl<T: ToCell + ?Sized> ToCell for Box<T> {
+    fn to_cell(&self) -> Cell<'_> {
+        (**self).to_cell()
+    }
+}
+
+/// The conversion of a row of data into cells.
+///
+/// Sequences of values implementing `ToString` or of `Cell`s -- vectors, arrays, and slices -- and
+/// tuples of up to twelve values implementing [`ToCell`](trait.ToCell.html) are rows. The cells of
+/// a tuple holding primitive numbers are marked [`numeric`](struct.Cell.html#method.numeric), so
+/// by default they are right-aligned. The cells of tuples and of sequences of `Cell`s borrow the
+/// text of strings in the row rather than copying it.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate colonnade;
+/// # use colonnade::Colonnade;
+/// # use std::error::Error;
+/// # fn demo() -> Result<(), Box<dyn Error>> {
+/// let mut colonnade = Colonnade::new(3, 80)?;
+/// let data = vec![("apples", 3, 0.5), ("kumquats", 12, 10.25)];
+/// let lines = colonnade.tabulate(&data)?;
+/// assert_eq!(vec!["apples    3   0.5", "kumquats 12 10.25"], lines);
+/// # Ok(()) }
+/// ```
+///
+/// Implement `ToRow` to tabulate your own types directly, keeping the conversion next to the type.
+///
+/// ```rust
+/// # extern crate colonnade;
+/// # use colonnade::{Cell, Colonnade, ToRow};
+/// # use std::error::Error;
+/// struct Planet {
+///     name: &'static str,
+///     moons: usize,
+/// }
+///
+/// impl ToRow for Planet {
+///     fn to_row(&self) -> Vec<Cell<'_>> {
+///         vec![Cell::new(self.name), Cell::new(self.moons).numeric(true)]
+///     }
+/// }
+///
+/// # fn demo() -> Result<(), Box<dyn Error>> {
+/// let planets = vec![
+///     Planet { name: "Earth", moons: 1 },
+///     Planet { name: "Jupiter", moons: 95 },
+/// ];
+/// let mut colonnade = Colonnade::new(2, 80)?;
+/// assert_eq!(vec!["Earth    1", "Jupiter 95"], colonnade.tabulate(&planets)?);
+/// # Ok(()) }
+/// ```
+pub trait ToRow {
+    /// Convert the row into a vector of cells, one per column. The cells may borrow their text
+    /// from the row.
+    fn to_row(&self) -> Vec<Cell<'_>>;
+}
+
+impl<W: ToString> ToRow for [W] {
+    fn to_row(&self) -> Vec<Cell<'_>> {
+        self.iter().map(|w| Cell::from(w.to_string())).collect()
+    }
+}
+
+impl<W: ToString, const N: usize> ToRow for [W; N] {
+    fn to_row(&self) -> Vec<Cell<'_>> {
+        self.iter().map(|w| Cell::from(w.to_string())).collect()
+    }
+}
+
+impl<W: ToString> ToRow for Vec<W> {
+    fn to_row(&self) -> Vec<Cell<'_>> {
+        self.iter().map(|w| Cell::from(w.to_string())).collect()
+    }
+}
+
+impl ToRow for [Cell<'_>] {
+    fn to_row(&self) -> Vec<Cell<'_>> {
+        self.iter().map(Cell::borrowed).collect()
+    }
+}
+
+impl<const N: usize> ToRow for [Cell<'_>; N] {
+    fn to_row(&self) -> Vec<Cell<'_>> {
+        self.iter().map(Cell::borrowed).collect()
+    }
+}
+
+impl ToRow for Vec<Cell<'_>> {
+    fn to_row(&self) -> Vec<Cell<'_>> {
+        self.iter().map(Cell::borrowed).collect()
+    }
+}
+
+impl<R: ToRow + ?Sized> ToRow for &R {
+    fn to_row(&self) -> Vec<Cell<'_>> {
+        (**self).to_row()
+    }
+}
+
+/// Descriptions of the columns of a type whose values are rows, typically implemented with
+/// `#[derive(Tabular)]` and the `derive` feature.
+pub trait Tabular: ToRow {
+    /// The header of each column.
+    fn headers() -> Vec<String>;
+    /// The alignment of each column; `None` for the default alignment. By default all columns have
+    /// the default alignment.
+    fn alignments() -> Vec<Option<Alignment>> {
+        vec![]
+    }
+}
+
+#[cfg(feature = "derive")]
+pub use colonnade_derive::Tabular;
+
+// support for code generated by colonnade_derive: a field whose type implements `ToCell` is
+// converted by it, any other field by its `Display` implementation
+#[doc(hidden)]
+pub mod __private {
+    use super::{Cell, ToCell};
+    use std::fmt;
+
+    pub struct Field<'a, T: ?Sized>(pub &'a T);
+
+    pub trait ViaToCell<'a> {
+        fn cell(&self) -> Cell<'a>;
+    }
+
+    impl<'a, T: ToCell + ?Sized> ViaToCell<'a> for Field<'a, T> {
+        fn cell(&self) -> Cell<'a> {
+            self.0.to_cell()
+        }
+    }
+
+    pub trait ViaDisplay<'a> {
+        fn cell(&self) -> Cell<'a>;
+    }
+
+    impl<'a, T: fmt::Display + ?Sized> ViaDisplay<'a> for &Field<'a, T> {
+        fn cell(&self) -> Cell<'a> {
+            Cell::new(self.0)
+        }
+    }
+}
+
+macro_rules! tuple_row {
+    ($($t:ident $i:tt),+) => {
+        impl<$($t: ToCell),+> ToRow for ($($t,)+) {
+            fn to_row(&self) -> Vec<Cell<'_>> {
+                vec![$(self.$i.to_cell()),+]
+            }
+        }
+    };
+}
+
+tuple_row!(A 0);
+tuple_row!(A 0, B 1);
+tuple_row!(A 0, B 1, C 2);
+tuple_row!(A 0, B 1, C 2, D 3);
+tuple_row!(A 0, B 1, C 2, D 3, E 4);
+tuple_row!(A 0, B 1, C 2, D 3, E 4, F 5);
+tuple_row!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+tuple_row!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+tuple_row!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+tuple_row!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+tuple_row!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+tuple_row!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
+
+/// Alignments left-to-right one can apply to columns of text.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Alignment {
+    /// Left justification -- the default alignment
+    Left,
+    /// Right justification
+    Right,
+    /// Centering
+    Center,
+    /// Justified to both margins; the last line in a column is left-justified
+    Justify,
+}
+
+/// What to do when the viewport is too narrow to display all the columns.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Degradation {
+    /// Return `ColonnadeError::InsufficientSpace` -- the default
+    Error,
+    /// Drop the lowest priority columns until the remainder fit in the viewport
+    DropColumns,
+    /// Drop columns as with `DropColumns` and append a line such as `+2 more` noting how many were dropped
+    DropColumnsWithNote,
+}
+
+/// How column widths are chosen when the data does not fit the viewport at its natural widths.
+///
+/// See [`Colonnade::layout_strategy`](struct.Colonnade.html#method.layout_strategy).
+#[derive(Debug, Clone, PartialEq)]
+pub enum LayoutStrategy {
+    /// Shrink columns to their longest words and then truncate them, lowest priority first,
+    /// giving surplus space back to the highest priority columns -- the default
+    Legacy,
+    /// Redistribute width among the columns to minimize the number of lines in the table, so no
+    /// one column wraps into a tall ribbon while others have room to spare
+    MinHeight,
+    /// Give each column room for its longest word and share the remaining width among the
+    /// columns in proportion to how much more each would need to display its text on one line
+    Proportional,
+}
+
+/// What to do when the columns have already been laid out and new data does not fit them.
+///
+/// A cell overflows its column if its text cannot be displayed on a single line at the column's width.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OverflowPolicy {
+    /// Wrap the text onto additional lines -- the default
+    Wrap,
+    /// Cut the text off at the column's width, marking the cut with an ellipsis
+    Truncate,
+    /// Discard the existing column widths and lay out the columns afresh, so text is never wrapped
+    /// or split merely because earlier data was narrower; data which fits the existing widths
+    /// keeps them
+    ///
+    /// See [`Colonnade::auto_relayout`](struct.Colonnade.html#method.auto_relayout).
+    Relayout,
+    /// Widen the overflowing columns as far as the viewport allows, never narrowing any column, so
+    /// lines already displayed remain aligned with those that follow; text that still doesn't
+    /// fit is wrapped
+    ///
+    /// See [`Colonnade::grew`](struct.Colonnade.html#method.grew).
+    Grow,
+    /// Return `ColonnadeError::Overflow`
+    Error,
+}
+
+/// Whether to display each row as a block of `header: value` lines rather than as a line of columns.
+///
+/// This is like the "expanded display" of `psql`. The header of each column is its
+/// [`name`](struct.Column.html#method.name) or, failing that, its index.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExpandedDisplay {
+    /// Always display rows as columns -- the default
+    Never,
+    /// Display rows as records when the columns cannot be laid out in the viewport or when some
+    /// column would be too narrow for the words in it
+    Auto,
+    /// Always display rows as records
+    Always,
+}
+
+/// What to do with rows whose length differs from the number of columns.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RaggedRows {
+    /// Return `ColonnadeError::InconsistentColumns` for any row that is too short or too long
+    Error,
+    /// Pad short rows with empty cells; return `ColonnadeError::InconsistentColumns` for long rows
+    /// -- the default
+    PadShort,
+    /// Discard the excess cells of long rows; return `ColonnadeError::InconsistentColumns` for short rows
+    TruncateLong,
+    /// Pad short rows with empty cells and discard the excess cells of long rows
+    PadOrTruncate,
+}
+
+/// How text written right to left, such as Arabic or Hebrew, is treated.
+///
+/// See [`Colonnade::bidi`](struct.Colonnade.html#method.bidi).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Bidi {
+    /// Treat all text alike -- the default
+    Ignore,
+    /// Right-align cells whose text is written right to left, unless their column has an
+    /// alignment
+    Align,
+    /// Right-align cells as with `Align`, and also wrap the text of every cell in Unicode bidi
+    /// isolates, so the direction of one cell's text doesn't disturb the order of its neighbors
+    Isolate,
+}
+
+/// How the blank lines separating rows are represented.
+///
+/// See [`Colonnade::separator`](struct.Colonnade.html#method.separator).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Separator {
+    /// An empty string
+    Empty,
+    /// Spaces as wide as the table
+    Spaces,
+    /// The given text repeated to the width of the table
+    Fill(String),
+}
+
+/// Lines framing the cells of a table: bars between the columns and rules between the rows, in
+/// the manner of the `"github"` and `"grid"` formats of python's `tabulate` library. By default
+/// nothing is drawn.
+///
+/// Bars are drawn in the column margins, one space before the text of each column and one space
+/// after the text of the last, so the margins must leave room for them. Rules follow the widths of
+/// the columns. Rows spanning the table and rows displayed as [records](enum.ExpandedDisplay.html)
+/// are not framed.
+///
+/// See [`Colonnade::borders`](struct.Colonnade.html#method.borders).
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate colonnade;
+/// # use colonnade::{Borders, Colonnade};
+/// # use std::error::Error;
+/// # fn demo() -> Result<(), Box<dyn Error>> {
+/// let mut colonnade = Colonnade::new(2, 80)?;
+/// colonnade.left_margin(2)?;
+/// colonnade.columns[1].left_margin(3).right_margin(2);
+/// colonnade.borders(Borders::new().bars('|').junction('+').edge_rules('-'));
+/// assert_eq!(
+///     vec!["+---+----+", "| a | bb |", "| c | d  |", "+---+----+"],
+///     colonnade.tabulate([["a", "bb"], ["c", "d"]])?
+/// );
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct Borders {
+    bars: Option<char>,
+    junction: Option<char>,
+    header_rule: Option<char>,
+    row_rules: Option<char>,
+    edge_rules: Option<char>,
+}
+
+impl Borders {
+    /// Start borders which draw nothing.
+    pub fn new() -> Borders {
+        Borders::default()
+    }
+    /// Draw the given character as a bar before each column and after the last.
+    pub fn bars(mut self, bar: char) -> Borders {
+        self.bars = Some(bar);
         self
     }
-    /// Assign a particular column a particular padding.
-    ///
-    /// See [`Colonnade::padding`](struct.Colonade.html#method.padding).
+    /// Draw the given character where bars cross rules. By default this is the bar itself.
+    pub fn junction(mut self, junction: char) -> Borders {
+        self.junction = Some(junction);
+        self
+    }
+    /// Draw a rule of the given character beneath the
+    /// [header row](struct.Colonnade.html#method.header_row), if it is displayed.
+    pub fn header_rule(mut self, rule: char) -> Borders {
+        self.header_rule = Some(rule);
+        self
+    }
+    /// Draw a rule of the given character between rows.
+    pub fn row_rules(mut self, rule: char) -> Borders {
+        self.row_rules = Some(rule);
+        self
+    }
+    /// Draw a rule of the given character above and below the table.
+    pub fn edge_rules(mut self, rule: char) -> Borders {
+        self.edge_rules = Some(rule);
+        self
+    }
+    // whether the borders draw anything at all
+    fn visible(&self) -> bool {
+        self.bars.is_some()
+            || self.header_rule.is_some()
+            || self.row_rules.is_some()
+            || self.edge_rules.is_some()
+    }
+    // where in a margin a bar is drawn: one space before the text of the column following it, or
+    // one space after the text of the column preceding it
+    fn bar_offset(margin: usize, before_text: bool) -> Option<usize> {
+        match margin {
+            0 => None,
+            _ if before_text => Some(margin.saturating_sub(2)),
+            _ => Some(1.min(margin - 1)),
+        }
+    }
+}
+
+/// The direction in which rows are sorted.
+///
+/// See [`Colonnade::sort_by_column`](struct.Colonnade.html#method.sort_by_column).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Order {
+    /// Smallest first
+    Asc,
+    /// Largest first
+    Desc,
+}
+
+// a function comparing cells
+type CompareFn = dyn Fn(&Cell, &Cell) -> Ordering + Send + Sync;
+
+/// A key by which rows are sorted: a column, an order, and optionally a function comparing the
+/// cells in the column.
+///
+/// See [`Colonnade::sort_by`](struct.Colonnade.html#method.sort_by).
+#[derive(Clone)]
+pub struct SortKey {
+    column: usize,
+    order: Order,
+    comparator: Option<Arc<CompareFn>>,
+}
+
+impl fmt::Debug for SortKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SortKey")
+            .field("column", &self.column)
+            .field("order", &self.order)
+            .field("comparator", &self.comparator.is_some())
+            .finish()
+    }
+}
+
+impl SortKey {
+    /// Construct a key comparing cells as numbers if both hold numbers, otherwise as text.
+    /// Numbers precede text.
     ///
     /// # Arguments
     ///
-    /// * `padding` - The width in blank spaces/lines of the desired padding.
+    /// * `column` - The index of the column to sort by.
+    /// * `order` - Whether to sort in ascending or descending order.
+    pub fn new(column: usize, order: Order) -> SortKey {
+        SortKey {
+            column,
+            order,
+            comparator: None,
+        }
+    }
+    /// Compare cells with the given function rather than by the default comparison. The order of
+    /// the key still applies: a descending key reverses the function's ordering.
     ///
-    /// # Example
+    /// # Arguments
     ///
-    /// ```rust
-    /// # extern crate colonnade;
-    /// # use colonnade::{Alignment,Colonnade};
-    /// # use std::error::Error;
-    /// # fn demo() -> Result<(), Box<dyn Error>> {
-    /// let mut colonnade = Colonnade::new(4, 100)?;
-    /// colonnade.columns[0].padding(1);
-    /// # Ok(()) }
-    /// ```
-    pub fn padding(&mut self, padding: usize) -> &mut Self {
-        self.padding_left = padding;
-        self.padding_right = padding;
-        self.padding_top = padding;
-        self.padding_bottom = padding;
-        self.adjusted = false;
+    /// * `comparator` - The function comparing two cells in the column.
+    pub fn comparator<F>(mut self, comparator: F) -> SortKey
+    where
+        F: Fn(&Cell, &Cell) -> Ordering + Send + Sync + 'static,
+    {
+        self.comparator = Some(Arc::new(comparator));
         self
     }
-    /// Assign a particular column a particular horizontal padding -- space before and after the column's text.
-    ///
-    /// See [`Colonnade::padding`](struct.Colonade.html#method.padding).
+    /// Compare the text of cells by the collation rules of a locale rather than by the order of
+    /// their bytes. Cells which hold numbers are still compared as numbers and precede text.
+    /// This replaces any [comparator](#method.comparator).
     ///
     /// # Arguments
     ///
-    /// * `padding` - The width in blank spaces/lines of the desired padding.
+    /// * `collator` - The collator for the locale whose rules apply.
     ///
     /// # Example
     ///
     /// ```rust
     /// # extern crate colonnade;
-    /// # use colonnade::{Alignment,Colonnade};
+    /// # use colonnade::{Colonnade, Order, SortKey};
+    /// # use icu_collator::{Collator, CollatorOptions};
     /// # use std::error::Error;
     /// # fn demo() -> Result<(), Box<dyn Error>> {
-    /// let mut colonnade = Colonnade::new(4, 100)?;
-    /// colonnade.columns[0].padding_horizontal(1);
+    /// let collator = Collator::try_new(&"sv".parse()?, CollatorOptions::new())?;
+    /// let mut colonnade = Colonnade::new(1, 80)?;
+    /// colonnade.sort_by(vec![SortKey::new(0, Order::Asc).collator(collator)])?;
+    /// let data = [["ö"], ["z"], ["a"]];
+    /// assert_eq!(vec!["a", "z", "ö"], colonnade.tabulate(data)?);
     /// # Ok(()) }
     /// ```
-    pub fn padding_horizontal(&mut self, padding: usize) -> &mut Self {
-        self.padding_left = padding;
-        self.padding_right = padding;
-        self.adjusted = false;
+    #[cfg(feature = "collation")]
+    pub fn collator(mut self, collator: icu_collator::Collator) -> SortKey {
+        self.comparator = Some(Arc::new(move |a: &Cell, b: &Cell| {
+            match (a.value, b.value) {
+                (None, None) => collator.compare(&a.text, &b.text),
+                _ => Colonnade::compare_cells(a, b),
+            }
+        }));
         self
     }
-    /// Assign a particular column a particular left padding -- space before the column's text.
+    // compare two rows by this key
+    fn compare(&self, a: &[Cell], b: &[Cell]) -> Ordering {
+        let (a, b) = (&a[self.column], &b[self.column]);
+        let ordering = match &self.comparator {
+            Some(f) => f(a, b),
+            None => Colonnade::compare_cells(a, b),
+        };
+        match self.order {
+            Order::Asc => ordering,
+            Order::Desc => ordering.reverse(),
+        }
+    }
+}
+
+/// The ways the numbers in a column can be aggregated into subtotals and totals.
+///
+/// See [`Column::aggregate`](struct.Column.html#method.aggregate).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Aggregate {
+    /// The sum of the numbers
+    Sum,
+    /// The number of cells which are not empty
+    Count,
+    /// The mean of the numbers
+    Mean,
+    /// The smallest number
+    Min,
+    /// The largest number
+    Max,
+}
+
+impl Aggregate {
+    // the aggregate of a column over some rows
+    fn apply(&self, rows: &[Vec<Cell>], column: usize) -> Cell<'static> {
+        let values = rows.iter().filter_map(|row| row[column].value);
+        let aggregate = match self {
+            Aggregate::Count => Some(
+                rows.iter()
+                    .filter(|row| !to_words(&row[column].text).is_empty())
+                    .count() as f64,
+            ),
+            Aggregate::Sum => Some(values.sum()),
+            Aggregate::Mean => {
+                let values = values.collect::<Vec<_>>();
+                if values.is_empty() {
+                    None
+                } else {
+                    Some(values.iter().sum::<f64>() / values.len() as f64)
+                }
+            }
+            Aggregate::Min => values.reduce(f64::min),
+            Aggregate::Max => values.reduce(f64::max),
+        };
+        match aggregate {
+            Some(n) => Cell::new(n).numeric(true),
+            None => Cell::new(""),
+        }
+    }
+}
+
+/// The display of numbers as percentages with a fixed precision.
+///
+/// See [`Column::percentage`](struct.Column.html#method.percentage).
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate colonnade;
+/// # use colonnade::{Colonnade, Percentage};
+/// # use std::error::Error;
+/// # fn demo() -> Result<(), Box<dyn Error>> {
+/// let mut colonnade = Colonnade::new(2, 80)?;
+/// colonnade.columns[1].percentage(Percentage::new(1).clamp(0.0, 100.0));
+/// let data = [("disk", 0.734), ("quota", 1.2)];
+/// assert_eq!(vec!["disk   73.4%", "quota 100.0%"], colonnade.tabulate(data)?);
+/// assert_eq!(vec![(1, 1)], colonnade.clamped());
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Percentage {
+    precision: usize,
+    ratio: bool,
+    clamp: Option<(f64, f64)>,
+}
+
+impl Percentage {
+    /// Construct a percentage format for ratios, so `0.5` is displayed as `50%`.
     ///
-    /// See [`Colonnade::padding`](struct.Colonade.html#method.padding).
+    /// # Arguments
+    ///
+    /// * `precision` - The number of digits after the decimal point.
+    pub fn new(precision: usize) -> Percentage {
+        Percentage {
+            precision,
+            ratio: true,
+            clamp: None,
+        }
+    }
+    /// Toggle whether the data are ratios, `0.5` for `50%`, or already percentages, `50` for `50%`.
+    /// By default they are ratios.
     ///
     /// # Arguments
     ///
-    /// * `padding` - The width in blank spaces/lines of the desired padding.
+    /// * `ratio` - Whether the data are ratios.
+    pub fn ratio(mut self, ratio: bool) -> Percentage {
+        self.ratio = ratio;
+        self
+    }
+    /// Limit the percentages displayed to a range. Values outside the range are displayed as the
+    /// nearest limit and reported by [`Colonnade::clamped`](struct.Colonnade.html#method.clamped).
     ///
-    /// # Example
+    /// # Arguments
     ///
-    /// ```rust
-    /// # extern crate colonnade;
-    /// # use colonnade::{Alignment,Colonnade};
-    /// # use std::error::Error;
-    /// # fn demo() -> Result<(), Box<dyn Error>> {
-    /// let mut colonnade = Colonnade::new(4, 100)?;
-    /// colonnade.columns[0].padding_left(1);
-    /// # Ok(()) }
-    /// ```
-    pub fn padding_left(&mut self, padding: usize) -> &mut Self {
-        self.padding_left = padding;
-        self.adjusted = false;
+    /// * `min` - The smallest percentage displayed.
+    /// * `max` - The largest percentage displayed.
+    pub fn clamp(mut self, min: f64, max: f64) -> Percentage {
+        self.clamp = Some((min, max));
         self
     }
-    /// Assign a particular column a particular right padding -- space after the column's text.
-    ///
-    /// See [`Colonnade::padding`](struct.Colonade.html#method.padding).
+    // the percentage and whether it was clamped, if the text is a number
+    fn format(&self, text: &str) -> Option<(String, bool)> {
+        let n = text.trim().parse::<f64>().ok().filter(|n| n.is_finite())?;
+        let n = if self.ratio { n * 100.0 } else { n };
+        let (n, clamped) = match self.clamp {
+            Some((min, _)) if n < min => (min, true),
+            Some((_, max)) if n > max => (max, true),
+            _ => (n, false),
+        };
+        Some((format!("{:.*}%", self.precision, n), clamped))
+    }
+}
+
+/// The display of numbers as horizontal bars, for quick histograms.
+///
+/// The bars are scaled to the width of the column after layout, less the room needed for the
+/// values, which are right-aligned beside them. A column of short numbers is narrow, so give it a
+/// [`min_width`](struct.Column.html#method.min_width) or [`fixed_width`](struct.Column.html#method.fixed_width)
+/// for the bars to be informative.
+///
+/// See [`Column::bar_chart`](struct.Column.html#method.bar_chart).
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate colonnade;
+/// # use colonnade::{BarChart, Colonnade};
+/// # use std::error::Error;
+/// # fn demo() -> Result<(), Box<dyn Error>> {
+/// let mut colonnade = Colonnade::new(2, 80)?;
+/// colonnade.columns[1].bar_chart(BarChart::new()).fixed_width(12)?;
+/// let data = [("apples", 42), ("pears", 84), ("plums", 0)];
+/// assert_eq!(
+///     vec![
+///         "apples ████▌     42",
+///         "pears  █████████ 84",
+///         "plums             0",
+///     ],
+///     colonnade.tabulate(data)?
+/// );
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BarChart {
+    max: Option<f64>,
+    hide_values: bool,
+}
+
+impl BarChart {
+    /// Construct a bar chart scaled to the largest value in the column, with values displayed.
+    pub fn new() -> BarChart {
+        BarChart::default()
+    }
+    /// Scale the bars so that this value fills the available width. Larger values are cut short.
     ///
     /// # Arguments
     ///
-    /// * `padding` - The width in blank spaces/lines of the desired padding.
+    /// * `max` - The value represented by a full-width bar.
+    pub fn max(mut self, max: f64) -> BarChart {
+        self.max = Some(max);
+        self
+    }
+    /// Toggle the display of values beside the bars. By default they are displayed.
     ///
-    /// # Example
+    /// # Arguments
     ///
-    /// ```rust
-    /// # extern crate colonnade;
-    /// # use colonnade::{Alignment,Colonnade};
-    /// # use std::error::Error;
-    /// # fn demo() -> Result<(), Box<dyn Error>> {
-    /// let mut colonnade = Colonnade::new(4, 100)?;
-    /// colonnade.columns[0].padding_right(1);
-    /// # Ok(()) }
-    /// ```
-    pub fn padding_right(&mut self, padding: usize) -> &mut Self {
-        self.padding_right = padding;
-        self.adjusted = false;
+    /// * `values` - Whether to display the values.
+    pub fn values(mut self, values: bool) -> BarChart {
+        self.hide_values = !values;
         self
     }
-    /// Assign a particular column a particular vertical padding -- blank lines before and after the column's text.
-    ///
-    /// See [`Colonnade::padding`](struct.Colonade.html#method.padding).
+    // a bar representing the given fraction of the width, to the nearest eighth of a character
+    fn bar(fraction: f64, width: usize) -> String {
+        const PARTS: [&str; 8] = ["", "▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+        let eighths = (fraction.clamp(0.0, 1.0) * (width * 8) as f64).round() as usize;
+        "█".repeat(eighths / 8) + PARTS[eighths % 8]
+    }
+}
+
+/// A color for text or its background.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    /// one of the 256 colors of an extended ANSI palette
+    Ansi256(u8),
+    /// a 24-bit color
+    Rgb(u8, u8, u8),
+}
+
+/// `Color::Ansi256` and `Color::Rgb` become the `anstyle` colors of the same kind.
+impl From<Color> for anstyle::Color {
+    fn from(color: Color) -> anstyle::Color {
+        use anstyle::AnsiColor;
+        match color {
+            Color::Black => AnsiColor::Black.into(),
+            Color::Red => AnsiColor::Red.into(),
+            Color::Green => AnsiColor::Green.into(),
+            Color::Yellow => AnsiColor::Yellow.into(),
+            Color::Blue => AnsiColor::Blue.into(),
+            Color::Magenta => AnsiColor::Magenta.into(),
+            Color::Cyan => AnsiColor::Cyan.into(),
+            Color::White => AnsiColor::White.into(),
+            Color::Ansi256(n) => anstyle::Ansi256Color(n).into(),
+            Color::Rgb(r, g, b) => anstyle::RgbColor(r, g, b).into(),
+        }
+    }
+}
+
+/// The bright ANSI colors become the corresponding colors of the 256-color palette.
+impl From<anstyle::Color> for Color {
+    fn from(color: anstyle::Color) -> Color {
+        match color {
+            anstyle::Color::Ansi(color) => match anstyle::Ansi256Color::from_ansi(color).0 {
+                0 => Color::Black,
+                1 => Color::Red,
+                2 => Color::Green,
+                3 => Color::Yellow,
+                4 => Color::Blue,
+                5 => Color::Magenta,
+                6 => Color::Cyan,
+                7 => Color::White,
+                n => Color::Ansi256(n),
+            },
+            anstyle::Color::Ansi256(color) => Color::Ansi256(color.0),
+            anstyle::Color::Rgb(color) => Color::Rgb(color.0, color.1, color.2),
+        }
+    }
+}
+
+/// The colors and text attributes with which a cell is displayed. Styled text is wrapped in ANSI
+/// escape sequences in the output of [`tabulate`](struct.Colonnade.html#method.tabulate) and
+/// [`macerate`](struct.Colonnade.html#method.macerate).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Style {
+    foreground: Option<Color>,
+    background: Option<Color>,
+    bold: bool,
+    dim: bool,
+    italic: bool,
+    underline: bool,
+}
+
+impl Style {
+    /// Construct a plain style.
+    pub fn new() -> Style {
+        Style::default()
+    }
+    // this style laid over another: its colors replace those of the other, and its attributes
+    // are added to the other's
+    fn over(&self, under: &Style) -> Style {
+        Style {
+            foreground: self.foreground.or(under.foreground),
+            background: self.background.or(under.background),
+            bold: self.bold || under.bold,
+            dim: self.dim || under.dim,
+            italic: self.italic || under.italic,
+            underline: self.underline || under.underline,
+        }
+    }
+    /// Set the color of the text.
     ///
     /// # Arguments
     ///
-    /// * `padding` - The width in blank spaces/lines of the desired padding.
+    /// * `color` - The color of the text.
+    pub fn foreground(mut self, color: Color) -> Style {
+        self.foreground = Some(color);
+        self
+    }
+    /// Set the color behind the text.
     ///
-    /// # Example
+    /// # Arguments
     ///
-    /// ```rust
-    /// # extern crate colonnade;
-    /// # use colonnade::{Alignment,Colonnade};
-    /// # use std::error::Error;
-    /// # fn demo() -> Result<(), Box<dyn Error>> {
-    /// let mut colonnade = Colonnade::new(4, 100)?;
-    /// colonnade.columns[0].padding_vertical(1);
-    /// # Ok(()) }
-    /// ```
-    pub fn padding_vertical(&mut self, padding: usize) -> &mut Self {
-        self.padding_top = padding;
-        self.padding_bottom = padding;
+    /// * `color` - The background color.
+    pub fn background(mut self, color: Color) -> Style {
+        self.background = Some(color);
         self
     }
-    /// Assign a particular column a particular top padding -- blank lines before the column's text.
-    ///
-    /// See [`Colonnade::padding`](struct.Colonade.html#method.padding).
+    /// Toggle bold text.
     ///
     /// # Arguments
     ///
-    /// * `padding` - The width in blank spaces/lines of the desired padding.
+    /// * `bold` - Whether the text is bold.
+    pub fn bold(mut self, bold: bool) -> Style {
+        self.bold = bold;
+        self
+    }
+    /// Toggle dim text.
     ///
-    /// # Example
+    /// # Arguments
     ///
-    /// ```rust
-    /// # extern crate colonnade;
-    /// # use colonnade::{Alignment,Colonnade};
-    /// # use std::error::Error;
-    /// # fn demo() -> Result<(), Box<dyn Error>> {
-    /// let mut colonnade = Colonnade::new(4, 100)?;
-    /// colonnade.columns[0].padding_top(1);
-    /// # Ok(()) }
-    /// ```
-    pub fn padding_top(&mut self, padding: usize) -> &mut Self {
-        self.padding_top = padding;
+    /// * `dim` - Whether the text is dim.
+    pub fn dim(mut self, dim: bool) -> Style {
+        self.dim = dim;
         self
     }
-    /// Assign a particular column a particular bottom padding -- blank lines after the column's text.
-    ///
-    /// See [`Colonnade::padding`](struct.Colonade.html#method.padding).
+    /// Toggle italic text.
     ///
     /// # Arguments
     ///
-    /// * `padding` - The width in blank spaces/lines of the desired padding.
+    /// * `italic` - Whether the text is italic.
+    pub fn italic(mut self, italic: bool) -> Style {
+        self.italic = italic;
+        self
+    }
+    /// Toggle underlined text.
     ///
-    /// # Example
+    /// # Arguments
     ///
-    /// ```rust
-    /// # extern crate colonnade;
-    /// # use colonnade::{Alignment,Colonnade};
-    /// # use std::error::Error;
-    /// # fn demo() -> Result<(), Box<dyn Error>> {
-    /// let mut colonnade = Colonnade::new(4, 100)?;
-    /// colonnade.columns[0].padding_bottom(1);
-    /// # Ok(()) }
-    /// ```
-    pub fn padding_bottom(&mut self, padding: usize) -> &mut Self {
-        self.padding_bottom = padding;
+    /// * `underline` - Whether the text is underlined.
+    pub fn underline(mut self, underline: bool) -> Style {
+        self.underline = underline;
         self
     }
-    /// Toggle whether words too wide to fit in the column are hyphenated when spit. By
-    /// default this is `true`. If there is only 1 character of available space in a column,
-    /// though, there is never any hyphenation.
+    /// Wrap the text in the escape sequences which display it in this style.
     ///
     /// # Arguments
     ///
-    /// * `hyphenate` - Whether to hyphenate when splitting words.
+    /// * `text` - The text to style.
     ///
     /// # Example
     ///
     /// ```rust
     /// # extern crate colonnade;
-    /// # use colonnade::{Alignment,Colonnade};
-    /// # use std::error::Error;
-    /// # fn demo() -> Result<(), Box<dyn Error>> {
-    /// let mut colonnade = Colonnade::new(1, 3)?;
-    /// colonnade.alignment(Alignment::Right);
-    /// for line in colonnade.tabulate(&[[1234]])? {
-    ///     println!("{}", line);
-    /// }
-    /// // 12-
-    /// //  34
-    /// colonnade.columns[0].hyphenate(false);
-    /// for line in colonnade.tabulate(&[[1234]])? {
-    ///     println!("{}", line);
-    /// }
-    /// // 123
-    /// //   4
-    /// # Ok(()) }
+    /// # use colonnade::{Color, Style};
+    /// let style = Style::new().foreground(Color::Red).bold(true);
+    /// assert_eq!("\u{1b}[1m\u{1b}[31mhot\u{1b}[0m", style.paint("hot"));
     /// ```
-    pub fn hyphenate(&mut self, hyphenate: bool) -> &mut Self {
-        self.hyphenate = hyphenate;
-        self
+    pub fn paint(&self, text: &str) -> String {
+        let style: anstyle::Style = self.clone().into();
+        if text.is_empty() {
+            text.to_string()
+        } else {
+            format!("{}{}{}", style.render(), text, style.render_reset())
+        }
     }
 }
 
-/// A struct holding formatting information. This is the object which tabulates data.
-#[derive(Debug, Clone)]
-pub struct Colonnade {
-    pub columns: Vec<Column>,
-    width: usize,
-    spaces_between_rows: usize,
+impl From<Style> for anstyle::Style {
+    fn from(style: Style) -> anstyle::Style {
+        let mut effects = anstyle::Effects::new();
+        for (on, effect) in [
+            (style.bold, anstyle::Effects::BOLD),
+            (style.dim, anstyle::Effects::DIMMED),
+            (style.italic, anstyle::Effects::ITALIC),
+            (style.underline, anstyle::Effects::UNDERLINE),
+        ] {
+            if on {
+                effects |= effect;
+            }
+        }
+        anstyle::Style::new()
+            .fg_color(style.foreground.map(Into::into))
+            .bg_color(style.background.map(Into::into))
+            .effects(effects)
+    }
 }
 
-#[cfg(feature = "nbsp")]
-fn to_words<'a>(s: &'a str) -> Vec<&'a str> {
-    lazy_static! {
-        static ref SPLITTABLE_SPACE: Regex = Regex::new(r"[\s&&[^\u00A0]]+").unwrap();
+/// Effects other than bold, dim, italic, and underline are dropped.
+impl From<anstyle::Style> for Style {
+    fn from(style: anstyle::Style) -> Style {
+        let effects = style.get_effects();
+        Style {
+            foreground: style.get_fg_color().map(Into::into),
+            background: style.get_bg_color().map(Into::into),
+            bold: effects.contains(anstyle::Effects::BOLD),
+            dim: effects.contains(anstyle::Effects::DIMMED),
+            italic: effects.contains(anstyle::Effects::ITALIC),
+            underline: effects.contains(anstyle::Effects::UNDERLINE),
+        }
     }
-    SPLITTABLE_SPACE
-        .split(s)
-        .filter(|s| s.len() > 0)
-        .collect::<Vec<&'a str>>()
 }
 
-#[cfg(not(feature = "nbsp"))]
-fn to_words<'a>(s: &'a str) -> Vec<&'a str> {
-    s.split_whitespace()
-        .filter(|s| s.len() > 0)
-        .collect::<Vec<&'a str>>()
+/// Where a cell styled by a [`Styler`](trait.Styler.html) is in a table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellContext {
+    /// the index of the cell's row as displayed, the [header row](struct.Colonnade.html#method.header_row)
+    /// counted first
+    pub row: usize,
+    /// the index of the cell's column
+    pub column: usize,
+    /// whether the cell is in the header row
+    pub header: bool,
 }
 
-// find the longest sequence of non-whitespace characters in a string
-fn longest_word(s: &str) -> usize {
-    to_words(s).iter().fold(0, |acc, v| {
-        let c = true_width(v);
-        if c > acc {
-            c
-        } else {
-            acc
-        }
-    })
+/// Something which decorates the text of cells as
+/// [`Colonnade::tabulate_styled`](struct.Colonnade.html#method.tabulate_styled) renders them,
+/// typically with ANSI escape sequences. It is implemented for [`Style`](struct.Style.html), for
+/// functions of a [`CellContext`](struct.CellContext.html) and text, for the styles of the
+/// `anstyle` crate, and, given the feature of the same name, for the styles of the `ansi_term`,
+/// `owo-colors`, and `yansi` crates.
+pub trait Styler {
+    /// Decorate the text of a cell.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Where the cell is.
+    /// * `text` - The text of the cell, aligned and padded.
+    fn style(&self, ctx: CellContext, text: &str) -> String;
 }
 
-fn true_width(s: &str) -> usize {
-    UnicodeSegmentation::graphemes(s, true).count()
+impl Styler for Style {
+    fn style(&self, _ctx: CellContext, text: &str) -> String {
+        self.paint(text)
+    }
 }
 
-impl Colonnade {
-    /// Construct a `Colonnade` with default values: left alignment, no column size
-    /// constraints, no blank lines between rows, 1 space margin between columns.
+impl<F> Styler for F
+where
+    F: Fn(CellContext, &str) -> String,
+{
+    fn style(&self, ctx: CellContext, text: &str) -> String {
+        self(ctx, text)
+    }
+}
+
+impl Styler for anstyle::Style {
+    fn style(&self, _ctx: CellContext, text: &str) -> String {
+        format!("{}{}{}", self.render(), text, self.render_reset())
+    }
+}
+
+#[cfg(feature = "ansi_term")]
+impl Styler for ansi_term::Style {
+    fn style(&self, _ctx: CellContext, text: &str) -> String {
+        self.paint(text).to_string()
+    }
+}
+
+#[cfg(feature = "ansi_term")]
+impl Styler for ansi_term::Colour {
+    fn style(&self, _ctx: CellContext, text: &str) -> String {
+        self.paint(text).to_string()
+    }
+}
+
+#[cfg(feature = "owo-colors")]
+impl Styler for owo_colors::Style {
+    fn style(&self, _ctx: CellContext, text: &str) -> String {
+        use owo_colors::OwoColorize;
+        text.style(*self).to_string()
+    }
+}
+
+#[cfg(feature = "yansi")]
+impl Styler for yansi::Style {
+    fn style(&self, _ctx: CellContext, text: &str) -> String {
+        use yansi::Paint;
+        text.paint(*self).to_string()
+    }
+}
+
+/// Writers which strip the colors and text attributes of styled cells from what is written to
+/// them when the output is not a terminal or `NO_COLOR` is set, re-exported from the `anstream`
+/// crate.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate colonnade;
+/// # use colonnade::{AutoStream, Colonnade, HeatScale};
+/// # use std::error::Error;
+/// # use std::io::Write;
+/// # fn demo() -> Result<(), Box<dyn Error>> {
+/// let mut colonnade = Colonnade::new(2, 80)?;
+/// colonnade.columns[1].heat(HeatScale::new((0, 0, 255), (255, 0, 0)));
+/// let mut out = AutoStream::auto(std::io::stdout());
+/// for line in colonnade.tabulate(&[("cold", 0), ("hot", 10)])? {
+///     writeln!(out, "{}", line)?;
+/// }
+/// # Ok(()) }
+/// ```
+pub use anstream::{AutoStream, ColorChoice};
+
+/// A scale of colors onto which the numbers in a column are mapped, from the smallest value to the
+/// largest, for heat-map-like tables. The range of values is determined when the data is laid out.
+///
+/// See [`Column::heat`](struct.Column.html#method.heat).
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate colonnade;
+/// # use colonnade::{Colonnade, HeatScale};
+/// # use std::error::Error;
+/// # fn demo() -> Result<(), Box<dyn Error>> {
+/// let mut colonnade = Colonnade::new(2, 80)?;
+/// colonnade.columns[1].heat(HeatScale::new((0, 0, 255), (255, 0, 0)));
+/// let data = [("Oslo", 4), ("Rome", 21), ("Cairo", 35)];
+/// for line in colonnade.tabulate(data)? {
+///     println!("{}", line);
+/// }
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeatScale {
+    low: (u8, u8, u8),
+    high: (u8, u8, u8),
+    range: Option<(f64, f64)>,
+    background: bool,
+}
+
+impl HeatScale {
+    /// Construct a scale coloring the text of cells.
     ///
     /// # Arguments
     ///
-    /// * `columns` - The number of columns of data to expect
-    /// * `width` - Viewport size in characters
+    /// * `low` - The red, green, and blue components of the color of the smallest value.
+    /// * `high` - The red, green, and blue components of the color of the largest value.
+    pub fn new(low: (u8, u8, u8), high: (u8, u8, u8)) -> HeatScale {
+        HeatScale {
+            low,
+            high,
+            range: None,
+            background: false,
+        }
+    }
+    /// Fix the range of values spanned by the scale rather than taking it from the data. Values
+    /// outside the range take the color of the nearest end of the scale.
     ///
-    /// # Errors
+    /// # Arguments
     ///
-    /// * `ColonnadeError::InsufficientSpace` - the viewport isn't wide enough for the columns and their margins
+    /// * `min` - The value given the `low` color.
+    /// * `max` - The value given the `high` color.
+    pub fn range(mut self, min: f64, max: f64) -> HeatScale {
+        self.range = Some((min, max));
+        self
+    }
+    /// Toggle whether the scale colors the background of cells rather than their text.
+    ///
+    /// # Arguments
+    ///
+    /// * `background` - Whether to color the background.
+    pub fn background(mut self, background: bool) -> HeatScale {
+        self.background = background;
+        self
+    }
+    // the style of a value the given fraction of the way through the range
+    fn style(&self, fraction: f64) -> Style {
+        let fraction = fraction.clamp(0.0, 1.0);
+        let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * fraction).round() as u8;
+        let color = Color::Rgb(
+            mix(self.low.0, self.high.0),
+            mix(self.low.1, self.high.1),
+            mix(self.low.2, self.high.2),
+        );
+        if self.background {
+            Style::new().background(color)
+        } else {
+            Style::new().foreground(color)
+        }
+    }
+}
+
+/// Units for the human-readable display of numbers.
+///
+/// See [`Column::humanize`](struct.Column.html#method.humanize).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Humanize {
+    /// A number of bytes, displayed in binary units: `512 B`, `1.2 GiB`
+    Bytes,
+    /// A number of seconds, displayed in the two largest units: `42s`, `5m 03s`, `2h 03m`, `3d 04h`
+    Seconds,
+}
+
+impl Humanize {
+    // the humanized text if the text is a number
+    fn format(&self, text: &str) -> Option<String> {
+        let n = text.trim().parse::<f64>().ok().filter(|n| n.is_finite())?;
+        let sign = if n < 0.0 { "-" } else { "" };
+        let n = n.abs();
+        Some(match self {
+            Humanize::Bytes => {
+                const UNITS: [&str; 7] = ["KiB", "MiB", "GiB", "TiB", "PiB", "EiB", "ZiB"];
+                if n < 1024.0 {
+                    format!("{}{} B", sign, n)
+                } else {
+                    let mut n = n / 1024.0;
+                    let mut unit = 0;
+                    while n >= 1024.0 && unit < UNITS.len() - 1 {
+                        n /= 1024.0;
+                        unit += 1;
+                    }
+                    format!("{}{:.1} {}", sign, n, UNITS[unit])
+                }
+            }
+            Humanize::Seconds => {
+                if n < 60.0 {
+                    if n.fract() == 0.0 {
+                        format!("{}{}s", sign, n)
+                    } else {
+                        format!("{}{:.1}s", sign, n)
+                    }
+                } else {
+                    let s = n.round() as u64;
+                    let (large, small, units) = if s < 3600 {
+                        (s / 60, s % 60, ("m", "s"))
+                    } else if s < 86400 {
+                        (s / 3600, s % 3600 / 60, ("h", "m"))
+                    } else {
+                        (s / 86400, s % 86400 / 3600, ("d", "h"))
+                    };
+                    format!("{}{}{} {:02}{}", sign, large, units.0, small, units.1)
+                }
+            }
+        })
+    }
+}
+
+/// Vertical alignments of text within a column.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VerticalAlignment {
+    /// the default vertical alignment
+    Top,
+    Middle,
+    Bottom,
+}
+
+/// Formatting applied to those cells of a column whose text parses as a number.
+///
+/// Formatted cells are marked [`numeric`](struct.Cell.html#method.numeric), so unless the column
+/// has an explicit alignment they are right-aligned. With a fixed precision, this lines up their
+/// decimal points.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate colonnade;
+/// # use colonnade::{Colonnade, NumberFormat};
+/// # use std::error::Error;
+/// # fn demo() -> Result<(), Box<dyn Error>> {
+/// let mut colonnade = Colonnade::new(2, 80)?;
+/// colonnade.columns[1].number_format(NumberFormat::new().thousands_separator(',').precision(2));
+/// let data = [["rent", "1250"], ["lottery", "-1234567.891"]];
+/// assert_eq!(
+///     vec!["rent         1,250.00", "lottery -1,234,567.89"],
+///     colonnade.tabulate(data)?
+/// );
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumberFormat {
+    decimal_point: char,
+    thousands_separator: Option<char>,
+    precision: Option<usize>,
+    scientific_threshold: Option<f64>,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        NumberFormat {
+            decimal_point: '.',
+            thousands_separator: None,
+            precision: None,
+            scientific_threshold: None,
+        }
+    }
+}
+
+impl NumberFormat {
+    /// Construct a format which leaves numbers as they are but marks them numeric.
+    pub fn new() -> NumberFormat {
+        NumberFormat::default()
+    }
+    /// Set the character separating the integer and fractional parts of numbers, both in the
+    /// data and in the formatted text. By default this is `.`.
+    ///
+    /// # Arguments
+    ///
+    /// * `decimal_point` - The decimal point character, such as `,` for much of Europe.
     ///
     /// # Example
     ///
     /// ```rust
     /// # extern crate colonnade;
-    /// # use colonnade::Colonnade;
-    /// let colonnade = Colonnade::new(4, 100);
+    /// # use colonnade::{Colonnade, NumberFormat};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(1, 80)?;
+    /// colonnade.number_format(
+    ///     NumberFormat::new()
+    ///         .decimal_point(',')
+    ///         .thousands_separator('.')
+    ///         .precision(2),
+    /// );
+    /// assert_eq!(vec!["1.234,50", "    0,25"], colonnade.tabulate([["1.234,5"], ["0,25"]])?);
+    /// # Ok(()) }
     /// ```
-    pub fn new(columns: usize, width: usize) -> Result<Colonnade, ColonnadeError> {
-        if columns == 0 {
-            return Err(ColonnadeError::InsufficientColumns);
+    pub fn decimal_point(mut self, decimal_point: char) -> NumberFormat {
+        self.decimal_point = decimal_point;
+        self
+    }
+    /// Group the digits of the integer part of numbers in threes. Separators already present in
+    /// the data are ignored when numbers are parsed.
+    ///
+    /// A space separator is also a place where the number may wrap in a narrow column.
+    ///
+    /// # Arguments
+    ///
+    /// * `separator` - The character placed between groups of digits, such as `,`, `.`, or ` `.
+    pub fn thousands_separator(mut self, separator: char) -> NumberFormat {
+        self.thousands_separator = Some(separator);
+        self
+    }
+    /// Display numbers with a fixed number of digits after the decimal point.
+    ///
+    /// # Arguments
+    ///
+    /// * `precision` - The number of digits after the decimal point.
+    pub fn precision(mut self, precision: usize) -> NumberFormat {
+        self.precision = Some(precision);
+        self
+    }
+    /// Display numbers in scientific notation -- `1.5e9` -- when their magnitude reaches a threshold.
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold` - The smallest magnitude displayed in scientific notation.
+    pub fn scientific_threshold(mut self, threshold: f64) -> NumberFormat {
+        self.scientific_threshold = Some(threshold);
+        self
+    }
+    // the text normalized to Rust's notation and its value, if the text is a number
+    fn parse(&self, text: &str) -> Option<(String, f64)> {
+        let text = text
+            .trim()
+            .chars()
+            .filter(|&c| Some(c) != self.thousands_separator)
+            .map(|c| if c == self.decimal_point { '.' } else { c })
+            .collect::<String>();
+        if text.is_empty()
+            || !text
+                .chars()
+                .all(|c| c.is_ascii_digit() || matches!(c, '+' | '-' | '.' | 'e' | 'E'))
+        {
+            return None;
         }
-        let mut columns: Vec<Column> = (0..columns).map(|i| Column::default(i)).collect();
-        columns[0].left_margin = 0;
-        let spec = Colonnade {
-            columns,
-            width,
-            spaces_between_rows: 0,
+        let n = text.parse::<f64>().ok().filter(|n| n.is_finite())?;
+        Some((text, n))
+    }
+    // the formatted text if the text is a number
+    fn format(&self, text: &str) -> Option<String> {
+        let (text, n) = self.parse(text)?;
+        let scientific = matches!(self.scientific_threshold, Some(t) if n != 0.0 && n.abs() >= t);
+        let formatted = match self.precision {
+            Some(p) if scientific => format!("{:.*e}", p, n),
+            None if scientific => format!("{:e}", n),
+            Some(p) => format!("{:.*}", p, n),
+            None if text.contains(['e', 'E']) => n.to_string(),
+            None => text,
         };
-        if !spec.sufficient_space() {
-            return Err(ColonnadeError::InsufficientSpace);
+        let formatted = formatted.replace('.', &self.decimal_point.to_string());
+        Some(match self.thousands_separator {
+            Some(separator) if !scientific => group_digits(&formatted, separator),
+            _ => formatted,
+        })
+    }
+}
+
+/// Formatting applied to those cells of a column whose text parses as a date or time.
+///
+/// By default, cells are parsed as RFC 3339 or ISO 8601 timestamps, such as
+/// `2024-05-01T13:02:47Z`, `2024-05-01T13:02:47`, or `2024-05-01 13:02`, or as dates, such as
+/// `2024-05-01`. Formatted cells are marked [`numeric`](struct.Cell.html#method.numeric), so unless
+/// the column has an explicit alignment they are right-aligned. Cells which do not parse, or which
+/// the output format cannot describe, are left as they are.
+///
+/// This type requires the `chrono` feature. Formats use `chrono`'s
+/// [`strftime`](https://docs.rs/chrono/latest/chrono/format/strftime/index.html) syntax.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate colonnade;
+/// # use colonnade::{Colonnade, DateTimeFormat};
+/// # use std::error::Error;
+/// # fn demo() -> Result<(), Box<dyn Error>> {
+/// let mut colonnade = Colonnade::new(2, 80)?;
+/// colonnade.columns[1].datetime_format(DateTimeFormat::new("%Y-%m-%d %H:%M"));
+/// let data = [["deployed", "2024-05-01T13:02:47Z"], ["reverted", "2024-05-02"]];
+/// assert_eq!(
+///     vec!["deployed 2024-05-01 13:02", "reverted 2024-05-02 00:00"],
+///     colonnade.tabulate(data)?
+/// );
+/// # Ok(()) }
+/// ```
+#[cfg(feature = "chrono")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DateTimeFormat {
+    input: Option<String>,
+    output: String,
+}
+
+#[cfg(feature = "chrono")]
+impl DateTimeFormat {
+    /// Construct a format which displays dates and times in the given format.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The format in which to display dates and times.
+    pub fn new(output: &str) -> DateTimeFormat {
+        DateTimeFormat {
+            input: None,
+            output: output.to_string(),
         }
-        Ok(spec)
     }
-    // the absolute minimal space that might fit this table assuming some data in every column
-    fn minimal_width(&self) -> usize {
+    /// Parse cells in the given format rather than as RFC 3339 or ISO 8601 timestamps.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The format of the dates or times in the data.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Colonnade, DateTimeFormat};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(1, 80)?;
+    /// colonnade.datetime_format(DateTimeFormat::new("%-d %B %Y").input("%d/%m/%Y"));
+    /// assert_eq!(vec!["1 May 2024"], colonnade.tabulate([["01/05/2024"]])?);
+    /// # Ok(()) }
+    /// ```
+    pub fn input(mut self, input: &str) -> DateTimeFormat {
+        self.input = Some(input.to_string());
+        self
+    }
+    // the formatted text if the text is a date or time
+    fn format(&self, text: &str) -> Option<String> {
+        use chrono::{DateTime, NaiveDate, NaiveDateTime};
+        use std::fmt::Write;
+        let text = text.trim();
+        let mut formatted = String::new();
+        let written = match &self.input {
+            Some(input) => {
+                if let Ok(t) = DateTime::parse_from_str(text, input) {
+                    write!(formatted, "{}", t.format(&self.output))
+                } else if let Ok(t) = NaiveDateTime::parse_from_str(text, input) {
+                    write!(formatted, "{}", t.format(&self.output))
+                } else {
+                    let t = NaiveDate::parse_from_str(text, input).ok()?;
+                    write!(
+                        formatted,
+                        "{}",
+                        t.and_hms_opt(0, 0, 0)?.format(&self.output)
+                    )
+                }
+            }
+            None => {
+                if let Ok(t) = DateTime::parse_from_rfc3339(text) {
+                    write!(formatted, "{}", t.format(&self.output))
+                } else if let Some(t) = [
+                    "%Y-%m-%dT%H:%M:%S%.f",
+                    "%Y-%m-%d %H:%M:%S%.f",
+                    "%Y-%m-%dT%H:%M",
+                    "%Y-%m-%d %H:%M",
+                ]
+                .iter()
+                .find_map(|f| NaiveDateTime::parse_from_str(text, f).ok())
+                {
+                    write!(formatted, "{}", t.format(&self.output))
+                } else {
+                    let t = NaiveDate::parse_from_str(text, "%Y-%m-%d").ok()?;
+                    write!(
+                        formatted,
+                        "{}",
+                        t.and_hms_opt(0, 0, 0)?.format(&self.output)
+                    )
+                }
+            }
+        };
+        written.ok().map(|_| formatted)
+    }
+}
+
+// insert a separator between groups of three digits in the integer part of a formatted number
+fn group_digits(number: &str, separator: char) -> String {
+    let start = number.find(|c: char| c.is_ascii_digit()).unwrap_or(0);
+    let end = number[start..]
+        .find(|c: char| !c.is_ascii_digit())
+        .map_or(number.len(), |i| start + i);
+    let digits = &number[start..end];
+    // the length of the leading group of digits, modulo 3
+    let lead = digits.len() % 3;
+    let mut grouped = number[..start].to_string();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && i % 3 == lead {
+            grouped.push(separator);
+        }
+        grouped.push(c);
+    }
+    grouped + &number[end..]
+}
+
+/// A struct holding formatting information for a particular column.
+#[derive(Debug, Clone)]
+pub struct Column {
+    index: usize,
+    name: Option<String>,
+    alignment: Option<Alignment>,
+    vertical_alignment: VerticalAlignment,
+    left_margin: usize,
+    right_margin: usize,
+    priority: usize,
+    min_width: Option<usize>,
+    min_width_fraction: Option<f64>,
+    max_width: Option<usize>,
+    max_width_fraction: Option<f64>,
+    padding_left: usize,
+    padding_right: usize,
+    padding_top: usize,
+    padding_bottom: usize,
+    hyphenate: bool,
+    avoid_orphans: bool,
+    collapse_below: Option<usize>,
+    hidden: bool,
+    // whether the column is left out of the column order
+    deselected: bool,
+    number_format: Option<NumberFormat>,
+    empty_placeholder: Option<String>,
+    #[cfg(feature = "chrono")]
+    datetime_format: Option<DateTimeFormat>,
+    humanize: Option<Humanize>,
+    currency: Option<String>,
+    percentage: Option<Percentage>,
+    bar_chart: Option<BarChart>,
+    heat: Option<HeatScale>,
+    style: Option<Style>,
+    hyperlink: Option<Hyperlink>,
+    computation: Option<Computation>,
+    literal: Option<String>,
+    aggregate: Option<Aggregate>,
+    // incremented with every change to the specification, so a layout can tell it is stale
+    revision: usize,
+}
+
+// what layout has computed for a column, as opposed to what the user has specified
+#[derive(Debug, Clone, Default)]
+struct ColumnState {
+    // the width of the column excluding any margins
+    width: usize,
+    // the width of the viewport, against which fractional widths are resolved
+    viewport: usize,
+    // the width of any literal, measured as the table measures text
+    literal: usize,
+    dropped: bool,
+    // the revision of the column's specification this state was computed from
+    revision: usize,
+}
+
+// the state of a column not yet laid out
+static UNLAID: ColumnState = ColumnState {
+    width: 0,
+    viewport: 0,
+    literal: 0,
+    dropped: false,
+    revision: 0,
+};
+
+/// What laying out a table computed, as opposed to what its columns specify: the width of each
+/// column, the columns dropped for lack of space, and what became of the data most recently
+/// tabulated. A `Colonnade` keeps its most recent layout and reuses it for later data while its
+/// specification is unchanged.
+///
+/// See [`Colonnade::layout`](struct.Colonnade.html#method.layout).
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate colonnade;
+/// # use colonnade::Colonnade;
+/// # use std::error::Error;
+/// # fn demo() -> Result<(), Box<dyn Error>> {
+/// let mut colonnade = Colonnade::new(2, 80)?;
+/// colonnade.tabulate([["a", "bbb"]])?;
+/// assert_eq!(vec![1, 3], colonnade.layout().widths());
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Layout {
+    columns: Vec<ColumnState>,
+    // whether the layout may be reused for more data
+    adjusted: bool,
+    overflows: Vec<(usize, usize)>,
+    clamped: Vec<(usize, usize)>,
+    // whether the most recent layout met the target height, if there is one
+    target_height_met: Option<bool>,
+    grew: bool,
+    // the number of lines of each row most recently rendered
+    row_heights: Vec<usize>,
+}
+
+impl Layout {
+    /// The width of each column, excluding its margins, in the order of the columns' indices. A
+    /// column not displayed, or not yet laid out, is 0 wide.
+    pub fn widths(&self) -> Vec<usize> {
         self.columns
             .iter()
-            .fold(0, |acc, v| acc + v.left_margin + v.min_width.unwrap_or(1)) // assume each column requires at least one character
+            .map(|c| if c.dropped { 0 } else { c.width })
+            .collect()
     }
-    fn sufficient_space(&self) -> bool {
-        self.minimal_width() <= self.width
+    /// The width of the column at the given index, excluding its margins, if it has been laid out.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The index of the column.
+    pub fn width(&self, column: usize) -> Option<usize> {
+        self.columns.get(column).map(|c| c.width)
     }
-    // the amount of space required to display the data given the current column specs
-    fn required_width(&self) -> usize {
-        self.columns.iter().fold(0, |acc, v| acc + v.outer_width())
+}
+
+// the function computing the cells of a virtual column
+type ComputeFn = dyn Fn(&[Cell]) -> Cell<'static> + Send + Sync;
+
+#[derive(Clone)]
+struct Computation(Arc<ComputeFn>);
+
+impl fmt::Debug for Computation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Computation")
     }
-    // make a blank line as wide as the table
-    fn blank_line(&self) -> String {
-        " ".repeat(self.required_width())
+}
+
+// the function providing the targets of the hyperlinks of a column's cells
+type LinkFn = dyn Fn(usize, &Cell) -> Option<String> + Send + Sync;
+
+#[derive(Clone)]
+struct Hyperlink(Arc<LinkFn>);
+
+impl fmt::Debug for Hyperlink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Hyperlink")
     }
-    fn maximum_vertical_padding(&self) -> usize {
-        let mut p = 0;
-        for c in &self.columns {
-            let p2 = c.vertical_padding();
-            if p2 > p {
-                p = p2;
-            }
+}
+
+impl Column {
+    fn default(index: usize) -> Column {
+        Column {
+            index,
+            name: None,
+            alignment: None,
+            vertical_alignment: VerticalAlignment::Top,
+            left_margin: 1,
+            right_margin: 0,
+            priority: usize::MAX,
+            min_width: None,
+            min_width_fraction: None,
+            max_width: None,
+            max_width_fraction: None,
+            padding_left: 0,
+            padding_right: 0,
+            padding_top: 0,
+            padding_bottom: 0,
+            hyphenate: true,
+            avoid_orphans: false,
+            collapse_below: None,
+            hidden: false,
+            deselected: false,
+            number_format: None,
+            empty_placeholder: None,
+            #[cfg(feature = "chrono")]
+            datetime_format: None,
+            humanize: None,
+            currency: None,
+            percentage: None,
+            bar_chart: None,
+            heat: None,
+            style: None,
+            hyperlink: None,
+            computation: None,
+            literal: None,
+            aggregate: None,
+            revision: 0,
         }
-        p
     }
-    fn len(&self) -> usize {
-        self.columns.len()
+    // note a change to the specification
+    fn revise(&mut self) {
+        self.revision = self.revision.wrapping_add(1);
     }
-    // determine the characters required to represent s after whitespace normalization
-    fn width_after_normalization(s: &str) -> usize {
-        let mut l = 0;
-        for w in to_words(s) {
-            if l != 0 {
-                l += 1;
+    fn horizontal_padding(&self) -> usize {
+        self.padding_left.saturating_add(self.padding_right)
+    }
+    fn vertical_padding(&self) -> usize {
+        self.padding_top.saturating_add(self.padding_bottom)
+    }
+    // the minimum width in a viewport of the given width, with any fraction of it resolved
+    fn min_in(&self, viewport: usize) -> Option<usize> {
+        let fraction = self
+            .min_width_fraction
+            .map(|f| (f * viewport as f64).ceil() as usize);
+        match (self.min_width, fraction) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        }
+    }
+    fn minimum_width(&self, state: &ColumnState) -> usize {
+        self.minimum_width_in(state, state.viewport)
+    }
+    fn minimum_width_in(&self, state: &ColumnState, viewport: usize) -> usize {
+        // a literal is never shrunk
+        let w1 = self.horizontal_padding() + state.literal;
+        let w2 = self.min_in(viewport).unwrap_or(w1);
+        if w2 > w1 {
+            w2
+        } else {
+            w1
+        }
+    }
+    // the narrowest the column can be and still have room for text
+    fn narrowest(&self, state: &ColumnState) -> usize {
+        self.narrowest_in(state, state.viewport)
+    }
+    fn narrowest_in(&self, state: &ColumnState, viewport: usize) -> usize {
+        self.minimum_width_in(state, viewport)
+            .max(self.horizontal_padding().saturating_add(1))
+    }
+    // the maximum width, with any fraction of the viewport resolved
+    fn max(&self, state: &ColumnState) -> Option<usize> {
+        let fraction = self
+            .max_width_fraction
+            .map(|f| (f * state.viewport as f64) as usize);
+        match (self.max_width, fraction) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+    fn effective_width(&self, state: &ColumnState) -> usize {
+        let w = if self.max(state).unwrap_or(state.width) < state.width {
+            self.max(state).unwrap()
+        } else {
+            state.width
+        };
+        let m = self.minimum_width(state);
+        if m > w {
+            m
+        } else {
+            w
+        }
+    }
+    fn inner_width(&self, state: &ColumnState) -> usize {
+        state.width.saturating_sub(self.horizontal_padding())
+    }
+    // the alignment of a cell in this column
+    fn alignment_for(&self, cell: &Cell) -> Alignment {
+        match &self.alignment {
+            Some(alignment) => alignment.clone(),
+            None if cell.numeric || cell.rtl => Alignment::Right,
+            None => Alignment::Left,
+        }
+    }
+    fn vertical_alignment_for(&self, cell: &Cell) -> VerticalAlignment {
+        cell.vertical_alignment
+            .clone()
+            .unwrap_or_else(|| self.vertical_alignment.clone())
+    }
+    // the lines of a cell's text broken so the last line isn't a lone word, if they would be
+    // otherwise and a word can be moved down to join it
+    fn unorphaned(&self, state: &ColumnState, cell: &Cell, widths: &Widths) -> Option<Vec<String>> {
+        if !self.avoid_orphans || cell.pinned || cell.guides.is_some() {
+            return None;
+        }
+        let width = self.inner_width(state);
+        let mut lines: Vec<Vec<&str>> = vec![];
+        let mut length = 0;
+        for word in to_words(&cell.text) {
+            let w = true_width(word, widths);
+            if w > width {
+                // words too long for the column are split where they fall
+                return None;
+            }
+            match lines.last_mut() {
+                Some(line) if length + 1 + w <= width => {
+                    line.push(word);
+                    length += 1 + w;
+                }
+                _ => {
+                    lines.push(vec![word]);
+                    length = w;
+                }
             }
-            l += true_width(w);
         }
-        l
+        let n = lines.len();
+        if n < 2 || lines[n - 1].len() > 1 || lines[n - 2].len() < 2 {
+            return None;
+        }
+        let moved = *lines[n - 2].last().unwrap();
+        if true_width(moved, widths) + 1 + length > width {
+            return None;
+        }
+        lines[n - 2].pop();
+        lines[n - 1].insert(0, moved);
+        Some(lines.into_iter().map(|line| line.join(" ")).collect())
     }
-    /// Returns the width of the colonnade in columns if the colonnade has already laid out data
-    /// and knows how much space this data will require.
-    pub fn width(&self) -> Option<usize> {
-        if self.adjusted() {
-            Some(self.required_width())
+    fn hyphenating(&self, state: &ColumnState) -> bool {
+        self.hyphenate && self.inner_width(state) > 1
+    }
+    fn is_shrinkable(&self, state: &ColumnState) -> bool {
+        self.minimum_width(state) < state.width
+    }
+    // shrink as close to width as possible
+    fn shrink(&self, state: &mut ColumnState, width: usize) {
+        let m = self.minimum_width(state);
+        state.width = if m > width { m } else { width }
+    }
+    // attempt to shrink by decrease amount, but no narrower than least
+    // returns whether there was any shrinkage
+    fn shrink_by(&self, state: &mut ColumnState, decrease: usize, least: usize) -> bool {
+        if self.is_shrinkable(state) {
+            // you can't shrink all the way to 0
+            let width = state.width.saturating_sub(decrease).max(least);
+            let before = state.width;
+            self.shrink(state, width);
+            before != state.width
         } else {
-            None
+            false
         }
     }
-    // returns priorites sorted lowest to highest
-    fn priorities(&self) -> Vec<usize> {
-        let mut v = self.columns.iter().map(|c| c.priority).collect::<Vec<_>>();
-        v.sort_unstable();
-        v.dedup();
-        v.reverse();
-        v
+    fn is_expandable(&self, state: &ColumnState) -> bool {
+        self.max(state).unwrap_or(usize::MAX) > state.width
     }
-    /// Converts the raw data in `table` into a vector of strings representing the data in tabular form.
-    /// Blank lines will be zero-width rather than full-width lines of whitespace.
+    // expands column as much as possible to fit width and as much as necessary to match min_width
+    fn expand(&self, state: &mut ColumnState, width: usize) -> bool {
+        if width <= state.width {
+            return false;
+        }
+        let change = if self.max(state).unwrap_or(width) < width {
+            self.max(state).unwrap()
+        } else if self.minimum_width(state) > width {
+            self.minimum_width(state)
+        } else {
+            width
+        };
+        let changed = state.width != change;
+        if changed {
+            state.width = change
+        }
+        changed
+    }
+    fn expand_by(&self, state: &mut ColumnState, increase: usize) -> bool {
+        self.expand(state, state.width.saturating_add(increase))
+    }
+    // whether the column takes part in layout and rendering in a viewport of the given width
+    fn is_displayed(&self, state: &ColumnState, viewport: usize) -> bool {
+        !self.hidden
+            && !self.deselected
+            && !state.dropped
+            && !matches!(self.collapse_below, Some(w) if viewport < w)
+    }
+    fn blank_line(&self, state: &ColumnState) -> String {
+        " ".repeat(state.width)
+    }
+    /// Assign a particular priority to the column.
     ///
-    /// If you need finer control over the text, for instance, if you want to add color codes, see
-    /// [`macerate`](#method.macerate).
+    /// Priority determines the order in which columns give up space when the viewport lacks sufficient
+    /// space to display all columns without wrapping. Lower priority columns give up space first.
     ///
     /// # Arguments
     ///
-    /// * `table` - The data to display.
-    ///
-    /// # Errors
-    ///
-    /// Any errors of [`lay_out`](#method.lay_out). If the data has already been laid out, this method will throw no errors.
+    /// * `priority` - The column's priority. Lower numbers confer higher priority; 0 is the highest priority.
     ///
     /// # Example
     ///
@@ -863,705 +2774,6574 @@ impl Colonnade {
     /// # use std::error::Error;
     /// # fn demo() -> Result<(), Box<dyn Error>> {
     /// let mut colonnade = Colonnade::new(4, 100)?;
-    /// let data = vec![vec!["some", "words", "for", "example"]];
-    /// let lines = colonnade.tabulate(&data)?;
+    /// // assign all columns the highest priority
+    /// colonnade.priority(0);
+    /// // now demote the last column
+    /// colonnade.columns[3].priority(1);
     /// # Ok(()) }
     /// ```
-    pub fn tabulate<T, U, V, W, X>(&mut self, table: T) -> Result<Vec<String>, ColonnadeError>
-    where
-        T: IntoIterator<Item = U, IntoIter = V>,
-        U: IntoIterator<Item = W, IntoIter = X>,
-        V: Iterator<Item = U>,
-        W: ToString,
-        X: Iterator<Item = W>,
-    {
-        self.macerate(table)
-            .and_then(|buffer| Ok(Colonnade::reconstitute_rows(buffer)))
+    pub fn priority(&mut self, priority: usize) -> &mut Self {
+        self.revise();
+        self.priority = priority;
+        self
     }
-    /// Chew up the text into bits suitable for piecemeal layout.
-    ///
-    /// More specifically, `macerate` digests the raw data in `table` into a vector of vectors of `(String, String)` tuples
-    /// representing the data in tabular form. Each tuple consists of a whitespace left margin and
-    /// the contents of a column. Separator lines will consist of a margin and text tuple where the
-    /// text is zero-width and the "margin" is as wide as the table.
-    ///
-    /// Maceration is useful if you wish to insert color codes to colorize the data or otherwise
-    /// manipulate the data post-layout. If you don't want to do this, see [`tabulate`](#method.tabulate).
+    /// Assign the same maximum width to all columns. By default columns have no maximum width.
     ///
     /// # Arguments
     ///
-    /// * `table` - The data to display.
+    /// * `max_width` - The common maximum width.
     ///
     /// # Errors
     ///
-    /// Any errors of [`lay_out`](#method.lay_out). If the data has already been laid out, this method will throw no errors.
+    /// * `ColonnadeError::MinGreaterThanMax` - Assigning a maximum width in conflict with some assigned minimum width.
     ///
     /// # Example
     ///
     /// ```rust
-    /// extern crate term;
-    /// // ... [some details omitted]
     /// # extern crate colonnade;
-    /// # use colonnade::{Alignment, Colonnade};
+    /// # use colonnade::Colonnade;
     /// # use std::error::Error;
     /// # fn demo() -> Result<(), Box<dyn Error>> {
-    /// // text to put in tabular form
-    /// let text = vec![
-    ///     vec![
-    ///         "Colonnade lets you format text in columns.",
-    ///         "As you can see, it supports text alignment, viewport width, and column widths.",
-    ///         "It doesn't natively support color codes, but it is easy enough to combine with a crate like term.",
-    ///     ],
-    ///     vec!["", "Two or more rows of columns makes a table.", ""],
-    /// ];
-    /// let mut colonnade = Colonnade::new(3, 80)?;
-    ///
-    /// // configure the table a bit
-    /// colonnade.spaces_between_rows(1).left_margin(4)?.fixed_width(15)?;
-    /// colonnade.columns[0].alignment(Alignment::Right).left_margin(8);
-    /// colonnade.columns[1].alignment(Alignment::Center).clear_limits();
-    /// // if the text is in colored cells, you will probably want some padding
-    /// colonnade.padding(1)?;
-    /// ///
-    /// // now print out the table
-    /// let mut t = term::stdout().unwrap();
-    /// for row in colonnade.macerate(&text)? {
-    ///     for line in row {
-    ///         for (i, (margin, text)) in line.iter().enumerate() {
-    ///             write!(t, "{}", margin)?;
-    ///             let background_color = if i % 2 == 0 {
-    ///                 term::color::WHITE
-    ///             } else {
-    ///                 term::color::BLACK
-    ///             };
-    ///             let foreground_color = match i % 3 {
-    ///                 1 => term::color::GREEN,
-    ///                 2 => term::color::RED,
-    ///                 _ => term::color::BLUE,
-    ///             };
-    ///             t.bg(background_color)?;
-    ///             t.fg(foreground_color)?;
-    ///             write!(t, "{}", text)?;
-    ///             t.reset()?;
-    ///         }
-    ///         println!();
-    ///     }
-    /// }
+    /// let mut colonnade = Colonnade::new(4, 100)?;
+    /// // assign the first column a maximum width of 20
+    /// colonnade.columns[0].max_width(20)?;
     /// # Ok(()) }
     /// ```
-    pub fn macerate<T, U, V, W, X>(
-        &mut self,
-        table: T,
-    ) -> Result<Vec<Vec<Vec<(String, String)>>>, ColonnadeError>
-    where
-        T: IntoIterator<Item = U, IntoIter = V>,
-        U: IntoIterator<Item = W, IntoIter = X>,
-        V: Iterator<Item = U>,
-        W: ToString,
-        X: Iterator<Item = W>,
-    {
-        self.lay_out(table).and_then(|owned_table| {
-            let ref_table = Colonnade::ref_table(&owned_table);
-            let table = &ref_table;
-            let mut buffer = vec![];
-            let mut p = self.maximum_vertical_padding();
-            if p == 0 {
-                p = 1;
-            }
-            for (i, row) in table.iter().enumerate() {
-                self.add_row(&mut buffer, row, i == table.len() - 1, p);
-            }
-            Ok(buffer)
-        })
-    }
-    // utility function to convert a T table to a String table
-    fn own_table<T, U, V, W, X>(&self, table: T) -> Vec<Vec<String>>
-    where
-        T: IntoIterator<Item = U, IntoIter = V>,
-        U: IntoIterator<Item = W, IntoIter = X>,
-        V: Iterator<Item = U>,
-        W: ToString,
-        X: Iterator<Item = W>,
-    {
-        let mut table = table
-            .into_iter()
-            .map(|v| {
-                v.into_iter()
-                    .map(|t| {
-                        let s = t.to_string();
-                        let bytes = strip_ansi_escapes::strip(&s);
-                        std::str::from_utf8(&bytes).expect(&format!("failed to restores bytes to utf8 string after stripping ansi escape sequences from {}", s)).to_string()
-                    })
-                    .collect::<Vec<String>>()
-            })
-            .collect::<Vec<Vec<String>>>();
-        // pad rows as necessary
-        for i in 0..table.len() {
-            while table[i].len() < self.len() {
-                table[i].push(String::new());
-            }
+    pub fn max_width(&mut self, max_width: usize) -> Result<&mut Self, ColonnadeError> {
+        if self.min_width.unwrap_or(max_width) > max_width {
+            Err(ColonnadeError::MinGreaterThanMax(
+                self.index,
+                self.name.clone(),
+            ))
+        } else {
+            self.max_width = Some(max_width);
+            self.revise();
+            Ok(self)
         }
-        table
     }
-    // utility function to convert a String table to a &str table
-    fn ref_table(table: &Vec<Vec<String>>) -> Vec<Vec<&str>> {
-        table
-            .iter()
-            .map(|v| v.iter().map(|s| s.as_ref()).collect::<Vec<&str>>())
-            .collect::<Vec<Vec<&str>>>()
+    /// Assign a particular column a maximum width as a fraction of the width of the viewport.
+    /// The fraction is resolved when the columns are laid out, so the same constraint suits
+    /// viewports of any width. If the column also has an absolute maximum width, the lesser of
+    /// the two applies. Fractions outside the range 0 to 1 are clamped. By default columns have
+    /// no maximum width.
+    ///
+    /// # Arguments
+    ///
+    /// * `fraction` - The largest fraction of the viewport the column may occupy.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 20)?;
+    /// colonnade.columns[0].max_width_fraction(0.4);
+    /// let data = [["a column of text", "b"]];
+    /// assert_eq!(vec!["a column b", "of text   "], colonnade.tabulate(data)?);
+    /// # Ok(()) }
+    /// ```
+    pub fn max_width_fraction(&mut self, fraction: f64) -> &mut Self {
+        let fraction = if fraction.is_nan() {
+            0.0
+        } else {
+            fraction.clamp(0.0, 1.0)
+        };
+        self.max_width_fraction = Some(fraction);
+        self.revise();
+        self
     }
-    fn reconstitute_rows(maceration: Vec<Vec<Vec<(String, String)>>>) -> Vec<String> {
-        maceration
-            .iter()
-            .flat_map(|row| {
-                row.iter().map(|line| {
-                    if line.len() == 1 && line[0].1.len() == 0 {
-                        String::new() // return empty strings instead of fat lines for blank lines
-                    } else {
-                        let mut l = String::new();
-                        for (margin, text) in line {
-                            l += margin;
-                            l += text;
-                        }
-                        l
-                    }
-                })
-            })
-            .collect()
+    /// Assign a particular minimum width to a particular column. By default columns have no minimum width.
+    ///
+    /// # Arguments
+    ///
+    /// * `min_width` - The common minimum width.
+    ///
+    /// # Errors
+    ///
+    /// * `ColonnadeError::MinGreaterThanMax` - Assigning a maximum width in conflict with some assigned minimum width.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(4, 100)?;
+    /// // assign the first column a minimum width of 20
+    /// colonnade.columns[0].min_width(20)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn min_width(&mut self, min_width: usize) -> Result<&mut Self, ColonnadeError> {
+        if self.max_width.unwrap_or(min_width) < min_width {
+            return Err(ColonnadeError::MinGreaterThanMax(
+                self.index,
+                self.name.clone(),
+            ));
+        }
+        self.min_width = Some(min_width);
+        self.revise();
+        Ok(self)
     }
-    // take one row of untabulated pieces of text and turn it into one or more vectors of (String,String) tuples,
-    // where each tuple represenst a left margin and some column text, the each vector representing one line of tabulated text
-    // these vectors are gathered into a vector and added to the buffer
-    fn add_row(
-        &self,
-        buffer: &mut Vec<Vec<Vec<(String, String)>>>,
-        row: &Vec<&str>,
-        last_row: bool,
-        maximum_vertical_padding: usize,
-    ) {
-        // turn the row, a list of blobs of text, into a list of lists of words, recording also the amount of blank space
-        // we need on either side of the words
-        let mut words: Vec<(usize, Vec<&str>, usize)> = row
-            .iter()
-            .enumerate()
-            .map(|(i, w)| {
-                (
-                    self.columns[i].padding_top,
-                    to_words(w),
-                    self.columns[i].padding_bottom,
-                )
-            })
-            .collect();
-        let mut current_lines: Vec<Vec<(String, String)>> = Vec::new();
-        // if all these lists are empty, just add a blank line (and maybe additional blank separator lines)
-        if words.iter().all(|(_, sentence, _)| sentence.is_empty()) {
-            for _ in 0..maximum_vertical_padding {
-                current_lines.push(
-                    self.columns
-                        .iter()
-                        .map(|c| (c.margin(), c.blank_line()))
-                        .collect(),
-                );
-            }
-            if !last_row {
-                for _ in 0..self.spaces_between_rows {
-                    current_lines.push(vec![(self.blank_line(), String::new())]);
-                }
-            }
+    /// Assign a particular column a minimum width as a fraction of the width of the viewport.
+    /// The fraction is resolved when the columns are laid out, so the same constraint suits
+    /// viewports of any width. If the column also has an absolute minimum width, the greater of
+    /// the two applies. Fractions outside the range 0 to 1 are clamped. By default columns have
+    /// no minimum width.
+    ///
+    /// A minimum width counts toward the space the columns require, so laying out columns
+    /// whose minimum widths cannot all fit in the viewport fails with
+    /// `ColonnadeError::InsufficientSpace`.
+    ///
+    /// # Arguments
+    ///
+    /// * `fraction` - The smallest fraction of the viewport the column may occupy.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 20)?;
+    /// colonnade.columns[0].min_width_fraction(0.25);
+    /// assert_eq!(vec!["a     b"], colonnade.tabulate([["a", "b"]])?);
+    /// # Ok(()) }
+    /// ```
+    pub fn min_width_fraction(&mut self, fraction: f64) -> &mut Self {
+        let fraction = if fraction.is_nan() {
+            0.0
         } else {
-            // otherwise, we build these lists into lines, we may use up some of these lists before others
-            while !words
-                .iter()
-                .all(|(pt, sentence, pb)| pb == &0 && pt == &0 && sentence.is_empty())
-            {
-                let mut pieces = vec![];
-                for (i, c) in self.columns.iter().enumerate() {
-                    let left_margin = c.margin();
-                    let mut line = String::new();
-                    let tuple = &mut words[i];
-                    if tuple.0 > 0 {
-                        line = c.blank_line();
-                        tuple.0 -= 1;
-                    } else if tuple.1.is_empty() {
-                        // we've used this one up, but there are still words to deal with in other sentences
-                        line = c.blank_line();
-                        if tuple.2 > 0 {
-                            tuple.2 -= 1;
-                        }
-                    } else {
-                        let mut l = c.padding_left;
-                        let mut phrase = " ".repeat(l);
-                        let mut first = true;
-                        while !tuple.1.is_empty() {
-                            let w = tuple.1.remove(0); // shift off the next word
-                            if first {
-                                let wl = true_width(w) + c.padding_right;
-                                if wl == c.width {
-                                    // word fills column
-                                    phrase += w;
-                                    break;
-                                } else if wl > c.width {
-                                    // word overflows column and we must split it
-                                    let hyphenating = c.hyphenating();
-                                    let mut offset = c.inner_width();
-                                    if hyphenating {
-                                        offset -= 1;
-                                    }
-                                    let graphemes = UnicodeSegmentation::graphemes(w, true)
-                                        .collect::<Vec<&str>>();
-                                    let prefix = graphemes[0..offset]
-                                        .iter()
-                                        .map(|&s| s)
-                                        .collect::<Vec<_>>()
-                                        .join("");
-                                    let byte_offset = prefix.len();
-                                    phrase += &prefix;
-                                    tuple.1.insert(0, &w[byte_offset..w.len()]); // unshift back the remaining fragment
-                                    if hyphenating {
-                                        phrase += "-";
-                                    }
-                                    break;
-                                }
-                            }
-                            // try to tack on a new word
-                            let new_length = l + true_width(w) + if first { 0 } else { 1 };
-                            if new_length + c.padding_right > c.width {
-                                tuple.1.insert(0, w);
-                                break;
-                            } else {
-                                if first {
-                                    first = false;
-                                } else {
-                                    phrase += " ";
-                                }
-                                phrase += w;
-                                l = new_length;
-                            }
-                        }
-                        // pad phrase out properly in its cell
-                        let true_width = true_width(phrase.as_str());
-                        if true_width < c.width {
-                            let surplus = c.width - true_width;
-                            match c.alignment {
-                                Alignment::Left => {
-                                    line += &phrase;
-                                    for _ in 0..surplus {
-                                        line += " "
-                                    }
-                                }
-                                Alignment::Center => {
-                                    let left_bit = surplus / 2;
-                                    for _ in 0..left_bit {
-                                        line += " "
-                                    }
-                                    line += &phrase;
-                                    for _ in 0..(surplus - left_bit) {
-                                        line += " "
-                                    }
-                                }
-                                Alignment::Right => {
-                                    for _ in 0..(surplus - c.padding_right) {
-                                        line += " "
-                                    }
-                                    line += &phrase;
-                                    for _ in 0..c.padding_right {
-                                        line += " "
-                                    }
-                                }
-                                Alignment::Justify => {
-                                    let words = phrase.split(" ").collect::<Vec<_>>(); // could be more efficient, but this allows simpler code structure
-                                    let last_words = tuple.1.is_empty();
-                                    if last_words || words.len() == 1 {
-                                        // treat as left-justified
-                                        line += &phrase;
-                                        for _ in 0..surplus {
-                                            line += " "
-                                        }
-                                    } else {
-                                        let gaps = words.len() - 1;
-                                        let rearrangeable = surplus + gaps - c.padding_right;
-                                        let min_spacer = rearrangeable / gaps;
-                                        let extra = rearrangeable - min_spacer * gaps;
-                                        let extra_offset = words.len() - extra;
-                                        for (i, word) in words.iter().enumerate() {
-                                            if i == 0 {
-                                                line += word;
-                                            } else {
-                                                for _ in 0..(min_spacer) {
-                                                    line += " ";
-                                                }
-                                                if i >= extra_offset {
-                                                    line += " ";
-                                                }
-                                                line += word;
-                                            }
-                                        }
-                                        for _ in 0..c.padding_right {
-                                            line += " "
-                                        }
-                                    }
-                                }
-                            }
-                        } else {
-                            line += &phrase;
-                        }
-                    }
-                    pieces.push((left_margin, line));
-                }
-                current_lines.push(pieces);
-            }
-            // now fix vertical alignment
-            'outer: for c in self.columns.iter() {
-                match c.vertical_alignment {
-                    VerticalAlignment::Top => (),
-                    _ => {
-                        let blank = c.blank_line();
-                        let end = current_lines.len() - c.padding_bottom;
-                        let mut movable_lines = 0;
-                        let mut pointer = end - 1;
-                        let top_pointer = c.padding_top;
-                        while current_lines[pointer][c.index].1 == blank {
-                            movable_lines += 1;
-                            if pointer == top_pointer {
-                                // this cell contains nothing but blank lines so alignment is irrelevant
-                                continue 'outer;
-                            }
-                            pointer -= 1;
-                        }
-                        if movable_lines == 0 {
-                            continue 'outer;
-                        }
-                        // pointer now points to the last movable line
-                        // top_pointer points to the insertion index where we can put blank lines
-                        // end points to an immovable index (perhaps beyond the end of the vector)
-                        let lines_to_move = if c.vertical_alignment == VerticalAlignment::Middle {
-                            movable_lines / 2
-                        } else {
+            fraction.clamp(0.0, 1.0)
+        };
+        self.min_width_fraction = Some(fraction);
+        self.revise();
+        self
+    }
+    /// Assign a particular maximum and minimum width to a particular column. By default columns have neither a maximum nor a minimum width.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - The common width.
+    ///
+    /// # Errors
+    ///
+    /// This method is a convenience method which assigns the column in question the same maximum and minimum width. Therefore
+    /// the errors thrown are those thrown by [`max_width`](#method.max_width) and [`min_width`](#method.min_width).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(4, 100)?;
+    /// // assign the first column a width of 20
+    /// colonnade.columns[0].fixed_width(20)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn fixed_width(&mut self, width: usize) -> Result<&mut Self, ColonnadeError> {
+        self.min_width = None;
+        self.min_width_fraction = None;
+        self.max_width = None;
+        self.max_width_fraction = None;
+        self.min_width(width)?;
+        self.max_width(width)?;
+        Ok(self)
+    }
+    /// Remove maximum or minimum column widths from a particular column.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(4, 100)?;
+    /// // initially assign all columns a width of 20
+    /// colonnade.fixed_width(20);
+    /// // but we want the first column to be flexible
+    /// colonnade.columns[0].clear_limits();
+    /// # Ok(()) }
+    /// ```
+    pub fn clear_limits(&mut self) -> &mut Self {
+        self.max_width = None;
+        self.max_width_fraction = None;
+        self.min_width = None;
+        self.min_width_fraction = None;
+        self.revise();
+        self
+    }
+    /// Assign a particular column a particular alignment. The default alignment is left.
+    ///
+    /// # Arguments
+    ///
+    /// * `alignment` - The desired alignment.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Alignment,Colonnade};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(4, 100)?;
+    /// // the first column should be right-aligned (it's numeric)
+    /// colonnade.columns[0].alignment(Alignment::Right);
+    /// # Ok(()) }
+    /// ```
+    pub fn alignment(&mut self, alignment: Alignment) -> &mut Self {
+        self.alignment = Some(alignment);
+        self
+    }
+    /// Assign a particular column a particular vertical alignment. The default alignment is top.
+    ///
+    /// # Arguments
+    ///
+    /// * `vertical_alignment` - The desired alignment.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Alignment,VerticalAlignment,Colonnade};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(4, 100)?;
+    /// // the first column should be right-aligned (it's numeric)
+    /// colonnade.columns[0].vertical_alignment(VerticalAlignment::Middle);
+    /// # Ok(()) }
+    /// ```
+    pub fn vertical_alignment(&mut self, vertical_alignment: VerticalAlignment) -> &mut Self {
+        self.vertical_alignment = vertical_alignment;
+        self
+    }
+    /// Assign a particular column a particular left margin. The left margin is a number of blank spaces
+    /// before the content of the column. By default the first column has a left margin of 0
+    /// and the other columns have a left margin of 1.
+    ///
+    /// # Arguments
+    ///
+    /// * `left_margin` - The width in blank spaces of the desired margin.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Alignment,Colonnade};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(4, 100)?;
+    /// colonnade.columns[0].left_margin(2);
+    /// # Ok(()) }
+    /// ```
+    pub fn left_margin(&mut self, left_margin: usize) -> &mut Self {
+        self.left_margin = left_margin;
+        self.revise();
+        self
+    }
+    /// Assign a particular column a right margin: a number of blank spaces after the column,
+    /// outside its padding, such as to reserve room for markers. In a maceration the right margin
+    /// is a piece of its own following the column's piece, with the spaces as its margin and
+    /// empty text. By default columns have no right margin.
+    ///
+    /// # Arguments
+    ///
+    /// * `right_margin` - The width in blank spaces of the desired margin.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 100)?;
+    /// colonnade.columns[0].right_margin(2);
+    /// assert_eq!(vec!["a   b"], colonnade.tabulate([["a", "b"]])?);
+    /// # Ok(()) }
+    /// ```
+    pub fn right_margin(&mut self, right_margin: usize) -> &mut Self {
+        self.right_margin = right_margin;
+        self.revise();
+        self
+    }
+    /// Assign a particular column a particular padding.
+    ///
+    /// See [`Colonnade::padding`](struct.Colonade.html#method.padding).
+    ///
+    /// # Arguments
+    ///
+    /// * `padding` - The width in blank spaces/lines of the desired padding.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Alignment,Colonnade};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(4, 100)?;
+    /// colonnade.columns[0].padding(1);
+    /// # Ok(()) }
+    /// ```
+    pub fn padding(&mut self, padding: usize) -> &mut Self {
+        self.padding_left = padding;
+        self.padding_right = padding;
+        self.padding_top = padding;
+        self.padding_bottom = padding;
+        self.revise();
+        self
+    }
+    /// Assign a particular column a particular horizontal padding -- space before and after the column's text.
+    ///
+    /// See [`Colonnade::padding`](struct.Colonade.html#method.padding).
+    ///
+    /// # Arguments
+    ///
+    /// * `padding` - The width in blank spaces/lines of the desired padding.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Alignment,Colonnade};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(4, 100)?;
+    /// colonnade.columns[0].padding_horizontal(1);
+    /// # Ok(()) }
+    /// ```
+    pub fn padding_horizontal(&mut self, padding: usize) -> &mut Self {
+        self.padding_left = padding;
+        self.padding_right = padding;
+        self.revise();
+        self
+    }
+    /// Assign a particular column a particular left padding -- space before the column's text.
+    ///
+    /// See [`Colonnade::padding`](struct.Colonade.html#method.padding).
+    ///
+    /// # Arguments
+    ///
+    /// * `padding` - The width in blank spaces/lines of the desired padding.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Alignment,Colonnade};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(4, 100)?;
+    /// colonnade.columns[0].padding_left(1);
+    /// # Ok(()) }
+    /// ```
+    pub fn padding_left(&mut self, padding: usize) -> &mut Self {
+        self.padding_left = padding;
+        self.revise();
+        self
+    }
+    /// Assign a particular column a particular right padding -- space after the column's text.
+    ///
+    /// See [`Colonnade::padding`](struct.Colonade.html#method.padding).
+    ///
+    /// # Arguments
+    ///
+    /// * `padding` - The width in blank spaces/lines of the desired padding.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Alignment,Colonnade};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(4, 100)?;
+    /// colonnade.columns[0].padding_right(1);
+    /// # Ok(()) }
+    /// ```
+    pub fn padding_right(&mut self, padding: usize) -> &mut Self {
+        self.padding_right = padding;
+        self.revise();
+        self
+    }
+    /// Assign a particular column a particular vertical padding -- blank lines before and after the column's text.
+    ///
+    /// See [`Colonnade::padding`](struct.Colonade.html#method.padding).
+    ///
+    /// # Arguments
+    ///
+    /// * `padding` - The width in blank spaces/lines of the desired padding.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Alignment,Colonnade};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(4, 100)?;
+    /// colonnade.columns[0].padding_vertical(1);
+    /// # Ok(()) }
+    /// ```
+    pub fn padding_vertical(&mut self, padding: usize) -> &mut Self {
+        self.padding_top = padding;
+        self.padding_bottom = padding;
+        self
+    }
+    /// Assign a particular column a particular top padding -- blank lines before the column's text.
+    ///
+    /// See [`Colonnade::padding`](struct.Colonade.html#method.padding).
+    ///
+    /// # Arguments
+    ///
+    /// * `padding` - The width in blank spaces/lines of the desired padding.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Alignment,Colonnade};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(4, 100)?;
+    /// colonnade.columns[0].padding_top(1);
+    /// # Ok(()) }
+    /// ```
+    pub fn padding_top(&mut self, padding: usize) -> &mut Self {
+        self.padding_top = padding;
+        self
+    }
+    /// Assign a particular column a particular bottom padding -- blank lines after the column's text.
+    ///
+    /// See [`Colonnade::padding`](struct.Colonade.html#method.padding).
+    ///
+    /// # Arguments
+    ///
+    /// * `padding` - The width in blank spaces/lines of the desired padding.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Alignment,Colonnade};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(4, 100)?;
+    /// colonnade.columns[0].padding_bottom(1);
+    /// # Ok(()) }
+    /// ```
+    pub fn padding_bottom(&mut self, padding: usize) -> &mut Self {
+        self.padding_bottom = padding;
+        self
+    }
+    /// Toggle whether words too wide to fit in the column are hyphenated when spit. By
+    /// default this is `true`. If there is only 1 character of available space in a column,
+    /// though, there is never any hyphenation.
+    ///
+    /// # Arguments
+    ///
+    /// * `hyphenate` - Whether to hyphenate when splitting words.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Alignment,Colonnade};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(1, 3)?;
+    /// colonnade.alignment(Alignment::Right);
+    /// for line in colonnade.tabulate(&[[1234]])? {
+    ///     println!("{}", line);
+    /// }
+    /// // 12-
+    /// //  34
+    /// colonnade.columns[0].hyphenate(false);
+    /// for line in colonnade.tabulate(&[[1234]])? {
+    ///     println!("{}", line);
+    /// }
+    /// // 123
+    /// //   4
+    /// # Ok(()) }
+    /// ```
+    pub fn hyphenate(&mut self, hyphenate: bool) -> &mut Self {
+        self.hyphenate = hyphenate;
+        self
+    }
+    /// Toggle whether wrapped text avoids ending with a line holding a single word, an orphan.
+    /// When the last word of the line before an orphan fits beside it, that word is moved down
+    /// to join it. This changes only where lines break, not the width of the column. By default
+    /// orphans are allowed.
+    ///
+    /// # Arguments
+    ///
+    /// * `avoid_orphans` - Whether to avoid orphans.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(1, 11)?;
+    /// let data = [["the quick brown fox"]];
+    /// assert_eq!(vec!["the quick  ", "brown fox  "], colonnade.tabulate(data)?);
+    /// let data = [["the quick brown fox jumps"]];
+    /// assert_eq!(vec!["the quick  ", "brown fox  ", "jumps      "], colonnade.tabulate(data)?);
+    /// colonnade.columns[0].avoid_orphans(true);
+    /// assert_eq!(vec!["the quick  ", "brown      ", "fox jumps  "], colonnade.tabulate(data)?);
+    /// # Ok(()) }
+    /// ```
+    pub fn avoid_orphans(&mut self, avoid_orphans: bool) -> &mut Self {
+        self.avoid_orphans = avoid_orphans;
+        self
+    }
+    /// Give the column a name. The name is used as the column's header in the
+    /// [header row](struct.Colonnade.html#method.header_row) and when rows are displayed as records.
+    ///
+    /// See [`Colonnade::expanded_display`](struct.Colonnade.html#method.expanded_display).
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The column's name.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// colonnade.columns[0].name("key");
+    /// colonnade.columns[1].name("value");
+    /// # Ok(()) }
+    /// ```
+    pub fn name(&mut self, name: &str) -> &mut Self {
+        self.name = Some(name.to_string());
+        self
+    }
+    /// Toggle whether the column is hidden. A hidden column still expects data -- it keeps its index
+    /// -- but it is excluded from layout and rendering. By default columns are visible.
+    ///
+    /// # Arguments
+    ///
+    /// * `hidden` - Whether to hide the column.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(3, 80)?;
+    /// colonnade.columns[1].hidden(true);
+    /// assert_eq!(vec!["a c"], colonnade.tabulate(&[["a", "b", "c"]])?);
+    /// # Ok(()) }
+    /// ```
+    pub fn hidden(&mut self, hidden: bool) -> &mut Self {
+        self.hidden = hidden;
+        self.revise();
+        self
+    }
+    /// Hide the column whenever the viewport is narrower than a particular width. Like the columns
+    /// of a responsive web table, less important columns then disappear automatically in narrow
+    /// viewports and reappear in wider ones. By default columns never collapse.
+    ///
+    /// # Arguments
+    ///
+    /// * `viewport_width` - The narrowest viewport in which the column is displayed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(3, 40)?;
+    /// colonnade.columns[2].collapse_below(60);
+    /// assert_eq!(vec!["a b"], colonnade.tabulate(&[["a", "b", "c"]])?);
+    /// colonnade.viewport(80)?;
+    /// assert_eq!(vec!["a b c"], colonnade.tabulate(&[["a", "b", "c"]])?);
+    /// # Ok(()) }
+    /// ```
+    pub fn collapse_below(&mut self, viewport_width: usize) -> &mut Self {
+        self.collapse_below = Some(viewport_width);
+        self.revise();
+        self
+    }
+    /// Format those cells of the column whose text parses as a number. By default cells are
+    /// displayed as they are.
+    ///
+    /// See [`NumberFormat`](struct.NumberFormat.html).
+    ///
+    /// # Arguments
+    ///
+    /// * `number_format` - The formatting to apply to numbers.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Colonnade, NumberFormat};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// colonnade.columns[1].number_format(NumberFormat::new().precision(1));
+    /// assert_eq!(vec!["pi 3.1", "e  2.7"], colonnade.tabulate([["pi", "3.14159"], ["e", "2.71828"]])?);
+    /// # Ok(()) }
+    /// ```
+    pub fn number_format(&mut self, number_format: NumberFormat) -> &mut Self {
+        self.number_format = Some(number_format);
+        self.revise();
+        self
+    }
+    /// Display a placeholder in place of empty cells -- those with no text but whitespace, those
+    /// made from `None`, and those added to short rows -- so they are not mistaken for missing
+    /// data. By default empty cells are blank.
+    ///
+    /// # Arguments
+    ///
+    /// * `placeholder` - The text to display in empty cells.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Cell, Colonnade};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// colonnade.columns[1].empty_placeholder("n/a");
+    /// let parents: [Option<&str>; 2] = [Some("Zeus"), None];
+    /// let data = vec![
+    ///     vec![Cell::from("Athena"), Cell::from(parents[0])],
+    ///     vec![Cell::from("Chaos"), Cell::from(parents[1])],
+    /// ];
+    /// assert_eq!(vec!["Athena Zeus", "Chaos  n/a "], colonnade.tabulate(&data)?);
+    /// # Ok(()) }
+    /// ```
+    pub fn empty_placeholder(&mut self, placeholder: &str) -> &mut Self {
+        self.empty_placeholder = Some(placeholder.to_string());
+        self.revise();
+        self
+    }
+    /// Format those cells of the column whose text parses as a date or time. By default cells are
+    /// displayed as they are.
+    ///
+    /// See [`DateTimeFormat`](struct.DateTimeFormat.html). This method requires the `chrono` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `datetime_format` - The formatting to apply to dates and times.
+    #[cfg(feature = "chrono")]
+    pub fn datetime_format(&mut self, datetime_format: DateTimeFormat) -> &mut Self {
+        self.datetime_format = Some(datetime_format);
+        self.revise();
+        self
+    }
+    /// Display those cells of the column whose text parses as a number as a human-readable
+    /// quantity, such as `1.2 GiB` or `2h 03m`. Humanized cells are marked
+    /// [`numeric`](struct.Cell.html#method.numeric), so unless the column has an explicit alignment
+    /// each quantity, number and unit together, is right-aligned. By default cells are displayed as
+    /// they are.
+    ///
+    /// # Arguments
+    ///
+    /// * `humanize` - The units of the numbers in the column.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Colonnade, Humanize};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(3, 80)?;
+    /// colonnade.columns[1].humanize(Humanize::Bytes);
+    /// colonnade.columns[2].humanize(Humanize::Seconds);
+    /// let data = [("backup.tar", 1288490189, 7380), ("notes.txt", 512, 42)];
+    /// assert_eq!(
+    ///     vec!["backup.tar 1.2 GiB 2h 03m", "notes.txt    512 B    42s"],
+    ///     colonnade.tabulate(data)?
+    /// );
+    /// # Ok(()) }
+    /// ```
+    pub fn humanize(&mut self, humanize: Humanize) -> &mut Self {
+        self.humanize = Some(humanize);
+        self.revise();
+        self
+    }
+    /// Display those cells of the column whose text parses as a number as amounts of money: the
+    /// currency symbol sits at the left edge of the cell and the amount is right-aligned in the
+    /// remaining space. Amounts are formatted with the column's
+    /// [`number_format`](#method.number_format) or, if it has none, with two decimal places and
+    /// commas between groups of digits. By default cells are displayed as they are.
+    ///
+    /// # Arguments
+    ///
+    /// * `symbol` - The currency symbol.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// colonnade.columns[1].currency("$").min_width(12)?;
+    /// let data = [("rent", 1234), ("coffee", 3)];
+    /// assert_eq!(
+    ///     vec!["rent   $   1,234.00", "coffee $       3.00"],
+    ///     colonnade.tabulate(data)?
+    /// );
+    /// # Ok(()) }
+    /// ```
+    pub fn currency(&mut self, symbol: &str) -> &mut Self {
+        self.currency = Some(symbol.to_string());
+        self.revise();
+        self
+    }
+    /// Display those cells of the column whose text parses as a number as percentages. Percentages
+    /// are marked [`numeric`](struct.Cell.html#method.numeric), so unless the column has an
+    /// explicit alignment they are right-aligned. By default cells are displayed as they are.
+    ///
+    /// See [`Percentage`](struct.Percentage.html).
+    ///
+    /// # Arguments
+    ///
+    /// * `percentage` - The formatting to apply to numbers.
+    pub fn percentage(&mut self, percentage: Percentage) -> &mut Self {
+        self.percentage = Some(percentage);
+        self.revise();
+        self
+    }
+    /// Display those cells of the column whose text parses as a number as horizontal bars.
+    /// By default cells are displayed as they are.
+    ///
+    /// See [`BarChart`](struct.BarChart.html).
+    ///
+    /// # Arguments
+    ///
+    /// * `bar_chart` - How to draw the bars.
+    pub fn bar_chart(&mut self, bar_chart: BarChart) -> &mut Self {
+        self.bar_chart = Some(bar_chart);
+        self.revise();
+        self
+    }
+    /// Color the numbers in the column according to their place in the column's range of values,
+    /// for a heat map. By default cells are not colored.
+    ///
+    /// See [`HeatScale`](struct.HeatScale.html).
+    ///
+    /// # Arguments
+    ///
+    /// * `heat` - The scale of colors from the smallest value to the largest.
+    pub fn heat(&mut self, heat: HeatScale) -> &mut Self {
+        self.heat = Some(heat);
+        self
+    }
+    /// Style the cells of the column. This is the layer of the styles
+    /// [resolved](struct.Colonnade.html#method.default_style) into the style of a cell above the
+    /// table's default style. By default the column has no style.
+    ///
+    /// # Arguments
+    ///
+    /// * `style` - The style of the column's cells, or `None` for no styling.
+    pub fn style(&mut self, style: Option<Style>) -> &mut Self {
+        self.style = style;
+        self
+    }
+    /// Make the cells of the column hyperlinks, clickable in terminals which support OSC 8
+    /// escape sequences. The text of each line of a cell, less the spaces aligning it, is linked
+    /// to the target the provider gives for the cell, if any. The header row is not linked, nor
+    /// is anything in [plain text](struct.Colonnade.html#method.plain_text) mode. By default cells
+    /// are not linked.
+    ///
+    /// # Arguments
+    ///
+    /// * `provider` - A function of the index of a row as displayed, the
+    ///   [header row](struct.Colonnade.html#method.header_row) counted first, and the cell, which
+    ///   returns the URL the cell links to.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// colonnade.columns[0].hyperlink(|_, cell| {
+    ///     Some(format!("https://github.com/dfhoughton/colonnade/issues/{}", cell.text()))
+    /// });
+    /// assert_eq!(
+    ///     vec![
+    ///         "\u{1b}]8;;https://github.com/dfhoughton/colonnade/issues/12\u{1b}\\12\u{1b}]8;;\u{1b}\\ fix widths"
+    ///     ],
+    ///     colonnade.tabulate(&[["12", "fix widths"]])?
+    /// );
+    /// # Ok(()) }
+    /// ```
+    pub fn hyperlink<F>(&mut self, provider: F) -> &mut Self
+    where
+        F: Fn(usize, &Cell) -> Option<String> + Send + Sync + 'static,
+    {
+        self.hyperlink = Some(Hyperlink(Arc::new(provider)));
+        self
+    }
+    /// Make the column virtual: rather than taking its cells from the data, compute each from the
+    /// other cells in its row. The rows of data omit virtual columns, so the function receives the
+    /// cells of a row as given, before any formatting, and the data itself is left untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `computation` - The function computing the cell from the row's other cells.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Cell, Colonnade, NumberFormat};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(4, 80)?;
+    /// colonnade.columns[3]
+    ///     .computed(|row| {
+    ///         let number = |i: usize| row[i].text().parse::<f64>().unwrap_or(f64::NAN);
+    ///         Cell::new(number(2) / number(1))
+    ///     })
+    ///     .number_format(NumberFormat::new().precision(2));
+    /// let data = [("Ann", 3, 5), ("Bo", 4, 2)];
+    /// assert_eq!(
+    ///     vec!["Ann 3 5 1.67", "Bo  4 2 0.50"],
+    ///     colonnade.tabulate(data)?
+    /// );
+    /// # Ok(()) }
+    /// ```
+    pub fn computed<F>(&mut self, computation: F) -> &mut Self
+    where
+        F: Fn(&[Cell]) -> Cell<'static> + Send + Sync + 'static,
+    {
+        self.computation = Some(Computation(Arc::new(computation)));
+        self.revise();
+        self
+    }
+    /// Make the column decorative: every row displays the same literal text, such as `"=>"`, and
+    /// the rows of data omit the column. The column is never shrunk narrower than the literal and
+    /// the literal is not formatted.
+    ///
+    /// # Arguments
+    ///
+    /// * `literal` - The text to display in every row.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(3, 80)?;
+    /// colonnade.columns[1].literal("=>");
+    /// let data = [["red", "stop"], ["green", "go"]];
+    /// assert_eq!(vec!["red   => stop", "green => go  "], colonnade.tabulate(data)?);
+    /// # Ok(()) }
+    /// ```
+    pub fn literal(&mut self, literal: &str) -> &mut Self {
+        self.literal = Some(literal.to_string());
+        self.revise();
+        self
+    }
+    /// Aggregate the numbers in the column in a row of subtotals at the end of each
+    /// [group](struct.Colonnade.html#method.group_by) and a row of totals at the end of the
+    /// table. The rows are labeled "subtotal" and "total" in the first column without an
+    /// aggregate. Aggregates are formatted like the rest of the column. By default columns are
+    /// not aggregated.
+    ///
+    /// # Arguments
+    ///
+    /// * `aggregate` - How to aggregate the column.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Aggregate, Colonnade};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(3, 80)?;
+    /// colonnade.group_by(Some(0))?;
+    /// colonnade.columns[2].aggregate(Aggregate::Sum);
+    /// let data = [("fruit", "apple", 3), ("vegetable", "kale", 1), ("fruit", "pear", 2)];
+    /// assert_eq!(
+    ///     vec![
+    ///         "fruit           ",
+    ///         "         apple 3",
+    ///         "         pear  2",
+    ///         "subtotal       5",
+    ///         "vegetable       ",
+    ///         "         kale  1",
+    ///         "subtotal       1",
+    ///         "total          6",
+    ///     ],
+    ///     colonnade.tabulate(data)?
+    /// );
+    /// # Ok(()) }
+    /// ```
+    pub fn aggregate(&mut self, aggregate: Aggregate) -> &mut Self {
+        self.aggregate = Some(aggregate);
+        self.revise();
+        self
+    }
+    // color the numbers in the column by their place in its range
+    fn apply_heat(&self, table: &mut [Vec<Cell>]) {
+        let heat = match &self.heat {
+            Some(heat) => heat,
+            None => return,
+        };
+        let i = self.index;
+        let values = Colonnade::values(table, i);
+        let (min, max) = heat.range.unwrap_or_else(|| {
+            values
+                .iter()
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &(_, v)| {
+                    (lo.min(v), hi.max(v))
+                })
+        });
+        for (r, v) in values {
+            let fraction = if max > min {
+                (v - min) / (max - min)
+            } else {
+                0.0
+            };
+            table[r][i].style = Some(heat.style(fraction));
+        }
+    }
+    // the part of a line which is right-aligned when the first word is pinned to the left edge of
+    // the cell, such as the amount of a currency cell or the value beside a bar
+    fn pinned_remainder<'a>(&self, cell: &Cell, phrase: &'a str) -> Option<&'a str> {
+        if let Some(symbol) = self.currency.as_ref().filter(|_| cell.numeric) {
+            phrase
+                .trim_start()
+                .strip_prefix(symbol.as_str())?
+                .strip_prefix(' ')
+        } else if cell.pinned {
+            phrase.trim_start().split_once(' ').map(|(_, value)| value)
+        } else {
+            None
+        }
+    }
+    // replace the numbers in the column with bars scaled to the column's width
+    fn draw_bars(&self, state: &ColumnState, table: &mut [Vec<Cell>], widths: &Widths) {
+        let chart = match &self.bar_chart {
+            Some(chart) => chart,
+            None => return,
+        };
+        let i = self.index;
+        let values = Colonnade::values(table, i);
+        let max = chart
+            .max
+            .unwrap_or_else(|| values.iter().fold(0.0, |acc, &(_, v)| v.max(acc)));
+        let value_width = if chart.hide_values {
+            0
+        } else {
+            values
+                .iter()
+                .map(|&(r, _)| true_width(table[r][i].text.trim(), widths) + 1)
+                .max()
+                .unwrap_or(0)
+        };
+        let width = self.inner_width(state).saturating_sub(value_width);
+        for (r, v) in values {
+            let fraction = if max > 0.0 { v / max } else { 0.0 };
+            let bar = BarChart::bar(fraction, width);
+            let cell = &mut table[r][i];
+            if chart.hide_values {
+                // the bar grows from the left edge
+                cell.text = bar.into();
+                cell.numeric = false;
+            } else if !bar.is_empty() {
+                cell.text = format!("{} {}", bar, cell.text.trim()).into();
+                cell.pinned = true;
+            }
+        }
+    }
+    // apply the column's formatting to a cell
+    fn format(&self, cell: &mut Cell) {
+        cell.value = match &self.number_format {
+            Some(f) => f.parse(&cell.text),
+            None => NumberFormat::default().parse(&cell.text),
+        }
+        .map(|(_, n)| n);
+        if let Some(symbol) = &self.currency {
+            let amount = match &self.number_format {
+                Some(f) => f.format(&cell.text),
+                None => NumberFormat::new()
+                    .thousands_separator(',')
+                    .precision(2)
+                    .format(&cell.text),
+            };
+            if let Some(amount) = amount {
+                cell.text = format!("{} {}", symbol, amount).into();
+                cell.numeric = true;
+            }
+            return;
+        }
+        if let Some((text, clamped)) = self.percentage.as_ref().and_then(|p| p.format(&cell.text)) {
+            cell.text = text.into();
+            cell.numeric = true;
+            cell.clamped = clamped;
+            return;
+        }
+        let formatted = self
+            .number_format
+            .as_ref()
+            .and_then(|f| f.format(&cell.text))
+            .or_else(|| self.humanize.as_ref().and_then(|h| h.format(&cell.text)));
+        #[cfg(feature = "chrono")]
+        let formatted = formatted.or_else(|| {
+            self.datetime_format
+                .as_ref()
+                .and_then(|f| f.format(&cell.text))
+        });
+        if let Some(text) = formatted {
+            cell.text = text.into();
+            cell.numeric = true;
+        }
+    }
+}
+
+/// The lines of a rendered table, which can be printed directly or iterated over.
+///
+/// See [`Colonnade::render`](struct.Colonnade.html#method.render).
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate colonnade;
+/// # use colonnade::Colonnade;
+/// # use std::error::Error;
+/// # fn demo() -> Result<(), Box<dyn Error>> {
+/// let mut colonnade = Colonnade::new(2, 80)?;
+/// let table = colonnade.render(&[["a", "b"], ["c", "d"]])?;
+/// assert_eq!((3, 2), (table.width(), table.height()));
+/// println!("{}", table);
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tabulation {
+    lines: Vec<String>,
+    width: usize,
+}
+
+impl Tabulation {
+    fn new(lines: Vec<String>, widths: &Widths) -> Tabulation {
+        let width = lines
+            .iter()
+            .map(|l| true_width(&strip_ansi_escapes::strip_str(l), widths))
+            .max()
+            .unwrap_or(0);
+        Tabulation { lines, width }
+    }
+    /// The width in characters of the widest line, not counting ANSI escape sequences.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    /// The number of lines.
+    pub fn height(&self) -> usize {
+        self.lines.len()
+    }
+    /// The lines of the table.
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+    /// The table as plain text suitable for comparison with a golden file: ANSI escape sequences
+    /// are removed, non-breaking spaces become ordinary spaces, trailing whitespace is trimmed from
+    /// every line, and every line ends with `\n`, whatever the platform.
+    ///
+    /// See [`Colonnade::render_snapshot`](struct.Colonnade.html#method.render_snapshot).
+    pub fn snapshot(&self) -> String {
+        let mut snapshot = String::new();
+        for line in &self.lines {
+            let plain = strip_ansi_escapes::strip_str(line).replace('\u{A0}', " ");
+            snapshot.push_str(plain.trim_end());
+            snapshot.push('\n');
+        }
+        snapshot
+    }
+}
+
+impl fmt::Display for Tabulation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, line) in self.lines.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            f.write_str(line)?;
+        }
+        Ok(())
+    }
+}
+
+impl IntoIterator for Tabulation {
+    type Item = String;
+    type IntoIter = std::vec::IntoIter<String>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.lines.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Tabulation {
+    type Item = &'a String;
+    type IntoIter = std::slice::Iter<'a, String>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.lines.iter()
+    }
+}
+
+impl From<Tabulation> for Vec<String> {
+    fn from(tabulation: Tabulation) -> Vec<String> {
+        tabulation.lines
+    }
+}
+
+// the number of blank lines between two rows, given their indices
+type SpacingFn = dyn Fn(usize, usize) -> usize + Send + Sync;
+
+#[derive(Clone)]
+struct RowSpacing(Arc<SpacingFn>);
+
+impl fmt::Debug for RowSpacing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RowSpacing")
+    }
+}
+
+// the function styling rows by their indices and cells
+type RowStyleFn = dyn Fn(usize, &[Cell]) -> Option<Style> + Send + Sync;
+
+#[derive(Clone)]
+struct RowStyle(Arc<RowStyleFn>);
+
+impl fmt::Debug for RowStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RowStyle")
+    }
+}
+
+// data on its way to being rendered
+struct Digest<'a> {
+    // the data, laid out unless it is to be displayed as records
+    table: Vec<Vec<Cell<'a>>>,
+    // the data rendered as records, if it is to be displayed as such
+    records: Option<Maceration>,
+    // the number of rows left out for lack of space
+    omitted: usize,
+}
+
+/// A struct holding formatting information. This is the object which tabulates data.
+#[derive(Debug, Clone)]
+pub struct Colonnade {
+    pub columns: Vec<Column>,
+    width: usize,
+    spaces_between_rows: usize,
+    row_spacing: Option<RowSpacing>,
+    fill_viewport: bool,
+    degradation: Degradation,
+    layout_strategy: LayoutStrategy,
+    target_height: Option<usize>,
+    overflow_policy: OverflowPolicy,
+    expanded_display: ExpandedDisplay,
+    max_rows: Option<usize>,
+    row_height: Option<usize>,
+    omission_marker: bool,
+    ragged_rows: RaggedRows,
+    bidi: Bidi,
+    mirrored: bool,
+    // how wide the terminal renders grapheme clusters
+    widths: Widths,
+    strict: bool,
+    // what the most recent layout computed
+    layout: Layout,
+    header_row: bool,
+    wrap_gutter: Option<char>,
+    // whether space is reserved for the row markers of a diff
+    diffing: bool,
+    sort: Vec<SortKey>,
+    group_by: Option<usize>,
+    total_style: Option<Style>,
+    header_style: Option<Style>,
+    separator_style: Option<Style>,
+    default_style: Option<Style>,
+    row_style: Option<RowStyle>,
+    // the styles alternating from one group of rows to the next
+    group_stripes: Vec<Style>,
+    // the order in which columns are displayed, if not their own
+    order: Option<Vec<usize>>,
+    // groups of columns which share a width
+    shared_widths: Vec<Vec<usize>>,
+    // the names of columns whose widths are kept in proportion, and the proportion
+    width_ratios: Vec<(String, String, f64)>,
+    trim_trailing: bool,
+    pad_lines: bool,
+    slack_in_margins: bool,
+    // whether escape sequences are kept out of the output altogether
+    plain_text: bool,
+    separator: Option<Separator>,
+    // how empty rows are represented, if they stand for separators
+    separator_rows: Option<Separator>,
+    borders: Borders,
+    suppress_outer_padding: bool,
+}
+
+#[cfg(feature = "nbsp")]
+fn to_words<'a>(s: &'a str) -> Vec<&'a str> {
+    lazy_static! {
+        static ref SPLITTABLE_SPACE: Regex = Regex::new(r"[\s&&[^\u00A0]]+").unwrap();
+    }
+    SPLITTABLE_SPACE
+        .split(s)
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<&'a str>>()
+}
+
+#[cfg(not(feature = "nbsp"))]
+fn to_words<'a>(s: &'a str) -> Vec<&'a str> {
+    s.split_whitespace()
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<&'a str>>()
+}
+
+// find the longest sequence of non-whitespace characters in a string
+fn longest_word(s: &str, widths: &Widths) -> usize {
+    to_words(s).iter().fold(0, |acc, v| {
+        let c = true_width(v, widths);
+        if c > acc {
+            c
+        } else {
+            acc
+        }
+    })
+}
+
+// whether text is written right to left: whether its first letter is of a script written so
+fn is_rtl(text: &str) -> bool {
+    text.chars().find(|c| c.is_alphabetic()).is_some_and(|c| {
+        matches!(c,
+            '\u{0590}'..='\u{08ff}'
+            | '\u{fb1d}'..='\u{fdff}'
+            | '\u{fe70}'..='\u{feff}'
+            | '\u{10800}'..='\u{10fff}'
+            | '\u{1e800}'..='\u{1efff}')
+    })
+}
+
+// the characters controlling the direction of text, which take no space
+fn is_bidi_control(grapheme: &str) -> bool {
+    matches!(
+        grapheme,
+        "\u{200e}"
+            | "\u{200f}"
+            | "\u{202a}"
+            | "\u{202b}"
+            | "\u{202c}"
+            | "\u{202d}"
+            | "\u{202e}"
+            | "\u{2066}"
+            | "\u{2067}"
+            | "\u{2068}"
+            | "\u{2069}"
+    )
+}
+
+// wrap text, less any spaces about it, in first strong isolate and pop directional isolate
+fn isolate(text: &str) -> String {
+    let glyphs = text.trim_matches(' ');
+    if glyphs.is_empty() {
+        return text.to_string();
+    }
+    let start = text.len() - text.trim_start_matches(' ').len();
+    format!(
+        "{}\u{2068}{}\u{2069}{}",
+        &text[..start],
+        glyphs,
+        &text[start + glyphs.len()..]
+    )
+}
+
+// wrap text, less any spaces about it, in the OSC 8 escape sequences making it a hyperlink
+fn link(target: &str, text: &str) -> String {
+    let glyphs = text.trim_matches(' ');
+    if glyphs.is_empty() {
+        return text.to_string();
+    }
+    let start = text.len() - text.trim_start_matches(' ').len();
+    format!(
+        "{}\u{1b}]8;;{}\u{1b}\\{}\u{1b}]8;;\u{1b}\\{}",
+        &text[..start],
+        target,
+        glyphs,
+        &text[start + glyphs.len()..]
+    )
+}
+
+// remove any ANSI escape sequences from the text of a cell
+fn strip_escapes(cell: &mut Cell) {
+    // only text containing control characters is changed by stripping, so only it is copied
+    if cell.text.contains(|ch: char| ch.is_control()) {
+        let bytes = strip_ansi_escapes::strip(cell.text.as_bytes());
+        cell.text = String::from_utf8(bytes).unwrap_or_else(|_| panic!("failed to restores bytes to utf8 string after stripping ansi escape sequences from {}", cell.text)).into();
+    }
+}
+
+// how wide the terminal renders grapheme clusters
+#[derive(Debug, Clone, Default)]
+struct Widths {
+    // the widths given to ranges of characters, the last given first
+    overrides: Vec<(RangeInclusive<char>, usize)>,
+    #[cfg(feature = "unicode-width")]
+    profile: WidthProfile,
+}
+
+impl Widths {
+    // whether every grapheme cluster is one column wide
+    fn uniform(&self) -> bool {
+        #[cfg(feature = "unicode-width")]
+        if self.profile != WidthProfile::Legacy {
+            return false;
+        }
+        self.overrides.is_empty()
+    }
+    // the width of a grapheme cluster
+    fn of(&self, grapheme: &str) -> usize {
+        if is_bidi_control(grapheme) {
+            return 0;
+        }
+        let overridden = grapheme
+            .chars()
+            .next()
+            .and_then(|c| self.overrides.iter().find(|(range, _)| range.contains(&c)));
+        match overridden {
+            Some(&(_, width)) => width,
+            #[cfg(feature = "unicode-width")]
+            None => self.profile.width(grapheme),
+            #[cfg(not(feature = "unicode-width"))]
+            None => 1,
+        }
+    }
+}
+
+/// How wide a terminal renders emoji and the characters of East Asian scripts. Terminals
+/// disagree, so tables containing such characters are aligned only if the profile matches the
+/// terminal. See [`Colonnade::width_profile`](struct.Colonnade.html#method.width_profile).
+#[cfg(feature = "unicode-width")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WidthProfile {
+    /// every grapheme cluster is one column wide
+    #[default]
+    Legacy,
+    /// grapheme clusters are as wide as recent Unicode data says: East Asian wide characters
+    /// and emoji, including sequences of emoji joined by zero width joiners and characters given
+    /// emoji presentation by a variation selector, are two columns wide, as in kitty
+    Kitty,
+    /// a grapheme cluster is as wide as its characters together, each measured by Unicode data,
+    /// so a sequence of joined emoji is as wide as its parts and a variation selector doesn't
+    /// widen a character, as in iTerm2
+    ITerm2,
+}
+
+#[cfg(feature = "unicode-width")]
+impl WidthProfile {
+    /// The profile with the given name, if there is one: `"legacy"`, `"kitty"`, or `"iterm2"`.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the profile, in any case.
+    pub fn named(name: &str) -> Option<WidthProfile> {
+        match name.to_lowercase().as_str() {
+            "legacy" => Some(WidthProfile::Legacy),
+            "kitty" => Some(WidthProfile::Kitty),
+            "iterm2" => Some(WidthProfile::ITerm2),
+            _ => None,
+        }
+    }
+    // the width of a grapheme cluster
+    fn width(self, grapheme: &str) -> usize {
+        use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+        match self {
+            WidthProfile::Legacy => 1,
+            WidthProfile::Kitty => grapheme.width(),
+            WidthProfile::ITerm2 => grapheme.chars().filter_map(|c| c.width()).sum(),
+        }
+    }
+}
+
+fn true_width(s: &str, widths: &Widths) -> usize {
+    let graphemes = UnicodeSegmentation::graphemes(s, true).filter(|g| !is_bidi_control(g));
+    if widths.uniform() {
+        return graphemes.count();
+    }
+    graphemes.map(|g| widths.of(g)).sum()
+}
+
+// the number of the leading grapheme clusters which fit in width
+fn fitting(graphemes: &[&str], width: usize, widths: &Widths) -> usize {
+    let mut total = 0;
+    for (i, g) in graphemes.iter().enumerate() {
+        total += widths.of(g);
+        if total > width {
+            return i;
+        }
+    }
+    graphemes.len()
+}
+
+// break s into lines no wider than width, splitting words only where they are wider than width
+fn wrap(s: &str, width: usize, widths: &Widths) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = vec![];
+    let mut line = String::new();
+    for word in to_words(s) {
+        let mut graphemes = UnicodeSegmentation::graphemes(word, true).collect::<Vec<&str>>();
+        if !line.is_empty() {
+            if true_width(&line, widths) + 1 + true_width(word, widths) <= width {
+                line += " ";
+                line += word;
+                continue;
+            }
+            lines.push(line);
+        }
+        while fitting(&graphemes, width, widths) < graphemes.len() {
+            // a grapheme wider than the line goes on a line of its own
+            let n = fitting(&graphemes, width, widths).max(1);
+            lines.push(graphemes.drain(0..n).collect());
+        }
+        line = graphemes.concat();
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+// the widths of the graphemes of each word of s, so the text can be wrapped at many widths
+// without measuring it again
+fn measure_words(s: &str, widths: &Widths) -> Vec<Vec<usize>> {
+    to_words(s)
+        .into_iter()
+        .map(|word| {
+            UnicodeSegmentation::graphemes(word, true)
+                .map(|g| widths.of(g))
+                .collect()
+        })
+        .collect()
+}
+
+// the number of lines wrap breaks text into at the given width, given the measurements of its
+// words
+fn wrapped_height(words: &[Vec<usize>], width: usize) -> usize {
+    let width = width.max(1);
+    let mut lines = 0;
+    // the width of the line being filled, if it holds anything
+    let mut line: Option<usize> = None;
+    for word in words {
+        let length: usize = word.iter().sum();
+        if let Some(l) = line {
+            if l + 1 + length <= width {
+                line = Some(l + 1 + length);
+                continue;
+            }
+            lines += 1;
+        }
+        let mut graphemes = &word[..];
+        loop {
+            let mut total = 0;
+            let fitting = graphemes
+                .iter()
+                .position(|&w| {
+                    total += w;
+                    total > width
+                })
+                .unwrap_or(graphemes.len());
+            if fitting == graphemes.len() {
+                break;
+            }
+            // a grapheme wider than the line goes on a line of its own
+            lines += 1;
+            graphemes = &graphemes[fitting.max(1)..];
+        }
+        line = if graphemes.is_empty() {
+            None
+        } else {
+            Some(graphemes.iter().sum())
+        };
+    }
+    if line.is_some() || lines == 0 {
+        lines += 1;
+    }
+    lines
+}
+
+// represent n with commas separating groups of thousands
+fn group_thousands(n: usize) -> String {
+    let digits = n.to_string();
+    let head = match digits.len() % 3 {
+        0 => 3,
+        h => h,
+    };
+    let mut s = digits[..head].to_string();
+    for group in digits.as_bytes()[head..].chunks(3) {
+        s.push(',');
+        s += std::str::from_utf8(group).unwrap();
+    }
+    s
+}
+
+/// The width of the viewport for output to a terminal. In order of precedence this is
+///
+/// 1. the explicit `width`, if any
+/// 2. the value of the `COLUMNS` environment variable, if it is a positive integer
+/// 3. the width of the terminal attached to standard output, if any
+/// 4. `default_width`
+///
+/// The terminal is consulted only with the `terminal` feature.
+///
+/// # Arguments
+///
+/// * `width` - An explicit width overriding auto-detection, such as the value of a `--width` option
+/// * `default_width` - The width to use when there is no terminal
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate colonnade;
+/// # use colonnade::{terminal_width, Colonnade};
+/// # use std::error::Error;
+/// # fn demo() -> Result<(), Box<dyn Error>> {
+/// assert_eq!(60, terminal_width(Some(60), 80));
+/// let colonnade = Colonnade::new(4, terminal_width(None, 80))?;
+/// # Ok(()) }
+/// ```
+pub fn terminal_width(width: Option<usize>, default_width: usize) -> usize {
+    if let Some(width) = width {
+        return width;
+    }
+    if let Some(columns) = std::env::var("COLUMNS")
+        .ok()
+        .and_then(|c| c.trim().parse::<usize>().ok())
+        .filter(|&c| c > 0)
+    {
+        return columns;
+    }
+    #[cfg(feature = "terminal")]
+    if let Some((terminal_size::Width(w), _)) = terminal_size::terminal_size() {
+        return w as usize;
+    }
+    default_width
+}
+
+// cut s down to at most width characters, marking any cut with an ellipsis
+fn truncate(s: &str, width: usize, widths: &Widths) -> String {
+    if true_width(s, widths) <= width {
+        return s.to_string();
+    }
+    let graphemes = UnicodeSegmentation::graphemes(s, true).collect::<Vec<&str>>();
+    if width < 2 {
+        graphemes[0..fitting(&graphemes, width, widths)].concat()
+    } else {
+        graphemes[0..fitting(&graphemes, width - 1, widths)].concat() + "…"
+    }
+}
+
+impl Colonnade {
+    /// Construct a `Colonnade` with default values: left alignment, no column size
+    /// constraints, no blank lines between rows, 1 space margin between columns.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - The number of columns of data to expect
+    /// * `width` - Viewport size in characters
+    ///
+    /// # Errors
+    ///
+    /// * `ColonnadeError::InsufficientSpace` - the viewport isn't wide enough for the columns and their margins
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// let colonnade = Colonnade::new(4, 100);
+    /// ```
+    pub fn new(columns: usize, width: usize) -> Result<Colonnade, ColonnadeError> {
+        if columns == 0 {
+            return Err(ColonnadeError::InsufficientColumns);
+        }
+        let mut columns: Vec<Column> = (0..columns).map(Column::default).collect();
+        columns[0].left_margin = 0;
+        let spec = Colonnade {
+            columns,
+            width,
+            spaces_between_rows: 0,
+            row_spacing: None,
+            fill_viewport: false,
+            degradation: Degradation::Error,
+            layout_strategy: LayoutStrategy::Legacy,
+            target_height: None,
+            overflow_policy: OverflowPolicy::Wrap,
+            expanded_display: ExpandedDisplay::Never,
+            max_rows: None,
+            row_height: None,
+            omission_marker: false,
+            ragged_rows: RaggedRows::PadShort,
+            bidi: Bidi::Ignore,
+            mirrored: false,
+            widths: Widths::default(),
+            strict: false,
+            layout: Layout::default(),
+            header_row: false,
+            wrap_gutter: None,
+            diffing: false,
+            sort: vec![],
+            group_by: None,
+            total_style: None,
+            header_style: Some(Style::new().bold(true).underline(true)),
+            separator_style: None,
+            default_style: None,
+            row_style: None,
+            group_stripes: vec![],
+            order: None,
+            shared_widths: vec![],
+            width_ratios: vec![],
+            trim_trailing: false,
+            pad_lines: false,
+            slack_in_margins: false,
+            plain_text: false,
+            separator: None,
+            separator_rows: None,
+            borders: Borders::default(),
+            suppress_outer_padding: false,
+        };
+        if !spec.sufficient_space() {
+            return Err(spec.insufficient_space());
+        }
+        Ok(spec)
+    }
+    /// Construct a `Colonnade` for tabulating values of a [`Tabular`](trait.Tabular.html) type. There
+    /// is a column for each of the type's headers, named after it and with its alignment, and the
+    /// [header row](#method.header_row) is displayed.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - Viewport size in characters
+    ///
+    /// # Errors
+    ///
+    /// * `ColonnadeError::InsufficientColumns` - the type has no headers
+    /// * `ColonnadeError::InsufficientSpace` - the viewport isn't wide enough for the columns and their margins
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Cell, Colonnade, Tabular, ToRow};
+    /// # use std::error::Error;
+    /// struct Planet {
+    ///     name: &'static str,
+    ///     moons: usize,
+    /// }
+    /// # impl ToRow for Planet {
+    /// #     fn to_row(&self) -> Vec<Cell<'_>> {
+    /// #         vec![Cell::new(self.name), Cell::new(self.moons).numeric(true)]
+    /// #     }
+    /// # }
+    /// # impl Tabular for Planet {
+    /// #     fn headers() -> Vec<String> {
+    /// #         vec!["name".to_string(), "moons".to_string()]
+    /// #     }
+    /// # }
+    /// // implement ToRow and Tabular, or derive Tabular with the derive feature
+    ///
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let planets = vec![
+    ///     Planet { name: "Earth", moons: 1 },
+    ///     Planet { name: "Jupiter", moons: 95 },
+    /// ];
+    /// let lines = Colonnade::for_tabular::<Planet>(80)?.tabulate(&planets)?;
+    /// assert_eq!(vec!["name    moons", "Earth       1", "Jupiter    95"], lines);
+    /// # Ok(()) }
+    /// ```
+    pub fn for_tabular<T: Tabular>(width: usize) -> Result<Colonnade, ColonnadeError> {
+        let headers = T::headers();
+        let mut colonnade = Colonnade::new(headers.len(), width)?;
+        for (c, header) in colonnade.columns.iter_mut().zip(headers.iter()) {
+            c.name(header);
+        }
+        for (c, alignment) in colonnade.columns.iter_mut().zip(T::alignments()) {
+            if let Some(alignment) = alignment {
+                c.alignment(alignment);
+            }
+        }
+        colonnade.header_row(true);
+        Ok(colonnade)
+    }
+    /// Construct a `Colonnade` whose viewport is the width of the terminal attached to standard
+    /// output, or `default_width` if standard output is not a terminal. The `COLUMNS` environment
+    /// variable, if set, takes precedence over the terminal.
+    ///
+    /// See [`terminal_width`](fn.terminal_width.html).
+    ///
+    /// This method requires the `terminal` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - The number of columns of data to expect
+    /// * `default_width` - Viewport size in characters when there is no terminal
+    ///
+    /// # Errors
+    ///
+    /// * `ColonnadeError::InsufficientSpace` - the viewport isn't wide enough for the columns and their margins
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// let colonnade = Colonnade::for_terminal(4, 80);
+    /// ```
+    #[cfg(feature = "terminal")]
+    pub fn for_terminal(columns: usize, default_width: usize) -> Result<Colonnade, ColonnadeError> {
+        Colonnade::new(columns, terminal_width(None, default_width))
+    }
+    /// Tabulate a sequence of key/value pairs with a [`key_value`](#method.key_value) colonnade.
+    /// The pairs are displayed in the order given.
+    ///
+    /// # Arguments
+    ///
+    /// * `pairs` - The key/value pairs to display: a map or any other source of pairs
+    /// * `width` - Viewport size in characters
+    ///
+    /// # Errors
+    ///
+    /// Any errors of [`tabulate`](#method.tabulate).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::collections::BTreeMap;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut map = BTreeMap::new();
+    /// map.insert("name", "colonnade");
+    /// map.insert("license", "MIT");
+    /// let lines = Colonnade::tabulate_pairs(&map, 80)?;
+    /// assert_eq!(vec!["license MIT      ", "   name colonnade"], lines);
+    /// # Ok(()) }
+    /// ```
+    pub fn tabulate_pairs<T, K, V>(pairs: T, width: usize) -> Result<Vec<String>, ColonnadeError>
+    where
+        T: IntoIterator<Item = (K, V)>,
+        K: fmt::Display,
+        V: fmt::Display,
+    {
+        let pairs = pairs
+            .into_iter()
+            .map(|(key, value)| [Cell::new(key), Cell::new(value)])
+            .collect::<Vec<_>>();
+        Colonnade::key_value(width)?.tabulate(&pairs)
+    }
+    /// Like [`tabulate_pairs`](#method.tabulate_pairs), but the pairs are sorted by key. This
+    /// gives a stable display for maps, such as `HashMap`, whose iteration order is arbitrary.
+    ///
+    /// # Arguments
+    ///
+    /// * `map` - The key/value pairs to display
+    /// * `width` - Viewport size in characters
+    ///
+    /// # Errors
+    ///
+    /// Any errors of [`tabulate`](#method.tabulate).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::collections::HashMap;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut map = HashMap::new();
+    /// map.insert("name", "colonnade");
+    /// map.insert("license", "MIT");
+    /// let lines = Colonnade::tabulate_map(&map, 80)?;
+    /// assert_eq!(vec!["license MIT      ", "   name colonnade"], lines);
+    /// # Ok(()) }
+    /// ```
+    pub fn tabulate_map<T, K, V>(map: T, width: usize) -> Result<Vec<String>, ColonnadeError>
+    where
+        T: IntoIterator<Item = (K, V)>,
+        K: fmt::Display + Ord,
+        V: fmt::Display,
+    {
+        let mut pairs = map.into_iter().collect::<Vec<_>>();
+        pairs.sort_by(|(a, _), (b, _)| a.cmp(b));
+        Colonnade::tabulate_pairs(pairs, width)
+    }
+    /// Construct a `Colonnade` styled by a [preset](struct.Preset.html).
+    ///
+    /// # Arguments
+    ///
+    /// * `preset` - The style common to all the columns.
+    /// * `columns` - The number of columns of data to expect.
+    /// * `width` - Viewport size in characters.
+    ///
+    /// # Errors
+    ///
+    /// * `ColonnadeError::InsufficientColumns` - The column count is 0.
+    /// * `ColonnadeError::InsufficientSpace` - The styled columns require more space than is available in the viewport.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Colonnade, Preset};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let preset = Preset::new().left_margin(2);
+    /// let mut colonnade = Colonnade::with_preset(&preset, 2, 80)?;
+    /// assert_eq!(vec!["  a  b"], colonnade.tabulate([["a", "b"]])?);
+    /// # Ok(()) }
+    /// ```
+    pub fn with_preset(
+        preset: &Preset,
+        columns: usize,
+        width: usize,
+    ) -> Result<Colonnade, ColonnadeError> {
+        let mut colonnade = Colonnade::new(columns, usize::MAX)?;
+        colonnade.width = width;
+        preset.apply(&mut colonnade)?;
+        Ok(colonnade)
+    }
+    /// Construct a two-column `Colonnade` for key/value pairs, such as a block of configuration
+    /// settings or statistics. The keys are right-aligned and have the highest priority, so when
+    /// space is short the values wrap first.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - Viewport size in characters
+    ///
+    /// # Errors
+    ///
+    /// * `ColonnadeError::InsufficientSpace` - the viewport isn't wide enough for the columns and their margins
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::key_value(20)?;
+    /// let stats = [("files", "12"), ("largest", "README.md at 10 KB")];
+    /// assert_eq!(
+    ///     vec!["  files 12          ", "largest README.md at", "        10 KB       "],
+    ///     colonnade.tabulate(&stats)?
+    /// );
+    /// # Ok(()) }
+    /// ```
+    pub fn key_value(width: usize) -> Result<Colonnade, ColonnadeError> {
+        let mut colonnade = Colonnade::new(2, width)?;
+        colonnade.columns[0].alignment(Alignment::Right).priority(0);
+        colonnade.columns[1].priority(1);
+        Ok(colonnade)
+    }
+    // the indices of the columns that take part in layout and rendering, from left to right
+    fn displayed(&self) -> Vec<usize> {
+        let mut displayed: Vec<usize> = match &self.order {
+            Some(order) => order.clone(),
+            None => (0..self.len()).collect(),
+        }
+        .into_iter()
+        .filter(|&i| self.columns[i].is_displayed(self.state(i), self.width))
+        .collect();
+        if self.mirrored {
+            displayed.reverse();
+        }
+        displayed
+    }
+    // the margin a column has on its leading side; whatever column is displayed first takes the
+    // first column's margin, and the first column, if displayed elsewhere, takes its margin
+    fn leading_margin(&self, i: usize) -> usize {
+        let displayed = self.displayed();
+        let first = if self.mirrored {
+            displayed.last()
+        } else {
+            displayed.first()
+        };
+        let first = first.cloned().unwrap_or(0);
+        if i == first {
+            self.columns[0].left_margin
+        } else if i == 0 {
+            self.columns[first].left_margin
+        } else {
+            self.columns[i].left_margin
+        }
+    }
+    // the left margin of a column as rendered; in a mirrored table the margins trade sides
+    fn margin_width(&self, i: usize) -> usize {
+        if self.mirrored {
+            self.columns[i].right_margin
+        } else {
+            self.leading_margin(i)
+        }
+    }
+    // the right margin of a column as rendered
+    fn right_margin_width(&self, i: usize) -> usize {
+        if self.mirrored {
+            self.leading_margin(i)
+        } else {
+            self.columns[i].right_margin
+        }
+    }
+    fn margin(&self, i: usize) -> String {
+        " ".repeat(self.margin_width(i))
+    }
+    // the absolute minimal space that might fit this table assuming some data in every column
+    fn minimal_width(&self) -> usize {
+        self.displayed().into_iter().fold(0, |acc: usize, i| {
+            acc.saturating_add(self.margin_width(i))
+                .saturating_add(self.right_margin_width(i))
+                .saturating_add(self.least_width(i)) // assume each column requires at least one character
+        })
+    }
+    // the least width a column may be given; in strict mode this leaves room for text inside the
+    // padding, otherwise it is a single character
+    fn least_width(&self, i: usize) -> usize {
+        let c = &self.columns[i];
+        if self.strict {
+            c.narrowest_in(self.state(i), self.width)
+        } else {
+            c.minimum_width_in(self.state(i), self.width).max(1)
+        }
+    }
+    // an error explaining the space required by the columns
+    fn insufficient_space(&self) -> ColonnadeError {
+        let columns = self
+            .displayed()
+            .into_iter()
+            .map(|i| ColumnSpace {
+                index: i,
+                name: self.columns[i].name.clone(),
+                margin: self.margin_width(i) + self.right_margin_width(i),
+                padding: self.columns[i].horizontal_padding(),
+                minimum: self.least_width(i),
+            })
+            .collect();
+        ColonnadeError::InsufficientSpace(SpaceDiagnostics {
+            viewport: self.width,
+            required: self
+                .minimal_width()
+                .saturating_add(self.width - self.available_width()),
+            columns,
+        })
+    }
+    // the number of columns that are not displayed
+    fn omitted(&self) -> usize {
+        self.len() - self.displayed().len()
+    }
+    // the marker appended to lines to indicate omitted columns, if any and if it fits in the viewport
+    // move the spaces aligning text within its cells into the margins, if they belong there
+    fn release_slack(&self, lines: &mut [Vec<(String, String)>]) {
+        if !self.slack_in_margins {
+            return;
+        }
+        let displayed = self.displayed();
+        for line in lines.iter_mut() {
+            let mut slack = String::new();
+            for (position, &i) in displayed.iter().enumerate() {
+                let c = &self.columns[i];
+                let (margin, text) = &mut line[position];
+                let glyphs = text.trim_matches(' ').to_string();
+                let (before, after) = if glyphs.is_empty() {
+                    let width = true_width(text, &self.widths);
+                    text.clear();
+                    (width, 0)
+                } else {
+                    let leading = text.len() - text.trim_start_matches(' ').len();
+                    let trailing = text.len() - text.trim_end_matches(' ').len();
+                    let left = leading.min(c.padding_left);
+                    let right = trailing.min(c.padding_right);
+                    *text = format!("{}{}{}", " ".repeat(left), glyphs, " ".repeat(right));
+                    (leading - left, trailing - right)
+                };
+                *margin = format!("{}{}{}", slack, margin, " ".repeat(before));
+                slack = " ".repeat(after);
+            }
+            match line.get_mut(displayed.len()) {
+                // the omission marker
+                Some((margin, _)) => margin.insert_str(0, &slack),
+                None => line.push((slack, String::new())),
+            }
+        }
+    }
+    // a line of a row in which every cell is blank
+    fn blank_row_line(&self, marker: &Option<String>) -> Vec<(String, String)> {
+        let mut pieces: Vec<(String, String)> = self
+            .displayed()
+            .into_iter()
+            .map(|i| (self.margin(i), self.columns[i].blank_line(self.state(i))))
+            .collect();
+        if let Some(marker) = marker {
+            pieces.push((" ".to_string(), marker.clone()));
+        }
+        pieces
+    }
+    // the stripe of the group of a cell's row, if any
+    fn stripe(&self, cell: &Cell) -> Option<&Style> {
+        match cell.group {
+            Some(g) if !self.group_stripes.is_empty() => {
+                Some(&self.group_stripes[g % self.group_stripes.len()])
+            }
+            _ => None,
+        }
+    }
+    fn marker(&self) -> Option<String> {
+        if self.omission_marker && self.omitted() > 0 {
+            let marker = format!("…+{}", self.omitted());
+            if true_width(&marker, &self.widths) < self.width {
+                return Some(marker);
+            }
+        }
+        None
+    }
+    // the width of the wrap gutter and diff markers and the spaces following them
+    fn gutter_width(&self) -> usize {
+        let mut width = 0;
+        if self.wrap_gutter.is_some() {
+            width += 2;
+        }
+        if self.diffing {
+            width += 2;
+        }
+        width
+    }
+    // the width available to the displayed columns
+    fn available_width(&self) -> usize {
+        let width = self.width.saturating_sub(self.gutter_width());
+        match self.marker() {
+            Some(marker) => width.saturating_sub(true_width(&marker, &self.widths) + 1),
+            None => width,
+        }
+    }
+    fn sufficient_space(&self) -> bool {
+        self.minimal_width() <= self.available_width()
+    }
+    // the amount of space required to display the data given the current column specs
+    fn required_width(&self) -> usize {
+        self.displayed().into_iter().fold(0, |acc: usize, i| {
+            acc.saturating_add(self.margin_width(i))
+                .saturating_add(self.right_margin_width(i))
+                .saturating_add(self.columns[i].effective_width(self.state(i)))
+        })
+    }
+    // the width of the table, including any omission marker and wrap gutter
+    fn table_width(&self) -> usize {
+        let width = self.required_width().saturating_add(self.gutter_width());
+        match self.marker() {
+            Some(marker) => width.saturating_add(1 + true_width(&marker, &self.widths)),
+            None => width,
+        }
+    }
+    // a line separating rows in a table of the given width
+    fn separator_line(&self, width: usize) -> Vec<(String, String)> {
+        self.fill_line(self.separator.as_ref(), width)
+    }
+    // a line of the given width represented as the separator specifies
+    fn fill_line(&self, separator: Option<&Separator>, width: usize) -> Vec<(String, String)> {
+        let line = match separator {
+            Some(Separator::Empty) => String::new(),
+            Some(Separator::Fill(fill)) if true_width(fill, &self.widths) > 0 => {
+                let mut line = String::new();
+                let mut length = 0;
+                for g in UnicodeSegmentation::graphemes(fill.as_str(), true).cycle() {
+                    let w = true_width(g, &self.widths);
+                    if length + w > width {
+                        break;
+                    }
+                    line += g;
+                    length += w;
+                }
+                // a wide grapheme may leave a gap at the end
+                line + &" ".repeat(width - length)
+            }
+            _ => " ".repeat(width),
+        };
+        vec![(line, String::new())]
+    }
+    // the padding above and below the cells of a column in a row, less any suppressed at the
+    // edges of the table
+    fn row_padding(&self, i: usize, first_row: bool, last_row: bool) -> (usize, usize) {
+        let c = &self.columns[i];
+        let suppressed = |edge: bool, padding: usize| {
+            if edge && self.suppress_outer_padding {
+                0
+            } else {
+                padding
+            }
+        };
+        (
+            suppressed(first_row, c.padding_top),
+            suppressed(last_row, c.padding_bottom),
+        )
+    }
+    fn maximum_vertical_padding(&self) -> usize {
+        let mut p = 0;
+        for c in self
+            .columns
+            .iter()
+            .filter(|c| c.is_displayed(self.state(c.index), self.width))
+        {
+            let p2 = c.vertical_padding();
+            if p2 > p {
+                p = p2;
+            }
+        }
+        p
+    }
+    fn len(&self) -> usize {
+        self.columns.len()
+    }
+    // determine the characters required to represent s after whitespace normalization
+    fn width_after_normalization(&self, s: &str) -> usize {
+        let mut l = 0;
+        for w in to_words(s) {
+            if l != 0 {
+                l += 1;
+            }
+            l += true_width(w, &self.widths);
+        }
+        l
+    }
+    /// Returns the width of the colonnade in columns if the colonnade has already laid out data
+    /// and knows how much space this data will require.
+    pub fn width(&self) -> Option<usize> {
+        if self.adjusted() {
+            Some(self.table_width())
+        } else {
+            None
+        }
+    }
+    // returns priorites sorted lowest to highest
+    fn priorities(&self) -> Vec<usize> {
+        let mut v = self
+            .columns
+            .iter()
+            .filter(|c| c.is_displayed(self.state(c.index), self.width))
+            .map(|c| c.priority)
+            .collect::<Vec<_>>();
+        v.sort_unstable();
+        v.dedup();
+        v.reverse();
+        v
+    }
+    /// Converts the raw data in `table` into a vector of strings representing the data in tabular form.
+    /// Blank lines will be zero-width rather than full-width lines of whitespace.
+    ///
+    /// If you need finer control over the text, for instance, if you want to add color codes, see
+    /// [`macerate`](#method.macerate).
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - The data to display: a sequence of [rows](trait.ToRow.html).
+    ///
+    /// # Errors
+    ///
+    /// Any errors of [`lay_out`](#method.lay_out). If the data has already been laid out, this method will throw no errors.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(4, 100)?;
+    /// let data = vec![vec!["some", "words", "for", "example"]];
+    /// let lines = colonnade.tabulate(&data)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn tabulate<T, R>(&mut self, table: T) -> Result<Vec<String>, ColonnadeError>
+    where
+        T: IntoIterator<Item = R>,
+        R: ToRow,
+    {
+        let maceration = self.macerate(table)?;
+        Ok(self.lines(maceration))
+    }
+    /// Like [`tabulate`](#method.tabulate), but returns a [`Tabulation`](struct.Tabulation.html),
+    /// which implements `Display`, so the table can be printed directly, and knows its width and
+    /// height.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - The data to display: a sequence of [rows](trait.ToRow.html).
+    ///
+    /// # Errors
+    ///
+    /// Any errors of [`tabulate`](#method.tabulate).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// println!("{}", colonnade.render(&[["some", "words"], ["for", "example"]])?);
+    /// # Ok(()) }
+    /// ```
+    pub fn render<T, R>(&mut self, table: T) -> Result<Tabulation, ColonnadeError>
+    where
+        T: IntoIterator<Item = R>,
+        R: ToRow,
+    {
+        let lines = self.tabulate(table)?;
+        Ok(Tabulation::new(lines, &self.widths))
+    }
+    /// Render the table as a single string whose form does not vary with the platform, the
+    /// terminal, or styling, for golden-file tests of programs which print tables. See
+    /// [`Tabulation::snapshot`](struct.Tabulation.html#method.snapshot).
+    ///
+    /// Note that where cells contain non-breaking spaces the `nbsp` feature still decides where
+    /// their text may wrap.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - The data to display: a sequence of [rows](trait.ToRow.html).
+    ///
+    /// # Errors
+    ///
+    /// Any errors of [`tabulate`](#method.tabulate).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// let snapshot = colonnade.render_snapshot(&[["a", "bbb"], ["ccc", "d"]])?;
+    /// assert_eq!("a   bbb\nccc d\n", snapshot);
+    /// # Ok(()) }
+    /// ```
+    pub fn render_snapshot<T, R>(&mut self, table: T) -> Result<String, ColonnadeError>
+    where
+        T: IntoIterator<Item = R>,
+        R: ToRow,
+    {
+        self.render(table).map(|t| t.snapshot())
+    }
+    /// Split delimited text into rows and tabulate them in one call, for pretty-printing simple
+    /// delimited data. Rows with more or fewer fields than there are columns are handled
+    /// according to the [ragged row policy](#method.ragged_rows).
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The delimited text, one row per line.
+    /// * `delimiter` - The character separating fields.
+    /// * `quoted` - Whether double quotes enclose fields, as in CSV. See [`split_delimited`](fn.split_delimited.html).
+    ///
+    /// # Errors
+    ///
+    /// Any errors of [`tabulate`](#method.tabulate).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// let lines = colonnade.tabulate_text("fruit,count\n\"apple, red\",3", ',', true)?;
+    /// assert_eq!(vec!["fruit      count", "apple, red 3    "], lines);
+    /// # Ok(()) }
+    /// ```
+    pub fn tabulate_text(
+        &mut self,
+        input: &str,
+        delimiter: char,
+        quoted: bool,
+    ) -> Result<Vec<String>, ColonnadeError> {
+        self.tabulate(split_delimited(input, delimiter, quoted))
+    }
+    /// Chew up the text into bits suitable for piecemeal layout.
+    ///
+    /// More specifically, `macerate` digests the raw data in `table` into a vector of vectors of `(String, String)` tuples
+    /// representing the data in tabular form. Each tuple consists of a whitespace left margin and
+    /// the contents of a column. Separator lines will consist of a margin and text tuple where the
+    /// text is zero-width and the "margin" is as wide as the table.
+    ///
+    /// Maceration is useful if you wish to insert color codes to colorize the data or otherwise
+    /// manipulate the data post-layout. If you don't want to do this, see [`tabulate`](#method.tabulate).
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - The data to display: a sequence of [rows](trait.ToRow.html).
+    ///
+    /// # Errors
+    ///
+    /// Any errors of [`lay_out`](#method.lay_out). If the data has already been laid out, this method will throw no errors.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate term;
+    /// // ... [some details omitted]
+    /// # extern crate colonnade;
+    /// # use colonnade::{Alignment, Colonnade};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// // text to put in tabular form
+    /// let text = vec![
+    ///     vec![
+    ///         "Colonnade lets you format text in columns.",
+    ///         "As you can see, it supports text alignment, viewport width, and column widths.",
+    ///         "It doesn't natively support color codes, but it is easy enough to combine with a crate like term.",
+    ///     ],
+    ///     vec!["", "Two or more rows of columns makes a table.", ""],
+    /// ];
+    /// let mut colonnade = Colonnade::new(3, 80)?;
+    ///
+    /// // configure the table a bit
+    /// colonnade.spaces_between_rows(1).left_margin(4)?.fixed_width(15)?;
+    /// colonnade.columns[0].alignment(Alignment::Right).left_margin(8);
+    /// colonnade.columns[1].alignment(Alignment::Center).clear_limits();
+    /// // if the text is in colored cells, you will probably want some padding
+    /// colonnade.padding(1)?;
+    /// ///
+    /// // now print out the table
+    /// let mut t = term::stdout().unwrap();
+    /// for row in colonnade.macerate(&text)? {
+    ///     for line in row {
+    ///         for (i, (margin, text)) in line.iter().enumerate() {
+    ///             write!(t, "{}", margin)?;
+    ///             let background_color = if i % 2 == 0 {
+    ///                 term::color::WHITE
+    ///             } else {
+    ///                 term::color::BLACK
+    ///             };
+    ///             let foreground_color = match i % 3 {
+    ///                 1 => term::color::GREEN,
+    ///                 2 => term::color::RED,
+    ///                 _ => term::color::BLUE,
+    ///             };
+    ///             t.bg(background_color)?;
+    ///             t.fg(foreground_color)?;
+    ///             write!(t, "{}", text)?;
+    ///             t.reset()?;
+    ///         }
+    ///         println!();
+    ///     }
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn macerate<T, R>(&mut self, table: T) -> Result<Maceration, ColonnadeError>
+    where
+        T: IntoIterator<Item = R>,
+        R: ToRow,
+    {
+        let rows: Vec<R> = table.into_iter().collect();
+        let owned_table = self.own_table(&rows)?;
+        let digest = self.digest(owned_table)?;
+        let mut buffer = match &digest.records {
+            Some(records) => records.clone(),
+            None => self.macerate_columns(&digest.table)?,
+        };
+        self.layout.row_heights = buffer.iter().map(Vec::len).collect();
+        buffer.append(&mut self.notes(&digest));
+        Ok(buffer)
+    }
+    /// Like [`macerate`](#method.macerate), but rather than collecting the pieces of the table
+    /// hands each in turn to a callback, along with an output to write to, so pieces can be
+    /// colored or otherwise decorated as they are written. A line break is written after each
+    /// line. Rows are rendered one at a time, so the whole maceration is never held in memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - The data to display: a sequence of [rows](trait.ToRow.html).
+    /// * `out` - Where the table is written.
+    /// * `write` - A function of the index of a row, the index of a piece in its line, the piece,
+    ///   and the output, which writes the piece.
+    ///
+    /// # Errors
+    ///
+    /// * `ColonnadeError::Write` - The callback or the output failed.
+    ///
+    /// Any errors of [`macerate`](#method.macerate).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// let mut out = String::new();
+    /// colonnade.macerate_with(&[["a", "b"], ["c", "d"]], &mut out, |_, column, piece, out| {
+    ///     if column == 1 {
+    ///         write!(out, "{}\u{1b}[1m{}\u{1b}[0m", piece.margin, piece.text)
+    ///     } else {
+    ///         write!(out, "{}{}", piece.margin, piece.text)
+    ///     }
+    /// })?;
+    /// assert_eq!("a \u{1b}[1mb\u{1b}[0m\nc \u{1b}[1md\u{1b}[0m\n", out);
+    /// # Ok(()) }
+    /// ```
+    pub fn macerate_with<T, R, F>(
+        &mut self,
+        table: T,
+        out: &mut dyn fmt::Write,
+        mut write: F,
+    ) -> Result<(), ColonnadeError>
+    where
+        T: IntoIterator<Item = R>,
+        R: ToRow,
+        F: FnMut(usize, usize, &Piece, &mut dyn fmt::Write) -> fmt::Result,
+    {
+        let widths = self.widths.clone();
+        self.stream(table, |index, row, kinds, columns| {
+            Colonnade::write_row(index, row, kinds, columns, &widths, out, &mut write)
+        })
+    }
+    // render a table a row at a time, handing each to a function along with the kind of each of
+    // its lines, lines of columns being of the kind Cell, and the columns and widths of the pieces
+    // of lines of columns
+    fn stream<T, R, F>(&mut self, table: T, mut emit: F) -> Result<(), ColonnadeError>
+    where
+        T: IntoIterator<Item = R>,
+        R: ToRow,
+        F: FnMut(
+            usize,
+            &[Vec<(String, String)>],
+            &[PieceKind],
+            &[Option<(usize, usize)>],
+        ) -> Result<(), ColonnadeError>,
+    {
+        let rows: Vec<R> = table.into_iter().collect();
+        let owned_table = self.own_table(&rows)?;
+        let digest = self.digest(owned_table)?;
+        let mut heights = vec![];
+        let mut index = 0;
+        // the kinds of the lines of a row whose last lines, as many as given, separate it from
+        // the next
+        let kinds = |kind, height, separators| {
+            let body = height - separators;
+            (0..height)
+                .map(|j| if j < body { kind } else { PieceKind::Separator })
+                .collect::<Vec<_>>()
+        };
+        match &digest.records {
+            Some(records) => {
+                for (i, row) in records.iter().enumerate() {
+                    heights.push(row.len());
+                    let separators = if i + 1 < records.len() {
+                        self.spacing_after(i).max(1)
+                    } else {
+                        0
+                    };
+                    let kinds = kinds(PieceKind::Record, row.len(), separators);
+                    emit(index, row, &kinds, &[])?;
+                    index += 1;
+                }
+            }
+            None => {
+                let p = self.blank_row_height();
+                let columns = self.piece_columns();
+                for (i, row) in digest.table.iter().enumerate() {
+                    let last_row = i == digest.table.len() - 1;
+                    let kind = match row.first() {
+                        Some(c) if c.spanning => PieceKind::Group,
+                        Some(c) if c.rule => PieceKind::Rule,
+                        _ => PieceKind::Cell,
+                    };
+                    let mut buffer = vec![];
+                    self.add_row(&mut buffer, row, i, last_row, p)?;
+                    for lines in buffer {
+                        heights.push(lines.len());
+                        let separators = if last_row {
+                            0
+                        } else {
+                            self.spacing_after(i).min(lines.len())
+                        };
+                        let mut kinds = kinds(kind, lines.len(), separators);
+                        // rules drawn by the borders separate rows as well
+                        for (kind, line) in kinds.iter_mut().zip(lines.iter()) {
+                            if *kind == PieceKind::Cell && line.len() == 1 && line[0].1.is_empty() {
+                                *kind = PieceKind::Separator;
+                            }
+                        }
+                        emit(index, &lines, &kinds, &columns)?;
+                        index += 1;
+                    }
+                }
+            }
+        }
+        self.layout.row_heights = heights;
+        for row in self.notes(&digest) {
+            emit(index, &row, &vec![PieceKind::Note; row.len()], &[])?;
+            index += 1;
+        }
+        Ok(())
+    }
+    // what a piece of a line of the given kind is, and the index and width of its column, if it
+    // belongs to one
+    fn classify(
+        kind: PieceKind,
+        position: usize,
+        text: &str,
+        columns: &[Option<(usize, usize)>],
+    ) -> (PieceKind, Option<(usize, usize)>) {
+        if kind != PieceKind::Cell {
+            return (kind, None);
+        }
+        match columns.get(position) {
+            Some(Some(column)) => (PieceKind::Cell, Some(*column)),
+            Some(None) => (PieceKind::Margin, None),
+            // the omission marker, or slack following the last column
+            None if text.is_empty() => (PieceKind::Margin, None),
+            None => (PieceKind::Marker, None),
+        }
+    }
+    // the indices and widths of the columns the pieces of a line of columns belong to; right
+    // margins belong to none
+    fn piece_columns(&self) -> Vec<Option<(usize, usize)>> {
+        let mut columns = vec![];
+        for i in self.displayed() {
+            columns.push(Some((i, self.state(i).width)));
+            if self.right_margin_width(i) > 0 {
+                columns.push(None);
+            }
+        }
+        columns
+    }
+    // hand the pieces of a row to a callback, ending each line with a line break
+    fn write_row<F>(
+        index: usize,
+        row: &[Vec<(String, String)>],
+        kinds: &[PieceKind],
+        columns: &[Option<(usize, usize)>],
+        widths: &Widths,
+        out: &mut dyn fmt::Write,
+        write: &mut F,
+    ) -> Result<(), ColonnadeError>
+    where
+        F: FnMut(usize, usize, &Piece, &mut dyn fmt::Write) -> fmt::Result,
+    {
+        for (line, &kind) in row.iter().zip(kinds) {
+            for (column, (margin, text)) in line.iter().enumerate() {
+                let (kind, belongs) = Colonnade::classify(kind, column, text, columns);
+                let piece = Piece {
+                    margin,
+                    text,
+                    width: true_width(&strip_ansi_escapes::strip_str(text), widths),
+                    column_width: belongs.map(|(_, w)| w),
+                    kind,
+                };
+                write(index, column, &piece, out).map_err(|_| ColonnadeError::Write)?;
+            }
+            out.write_char('\n').map_err(|_| ColonnadeError::Write)?;
+        }
+        Ok(())
+    }
+    /// Like [`tabulate`](#method.tabulate), but the text of each cell, alignment and padding
+    /// included, is passed through a [`Styler`](trait.Styler.html), so cells can be colored with
+    /// whatever color crate an application already uses. The [header row](#method.header_row) is
+    /// given the [header style](#method.header_style), if there is one, and lines separating
+    /// rows the [separator style](#method.separator_style). Rows displayed as
+    /// [records](enum.ExpandedDisplay.html) and notes are not styled, nor is anything in
+    /// [plain text](#method.plain_text) mode.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - The data to display: a sequence of [rows](trait.ToRow.html).
+    /// * `styler` - What styles the cells.
+    ///
+    /// # Errors
+    ///
+    /// Any errors of [`tabulate`](#method.tabulate).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{CellContext, Color, Colonnade, Style};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// let red = Style::new().foreground(Color::Red);
+    /// let styler = |ctx: CellContext, text: &str| {
+    ///     if ctx.column == 1 {
+    ///         red.paint(text)
+    ///     } else {
+    ///         text.to_string()
+    ///     }
+    /// };
+    /// assert_eq!(
+    ///     vec!["error \u{1b}[31mdisk full\u{1b}[0m"],
+    ///     colonnade.tabulate_styled(&[["error", "disk full"]], &styler)?
+    /// );
+    /// # Ok(()) }
+    /// ```
+    pub fn tabulate_styled<T, R, S>(
+        &mut self,
+        table: T,
+        styler: &S,
+    ) -> Result<Vec<String>, ColonnadeError>
+    where
+        T: IntoIterator<Item = R>,
+        R: ToRow,
+        S: Styler + ?Sized,
+    {
+        let header = self.header_row;
+        let header_style = self.header_style.clone();
+        let separator_style = self.separator_style.clone();
+        let plain = self.plain_text;
+        let mut maceration = vec![];
+        self.stream(table, |row, lines, kinds, columns| {
+            let mut lines = lines.to_vec();
+            for (line, &kind) in lines.iter_mut().zip(kinds).filter(|_| !plain) {
+                for (position, piece) in line.iter_mut().enumerate() {
+                    match Colonnade::classify(kind, position, &piece.1, columns) {
+                        (PieceKind::Cell, Some((column, _))) => {
+                            let ctx = CellContext {
+                                row,
+                                column,
+                                header: header && row == 0,
+                            };
+                            piece.1 = match &header_style {
+                                Some(style) if ctx.header => style.paint(&piece.1),
+                                _ => styler.style(ctx, &piece.1),
+                            };
+                        }
+                        // blank separators are left blank
+                        (PieceKind::Separator, _) | (PieceKind::Rule, _)
+                            if !piece.0.trim().is_empty() =>
+                        {
+                            if let Some(style) = &separator_style {
+                                piece.0 = style.paint(&piece.0);
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+            }
+            maceration.push(lines);
+            Ok(())
+        })?;
+        Ok(self.lines(maceration))
+    }
+    /// Like [`tabulate`](#method.tabulate), but returns only a window of the lines of the table.
+    /// The layout is the same as for the whole table, but lines outside the window are not kept,
+    /// which is useful for scrollable views of large tables.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - The data to display.
+    /// * `start_line` - The index of the first line to return.
+    /// * `count` - The maximum number of lines to return.
+    ///
+    /// # Errors
+    ///
+    /// Any errors of [`tabulate`](#method.tabulate).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// let data = vec![vec!["a", "b"], vec!["c", "dd"], vec!["e", "f"]];
+    /// assert_eq!(vec!["c dd"], colonnade.tabulate_window(&data, 1, 1)?);
+    /// # Ok(()) }
+    /// ```
+    pub fn tabulate_window<T, R>(
+        &mut self,
+        table: T,
+        start_line: usize,
+        count: usize,
+    ) -> Result<Vec<String>, ColonnadeError>
+    where
+        T: IntoIterator<Item = R>,
+        R: ToRow,
+    {
+        let rows: Vec<R> = table.into_iter().collect();
+        let owned_table = self.own_table(&rows)?;
+        let digest = self.digest(owned_table)?;
+        let end_line = start_line.saturating_add(count);
+        let mut lines = vec![];
+        let mut line_number = 0;
+        let mut window = |buffer: Maceration| {
+            for line in self.lines(buffer) {
+                if line_number >= start_line && line_number < end_line {
+                    lines.push(line);
+                }
+                line_number += 1;
+            }
+            line_number >= end_line
+        };
+        let full = match &digest.records {
+            Some(records) => window(records.clone()),
+            None => {
+                let p = self.blank_row_height();
+                let mut full = false;
+                for (i, row) in digest.table.iter().enumerate() {
+                    let mut buffer = vec![];
+                    self.add_row(&mut buffer, row, i, i == digest.table.len() - 1, p)?;
+                    if window(buffer) {
+                        full = true;
+                        break;
+                    }
+                }
+                full
+            }
+        };
+        if !full {
+            window(self.notes(&digest));
+        }
+        Ok(lines)
+    }
+    /// Tabulate the differences between two versions of a table with the same columns. Rows are
+    /// matched by the text of a key column. Both versions share one layout and each row is marked
+    /// in a column on the left: `+` for an added row, `-` for a removed one, `~` for a changed one,
+    /// and a space for one that is unchanged. In a changed row the cells that differ are
+    /// prefixed with `*` and display the new text. Removed rows appear where they were in the old
+    /// version.
+    ///
+    /// If the table is shown in [expanded display](#method.expanded_display), rows are not marked.
+    ///
+    /// # Arguments
+    ///
+    /// * `old` - The old version of the data.
+    /// * `new` - The new version of the data.
+    /// * `key` - The index of the column identifying rows.
+    ///
+    /// # Errors
+    ///
+    /// Any errors of [`tabulate`](#method.tabulate).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// let old = [("apple", 3), ("banana", 5), ("cherry", 7)];
+    /// let new = [("apple", 3), ("cherry", 8), ("date", 1)];
+    /// assert_eq!(
+    ///     vec![
+    ///         "  apple   3",
+    ///         "- banana  5",
+    ///         "~ cherry *8",
+    ///         "+ date    1",
+    ///     ],
+    ///     colonnade.tabulate_diff(old, new, 0)?
+    /// );
+    /// # Ok(()) }
+    /// ```
+    pub fn tabulate_diff<T, R, U, S>(
+        &mut self,
+        old: T,
+        new: U,
+        key: usize,
+    ) -> Result<Vec<String>, ColonnadeError>
+    where
+        T: IntoIterator<Item = R>,
+        R: ToRow,
+        U: IntoIterator<Item = S>,
+        S: ToRow,
+    {
+        let old: Vec<R> = old.into_iter().collect();
+        let new: Vec<S> = new.into_iter().collect();
+        let old = self.own_table(&old)?;
+        let new = self.own_table(&new)?;
+        let key_of = |row: &[Cell]| row.get(key).map(|c| c.text.to_string()).unwrap_or_default();
+        // the indices of the old rows by key, in order
+        let mut unmatched: HashMap<String, VecDeque<usize>> = HashMap::new();
+        for (i, row) in old.iter().enumerate() {
+            unmatched.entry(key_of(row)).or_default().push_back(i);
+        }
+        let mut matched = vec![false; old.len()];
+        let mut pairs = vec![];
+        for row in new {
+            let j = unmatched.get_mut(&key_of(&row)).and_then(|q| q.pop_front());
+            if let Some(j) = j {
+                matched[j] = true;
+            }
+            pairs.push((j, row));
+        }
+        let mut marks = vec![];
+        let mut table = vec![];
+        let mut next_old = 0;
+        let mut removed_before = |end: usize, marks: &mut Vec<char>, table: &mut Vec<_>| {
+            while next_old < end {
+                if !matched[next_old] {
+                    marks.push('-');
+                    table.push(old[next_old].clone());
+                }
+                next_old += 1;
+            }
+        };
+        for (j, mut row) in pairs {
+            match j {
+                None => marks.push('+'),
+                Some(j) => {
+                    removed_before(j, &mut marks, &mut table);
+                    let mut changed = false;
+                    for (cell, before) in row.iter_mut().zip(old[j].iter()) {
+                        if cell.text != before.text {
+                            cell.text = format!("*{}", cell.text).into();
+                            changed = true;
+                        }
+                    }
+                    marks.push(if changed { '~' } else { ' ' });
+                }
+            }
+            table.push(row);
+        }
+        removed_before(old.len(), &mut marks, &mut table);
+        if self.header_row {
+            marks.insert(0, ' ');
+        }
+        // lay out both versions together with room for the markers
+        self.diffing = true;
+        self.reset();
+        let digest = self.digest(table);
+        self.diffing = false;
+        let digest = digest?;
+        let mut buffer = match &digest.records {
+            Some(records) => records.clone(),
+            None => {
+                let mut buffer = self.macerate_columns(&digest.table)?;
+                for (row, mark) in buffer.iter_mut().zip(marks) {
+                    for line in row.iter_mut() {
+                        if !(line.len() == 1 && line[0].1.is_empty()) {
+                            line[0].0 = format!("{} {}", mark, line[0].0);
+                        }
+                    }
+                }
+                buffer
+            }
+        };
+        // the next layout must reclaim the markers' space
+        self.layout.adjusted = false;
+        buffer.append(&mut self.notes(&digest));
+        Ok(self.lines(buffer))
+    }
+    // truncate the data to the maximum number of rows, lay it out, and determine whether it
+    // should be displayed as records
+    fn digest<'a>(
+        &mut self,
+        mut owned_table: Vec<Vec<Cell<'a>>>,
+    ) -> Result<Digest<'a>, ColonnadeError> {
+        self.layout.clamped = owned_table
+            .iter()
+            .enumerate()
+            .flat_map(|(r, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(_, cell)| cell.clamped)
+                    .map(move |(c, _)| (r, c))
+            })
+            .collect();
+        let omitted = match self.max_rows {
+            Some(n) if owned_table.len() > n => {
+                let omitted = owned_table.len() - n;
+                owned_table.truncate(n);
+                omitted
+            }
+            _ => 0,
+        };
+        let (table, records) = match self.expanded_display {
+            ExpandedDisplay::Never => (self.lay_out(self.with_header(owned_table))?, None),
+            ExpandedDisplay::Always => {
+                let records = self.macerate_records(&owned_table)?;
+                (owned_table, Some(records))
+            }
+            ExpandedDisplay::Auto => match self.lay_out(self.with_header(owned_table.clone())) {
+                Err(ColonnadeError::InsufficientSpace(_)) => {
+                    let records = self.macerate_records(&owned_table)?;
+                    (owned_table, Some(records))
+                }
+                Err(e) => return Err(e),
+                Ok(owned_table) => {
+                    if self.splits_words(&owned_table) {
+                        let records = self.macerate_records(&owned_table)?;
+                        (owned_table, Some(records))
+                    } else {
+                        (owned_table, None)
+                    }
+                }
+            },
+        };
+        let mut table = table;
+        if records.is_none() {
+            self.draw(&mut table);
+        }
+        Ok(Digest {
+            table,
+            records,
+            omitted,
+        })
+    }
+    // the row indices and values of the cells in a column which hold numbers
+    fn values(table: &[Vec<Cell>], column: usize) -> Vec<(usize, f64)> {
+        table
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| !row[column].total)
+            .filter_map(|(r, row)| row[column].value.map(|v| (r, v)))
+            .collect()
+    }
+    // fit bars and other graphics to the widths of their columns and color the cells
+    fn draw(&self, table: &mut [Vec<Cell>]) {
+        for c in self
+            .columns
+            .iter()
+            .filter(|c| c.is_displayed(self.state(c.index), self.width))
+        {
+            c.apply_heat(table);
+            c.draw_bars(self.state(c.index), table, &self.widths);
+            for row in table.iter_mut() {
+                let cell = &mut row[c.index];
+                if let Some(graphic) = &cell.graphic {
+                    cell.text = graphic.draw(c.inner_width(self.state(c.index))).into();
+                }
+            }
+        }
+    }
+    // prefix the table with the header row, if any
+    fn with_header<'a>(&self, mut table: Vec<Vec<Cell<'a>>>) -> Vec<Vec<Cell<'a>>> {
+        if self.header_row {
+            let header = self
+                .columns
+                .iter()
+                .map(|c| Cell::new(c.name.as_deref().unwrap_or("")))
+                .collect();
+            table.insert(0, header);
+        }
+        table
+    }
+    // the notes following the table proper: dropped columns and omitted rows
+    fn notes(&self, digest: &Digest) -> Maceration {
+        let mut buffer = vec![];
+        if digest.records.is_none() && self.degradation == Degradation::DropColumnsWithNote {
+            let dropped = self.dropped_columns().len();
+            if dropped > 0 {
+                let note = format!("+{} more", dropped);
+                // in strict mode even the note must fit the viewport
+                let lines = if self.strict {
+                    wrap(&note, self.width, &self.widths)
+                } else {
+                    vec![note]
+                };
+                buffer.push(
+                    lines
+                        .into_iter()
+                        .map(|line| vec![(String::new(), line)])
+                        .collect(),
+                );
+            }
+        }
+        if digest.omitted > 0 {
+            let noun = if digest.omitted == 1 { "row" } else { "rows" };
+            let summary = format!("… and {} more {}", group_thousands(digest.omitted), noun);
+            let width = match &digest.records {
+                Some(records) => self.maceration_width(records),
+                None => self.table_width(),
+            };
+            buffer.push(
+                wrap(&summary, width, &self.widths)
+                    .into_iter()
+                    .map(|line| vec![(String::new(), line)])
+                    .collect(),
+            );
+        }
+        buffer
+    }
+    // the number of lines in a row with no data
+    fn blank_row_height(&self) -> usize {
+        let p = self.maximum_vertical_padding();
+        if p == 0 {
+            1
+        } else {
+            p
+        }
+    }
+    // render laid out data as lines of columns
+    fn macerate_columns(&self, owned_table: &[Vec<Cell>]) -> Result<Maceration, ColonnadeError> {
+        let table = owned_table;
+        let mut buffer = vec![];
+        let p = self.blank_row_height();
+        for (i, row) in table.iter().enumerate() {
+            self.add_row(&mut buffer, row, i, i == table.len() - 1, p)?;
+        }
+        Ok(buffer)
+    }
+    // the width of the widest line in a maceration
+    fn maceration_width(&self, maceration: &[Vec<Vec<(String, String)>>]) -> usize {
+        maceration
+            .iter()
+            .flatten()
+            .map(|line| {
+                line.iter()
+                    .map(|(margin, text)| {
+                        true_width(margin, &self.widths) + true_width(text, &self.widths)
+                    })
+                    .sum::<usize>()
+            })
+            .max()
+            .unwrap_or(0)
+    }
+    // whether any displayed column is too narrow for some word in the data given the current layout
+    fn splits_words(&self, table: &[Vec<Cell>]) -> bool {
+        table.iter().any(|row| {
+            row.iter().zip(self.columns.iter()).any(|(cell, c)| {
+                c.is_displayed(self.state(c.index), self.width)
+                    && longest_word(&cell.measure(&self.widths), &self.widths)
+                        + c.horizontal_padding()
+                        > self.state(c.index).width
+            })
+        })
+    }
+    // render each row as a block of header: value lines
+    fn macerate_records(&mut self, table: &[Vec<Cell>]) -> Result<Maceration, ColonnadeError> {
+        self.reset();
+        self.validate(table)?;
+        let displayed = self.displayed();
+        let mut records = Colonnade::new(2, self.width)?;
+        records.widths = self.widths.clone();
+        records.columns[0].alignment(Alignment::Right).priority(0);
+        records.columns[1].priority(1);
+        let headers = displayed
+            .iter()
+            .map(|&i| match &self.columns[i].name {
+                Some(name) => format!("{}:", name),
+                None => format!("{}:", i),
+            })
+            .collect::<Vec<_>>();
+        let data = table
+            .iter()
+            .flat_map(|row| {
+                displayed
+                    .iter()
+                    .zip(headers.iter())
+                    .map(move |(&i, header)| vec![header.as_str(), &*row[i].text])
+            })
+            .collect::<Vec<_>>();
+        let lines = records.macerate(&data)?;
+        let separator = self.separator_line(records.required_width());
+        let mut buffer = vec![];
+        for (i, chunk) in lines.chunks(displayed.len().max(1)).enumerate() {
+            let mut record = chunk.concat();
+            if i + 1 < table.len() {
+                for _ in 0..self.spacing_after(i).max(1) {
+                    record.push(separator.clone());
+                }
+            }
+            buffer.push(record);
+        }
+        Ok(buffer)
+    }
+    // utility function to convert a T table to a String table
+    fn own_table<'a, R: ToRow>(&self, rows: &'a [R]) -> Result<Vec<Vec<Cell<'a>>>, ColonnadeError> {
+        let mut table = rows
+            .iter()
+            .map(|v| {
+                let mut row = v.to_row();
+                for cell in row.iter_mut() {
+                    strip_escapes(cell);
+                }
+                // an empty row may stand for a separator
+                if self.separator_rows.is_some() && row.iter().all(|c| to_words(&c.text).is_empty())
+                {
+                    let mut rule = vec![Cell::new(""); self.len()];
+                    rule[0].rule = true;
+                    return rule;
+                }
+                // compute the cells of virtual and decorative columns from the row as given
+                let computed = self
+                    .columns
+                    .iter()
+                    .filter_map(|c| match (&c.literal, &c.computation) {
+                        (Some(literal), _) => Some((c.index, Cell::new(literal))),
+                        (None, Some(f)) => Some((c.index, f.0(&row))),
+                        (None, None) => None,
+                    })
+                    .collect::<Vec<_>>();
+                for (i, cell) in computed {
+                    row.insert(i.min(row.len()), cell);
+                }
+                for (cell, column) in row.iter_mut().zip(self.columns.iter()) {
+                    if column.literal.is_none() {
+                        column.format(cell);
+                    }
+                    // computed and decorative cells may bring their own escapes
+                    if self.plain_text {
+                        strip_escapes(cell);
+                    }
+                    cell.rtl = self.bidi != Bidi::Ignore && is_rtl(&cell.text);
+                }
+                row
+            })
+            .collect::<Vec<Vec<Cell>>>();
+        // pad or truncate rows as the ragged row policy allows
+        for (i, row) in table.iter_mut().enumerate() {
+            let fixable = match self.ragged_rows {
+                RaggedRows::Error => false,
+                RaggedRows::PadShort => row.len() < self.len(),
+                RaggedRows::TruncateLong => row.len() > self.len(),
+                RaggedRows::PadOrTruncate => true,
+            };
+            if row.len() != self.len() && !fixable {
+                return Err(ColonnadeError::InconsistentColumns(
+                    i,
+                    row.len(),
+                    self.len(),
+                ));
+            }
+            row.resize(self.len(), Cell::new(""));
+            if row[0].rule {
+                continue;
+            }
+            for (cell, column) in row.iter_mut().zip(self.columns.iter()) {
+                if let Some(placeholder) = &column.empty_placeholder {
+                    if to_words(&cell.text).is_empty() {
+                        cell.text = placeholder.clone().into();
+                        cell.numeric = false;
+                    }
+                }
+            }
+        }
+        // rows are grouped before they are sorted by any other keys
+        let group = self.group_by.map(|c| SortKey::new(c, Order::Asc));
+        let keys = group.iter().chain(self.sort.iter()).collect::<Vec<_>>();
+        if !keys.is_empty() {
+            table.sort_by(|a, b| {
+                keys.iter()
+                    .map(|key| key.compare(a, b))
+                    .find(|&o| o != Ordering::Equal)
+                    .unwrap_or(Ordering::Equal)
+            });
+        }
+        if self.group_by.is_some() || self.columns.iter().any(|c| c.aggregate.is_some()) {
+            table = self.segment(table);
+        }
+        for c in 0..self.len() {
+            Colonnade::grow_tree(&mut table, c);
+        }
+        Ok(table)
+    }
+    // precede each group of rows sharing a value in the grouping column with a header displaying
+    // the value, removing the value from the rows, and follow each group with its subtotals and
+    // the table with its totals
+    fn segment<'a>(&self, table: Vec<Vec<Cell<'a>>>) -> Vec<Vec<Cell<'a>>> {
+        let aggregating = self.columns.iter().any(|c| c.aggregate.is_some());
+        let mut segmented = Vec::with_capacity(table.len());
+        let mut start = 0;
+        let mut groups = 0;
+        while start < table.len() {
+            let first = segmented.len();
+            let end = match self.group_by {
+                Some(c) => {
+                    let value = &table[start][c].text;
+                    let mut header = vec![Cell::new(""); self.len()];
+                    header[0] = Cell::new(value);
+                    header[0].spanning = true;
+                    segmented.push(header);
+                    start
+                        + table[start..]
+                            .iter()
+                            .take_while(|row| &row[c].text == value)
+                            .count()
+                }
+                None => table.len(),
+            };
+            for row in &table[start..end] {
+                let mut row = row.clone();
+                if let Some(c) = self.group_by {
+                    row[c] = Cell::new("");
+                }
+                segmented.push(row);
+            }
+            if aggregating && self.group_by.is_some() {
+                segmented.push(self.total_row(&table[start..end], "subtotal"));
+            }
+            if self.group_by.is_some() {
+                for cell in segmented[first..].iter_mut().flatten() {
+                    cell.group = Some(groups);
+                }
+                groups += 1;
+            }
+            start = end;
+        }
+        if aggregating {
+            segmented.push(self.total_row(&table, "total"));
+        }
+        segmented
+    }
+    // a row of the aggregates of the columns over some rows, labeled in the first column without
+    // an aggregate
+    fn total_row(&self, rows: &[Vec<Cell>], label: &str) -> Vec<Cell<'static>> {
+        let mut labeled = false;
+        self.columns
+            .iter()
+            .map(|c| {
+                let mut cell = match &c.aggregate {
+                    Some(aggregate) => {
+                        let mut cell = aggregate.apply(rows, c.index);
+                        c.format(&mut cell);
+                        cell
+                    }
+                    None if !labeled => {
+                        labeled = true;
+                        Cell::new(label)
+                    }
+                    None => Cell::new(""),
+                };
+                cell.total = true;
+                cell.style = self.total_style.clone();
+                cell
+            })
+            .collect()
+    }
+    // compare cells as numbers if both hold numbers, otherwise as text; numbers precede text
+    fn compare_cells(a: &Cell, b: &Cell) -> Ordering {
+        match (a.value, b.value) {
+            (Some(a), Some(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => a.text.cmp(&b.text),
+        }
+    }
+    // draw the guides connecting the tree nodes in a column
+    fn grow_tree(table: &mut [Vec<Cell>], column: usize) {
+        // continues[k] is whether a guide at depth k continues below the current row, which
+        // is so if the next node no deeper than k is at depth k
+        let mut continues: Vec<bool> = vec![];
+        for row in table.iter_mut().rev() {
+            let cell = &mut row[column];
+            let depth = match cell.depth {
+                Some(depth) => depth,
+                None => {
+                    continues.clear();
+                    continue;
+                }
+            };
+            if depth > 0 {
+                continues.resize(continues.len().max(depth + 1), false);
+                let mut ancestors = String::new();
+                for &c in &continues[1..depth] {
+                    ancestors += if c { "│ " } else { "  " };
+                }
+                let (first, rest) = if continues[depth] {
+                    ("├─", "│ ")
+                } else {
+                    ("└─", "  ")
+                };
+                cell.guides = Some((ancestors.clone() + first, ancestors + rest));
+            }
+            continues.truncate(depth);
+            continues.resize(depth + 1, false);
+            continues[depth] = true;
+        }
+    }
+    // the texts by which the cells of the table are measured during layout
+    fn measures<'a>(&self, table: &'a [Vec<Cell>]) -> Vec<Vec<Cow<'a, str>>> {
+        table
+            .iter()
+            .map(|v| v.iter().map(|c| c.measure(&self.widths)).collect())
+            .collect()
+    }
+    // join the pieces of a maceration into lines as configured
+    fn lines(&self, maceration: Maceration) -> Vec<String> {
+        let width = self.maceration_width(&maceration);
+        let mut lines = Colonnade::reconstitute_rows(maceration, self.separator.is_none());
+        if self.pad_lines {
+            for line in lines.iter_mut() {
+                *line += &" ".repeat(width.saturating_sub(true_width(line, &self.widths)));
+            }
+        } else if self.trim_trailing {
+            for line in lines.iter_mut() {
+                line.truncate(line.trim_end_matches(' ').len());
+            }
+        }
+        lines
+    }
+    // collapse indicates whether blank lines are reduced to empty strings
+    fn reconstitute_rows(maceration: Maceration, collapse: bool) -> Vec<String> {
+        maceration
+            .iter()
+            .flat_map(|row| {
+                row.iter().map(|line| {
+                    if collapse
+                        && line.len() == 1
+                        && line[0].1.is_empty()
+                        && line[0].0.trim_start_matches(' ').is_empty()
+                    {
+                        String::new() // return empty strings instead of fat lines for blank lines
+                    } else {
+                        let mut l = String::new();
+                        for (margin, text) in line {
+                            l += margin;
+                            l += text;
+                        }
+                        l
+                    }
+                })
+            })
+            .collect()
+    }
+    // take one row of untabulated pieces of text and turn it into one or more vectors of (String,String) tuples,
+    // where each tuple represenst a left margin and some column text, the each vector representing one line of tabulated text
+    // these vectors are gathered into a vector and added to the buffer
+    fn add_row(
+        &self,
+        buffer: &mut Maceration,
+        row: &[Cell],
+        index: usize,
+        last_row: bool,
+        maximum_vertical_padding: usize,
+    ) -> Result<(), ColonnadeError> {
+        let padding: Vec<(usize, usize)> = (0..self.len())
+            .map(|i| self.row_padding(i, index == 0, last_row))
+            .collect();
+        // tree nodes are wrapped in advance so each line keeps its guides, as is text already broken
+        // into lines and text which would otherwise end with an orphan
+        let branches: Vec<Option<Vec<String>>> = row
+            .iter()
+            .enumerate()
+            .map(|(i, w)| {
+                w.branch(self.columns[i].inner_width(self.state(i)), &self.widths)
+                    .or_else(|| {
+                        w.broken_lines(self.columns[i].inner_width(self.state(i)), &self.widths)
+                    })
+                    .or_else(|| self.columns[i].unorphaned(self.state(i), w, &self.widths))
+            })
+            .collect();
+        // turn the row, a list of blobs of text, into a list of lists of words, recording also the amount of blank space
+        // we need on either side of the words
+        let mut words: Vec<(usize, Vec<&str>, usize)> = row
+            .iter()
+            .enumerate()
+            .map(|(i, w)| {
+                if self.columns[i].is_displayed(self.state(i), self.width) {
+                    (
+                        padding[i].0,
+                        match &branches[i] {
+                            Some(lines) => lines.iter().map(String::as_str).collect(),
+                            None => to_words(&w.text),
+                        },
+                        padding[i].1,
+                    )
+                } else {
+                    (0, vec![], 0)
+                }
+            })
+            .collect();
+        let marker = self.marker();
+        let mut current_lines: Vec<Vec<(String, String)>> = Vec::new();
+        if let Some(cell) = row.first().filter(|c| c.spanning) {
+            // a group header spans the table, and may extend beyond it into the viewport
+            let width = self.table_width().saturating_sub(self.gutter_width());
+            let viewport = self.width.saturating_sub(self.gutter_width());
+            let stripe = self.stripe(cell).filter(|_| !self.plain_text);
+            for line in wrap(&cell.text, viewport, &self.widths) {
+                let padding = " ".repeat(width.saturating_sub(true_width(&line, &self.widths)));
+                let text = line + &padding;
+                let text = match stripe {
+                    Some(style) => style.paint(&text),
+                    None => text,
+                };
+                current_lines.push(vec![(String::new(), text)]);
+            }
+            if !last_row {
+                for _ in 0..self.spacing_after(index) {
+                    current_lines.push(self.separator_line(self.table_width()));
+                }
+            }
+        } else if row.first().is_some_and(|c| c.rule) {
+            // an empty row standing for a separator
+            let width = self.table_width();
+            current_lines.push(self.fill_line(self.separator_rows.as_ref(), width));
+            if !last_row {
+                for _ in 0..self.spacing_after(index) {
+                    current_lines.push(self.separator_line(width));
+                }
+            }
+        } else if words.iter().all(|(_, sentence, _)| sentence.is_empty()) {
+            // if all these lists are empty, just add a blank line (and maybe additional blank separator lines)
+            let height = if let Some(height) = self.row_height {
+                height
+            } else if self.suppress_outer_padding && (index == 0 || last_row) {
+                padding.iter().map(|(t, b)| t + b).max().unwrap_or(0).max(1)
+            } else {
+                maximum_vertical_padding
+            };
+            for _ in 0..height {
+                current_lines.push(self.blank_row_line(&marker));
+            }
+            self.release_slack(&mut current_lines);
+            if !last_row {
+                for _ in 0..self.spacing_after(index) {
+                    current_lines.push(self.separator_line(self.table_width()));
+                }
+            }
+        } else {
+            // otherwise, we build these lists into lines, we may use up some of these lists before others
+            while !words
+                .iter()
+                .all(|(pt, sentence, pb)| pb == &0 && pt == &0 && sentence.is_empty())
+            {
+                let mut pieces = vec![];
+                for i in self.displayed() {
+                    let c = &self.columns[i];
+                    let left_margin = self.margin(i);
+                    let mut line = String::new();
+                    let tuple = &mut words[i];
+                    if tuple.0 > 0 {
+                        line = c.blank_line(self.state(c.index));
+                        tuple.0 -= 1;
+                    } else if tuple.1.is_empty() {
+                        // we've used this one up, but there are still words to deal with in other sentences
+                        line = c.blank_line(self.state(c.index));
+                        if tuple.2 > 0 {
+                            tuple.2 -= 1;
+                        }
+                    } else {
+                        let mut l = c.padding_left;
+                        let mut phrase = " ".repeat(l);
+                        let mut first = true;
+                        while !tuple.1.is_empty() {
+                            let w = tuple.1.remove(0); // shift off the next word
+                            if first {
+                                let wl = true_width(w, &self.widths) + c.horizontal_padding();
+                                if wl == self.state(c.index).width {
+                                    // word fills column
+                                    phrase += w;
+                                    break;
+                                } else if wl > self.state(c.index).width {
+                                    // word overflows column and we must split it
+                                    let hyphenating = c.hyphenating(self.state(c.index));
+                                    let mut offset = c.inner_width(self.state(c.index));
+                                    if hyphenating {
+                                        offset -= 1;
+                                    }
+                                    let graphemes = UnicodeSegmentation::graphemes(w, true)
+                                        .collect::<Vec<&str>>();
+                                    let prefix = graphemes
+                                        [0..fitting(&graphemes, offset, &self.widths).max(1)]
+                                        .to_vec()
+                                        .join("");
+                                    let byte_offset = prefix.len();
+                                    phrase += &prefix;
+                                    if byte_offset < w.len() {
+                                        tuple.1.insert(0, &w[byte_offset..w.len()]);
+                                        // unshift back the remaining fragment
+                                    }
+                                    if hyphenating {
+                                        phrase += "-";
+                                    }
+                                    break;
+                                }
+                            }
+                            // try to tack on a new word
+                            let new_length =
+                                l + true_width(w, &self.widths) + if first { 0 } else { 1 };
+                            if new_length + c.padding_right > self.state(c.index).width {
+                                tuple.1.insert(0, w);
+                                break;
+                            } else {
+                                if first {
+                                    first = false;
+                                } else {
+                                    phrase += " ";
+                                }
+                                phrase += w;
+                                l = new_length;
+                                if branches[i].is_some() {
+                                    // each line wrapped in advance is a unit
+                                    break;
+                                }
+                            }
+                        }
+                        // pad phrase out properly in its cell
+                        let true_width = true_width(phrase.as_str(), &self.widths);
+                        if true_width < self.state(c.index).width {
+                            let surplus = self.state(c.index).width - true_width;
+                            if let Some(amount) = c.pinned_remainder(&row[i], &phrase) {
+                                // the first word stays at the left edge and the rest is right-aligned
+                                line += &phrase[..phrase.len() - amount.len()];
+                                for _ in
+                                    0..self.sub(surplus, c.padding_right, "pinned alignment")?
+                                {
+                                    line += " "
+                                }
+                                line += amount;
+                                for _ in 0..c.padding_right {
+                                    line += " "
+                                }
+                            } else {
+                                match c.alignment_for(&row[i]) {
+                                    Alignment::Left => {
+                                        line += &phrase;
+                                        for _ in 0..surplus {
+                                            line += " "
+                                        }
+                                    }
+                                    Alignment::Center => {
+                                        let left_bit = surplus / 2;
+                                        for _ in 0..left_bit {
+                                            line += " "
+                                        }
+                                        line += &phrase;
+                                        for _ in 0..(surplus - left_bit) {
+                                            line += " "
+                                        }
+                                    }
+                                    Alignment::Right => {
+                                        for _ in 0..self.sub(
+                                            surplus,
+                                            c.padding_right,
+                                            "right alignment",
+                                        )? {
+                                            line += " "
+                                        }
+                                        line += &phrase;
+                                        for _ in 0..c.padding_right {
+                                            line += " "
+                                        }
+                                    }
+                                    Alignment::Justify => {
+                                        let words = phrase.split(" ").collect::<Vec<_>>(); // could be more efficient, but this allows simpler code structure
+                                        let last_words = tuple.1.is_empty();
+                                        if last_words || words.len() == 1 {
+                                            // treat as left-justified
+                                            line += &phrase;
+                                            for _ in 0..surplus {
+                                                line += " "
+                                            }
+                                        } else {
+                                            let gaps = words.len() - 1;
+                                            let rearrangeable = self.sub(
+                                                surplus + gaps,
+                                                c.padding_right,
+                                                "justification",
+                                            )?;
+                                            let min_spacer = rearrangeable / gaps;
+                                            let extra = rearrangeable - min_spacer * gaps;
+                                            let extra_offset = words.len() - extra;
+                                            for (i, word) in words.iter().enumerate() {
+                                                if i == 0 {
+                                                    line += word;
+                                                } else {
+                                                    for _ in 0..(min_spacer) {
+                                                        line += " ";
+                                                    }
+                                                    if i >= extra_offset {
+                                                        line += " ";
+                                                    }
+                                                    line += word;
+                                                }
+                                            }
+                                            for _ in 0..c.padding_right {
+                                                line += " "
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        } else {
+                            line += &phrase;
+                        }
+                    }
+                    pieces.push((left_margin, line));
+                }
+                if let Some(marker) = &marker {
+                    pieces.push((" ".to_string(), marker.clone()));
+                }
+                current_lines.push(pieces);
+            }
+            // pad a short row so vertical alignment can distribute the blank lines
+            if let Some(height) = self.row_height {
+                while current_lines.len() < height {
+                    current_lines.push(self.blank_row_line(&marker));
+                }
+            }
+            // now fix vertical alignment
+            'outer: for (position, i) in self.displayed().into_iter().enumerate() {
+                let c = &self.columns[i];
+                let vertical_alignment = c.vertical_alignment_for(&row[i]);
+                match vertical_alignment {
+                    VerticalAlignment::Top => (),
+                    _ => {
+                        let blank = c.blank_line(self.state(c.index));
+                        let end =
+                            self.sub(current_lines.len(), padding[i].1, "vertical alignment")?;
+                        let top_pointer = padding[i].0;
+                        if end <= top_pointer {
+                            // this cell consists only of padding
+                            continue 'outer;
+                        }
+                        let mut movable_lines = 0;
+                        let mut pointer = end - 1;
+                        while current_lines[pointer][position].1 == blank {
+                            movable_lines += 1;
+                            if pointer == top_pointer {
+                                // this cell contains nothing but blank lines so alignment is irrelevant
+                                continue 'outer;
+                            }
+                            pointer -= 1;
+                        }
+                        if movable_lines == 0 {
+                            continue 'outer;
+                        }
+                        // pointer now points to the last movable line
+                        // top_pointer points to the insertion index where we can put blank lines
+                        // end points to an immovable index (perhaps beyond the end of the vector)
+                        let lines_to_move = if vertical_alignment == VerticalAlignment::Middle {
+                            movable_lines / 2
+                        } else {
                             movable_lines
                         };
                         // we extract the tuples for the relevant column from top_pointer to end, rotate
                         // them lines_to_move times, and reinstall them
                         let mut rotator = Vec::with_capacity(end - top_pointer);
-                        for i in top_pointer..end {
-                            rotator.push(current_lines[i].remove(c.index));
+                        for line in current_lines.iter_mut().take(end).skip(top_pointer) {
+                            rotator.push(line.remove(position));
+                        }
+                        for _ in 0..lines_to_move {
+                            let pair = rotator.remove(rotator.len() - 1);
+                            rotator.insert(0, pair);
+                        }
+                        for line in current_lines.iter_mut().take(end).skip(top_pointer) {
+                            line.insert(position, rotator.remove(0));
+                        }
+                    }
+                }
+            }
+            // cut a tall row down, keeping the lines of each cell its vertical alignment favors
+            if let Some(height) = self.row_height.filter(|&h| current_lines.len() > h) {
+                let excess = current_lines.len() - height;
+                let starts: Vec<usize> = self
+                    .displayed()
+                    .into_iter()
+                    .map(|i| match self.columns[i].vertical_alignment_for(&row[i]) {
+                        VerticalAlignment::Top => 0,
+                        VerticalAlignment::Middle => excess / 2,
+                        VerticalAlignment::Bottom => excess,
+                    })
+                    .collect();
+                current_lines = (0..height)
+                    .map(|j| {
+                        let mut line: Vec<(String, String)> = starts
+                            .iter()
+                            .enumerate()
+                            .map(|(position, &start)| current_lines[start + j][position].clone())
+                            .collect();
+                        // the omission marker, if any
+                        line.extend(current_lines[j][starts.len()..].iter().cloned());
+                        line
+                    })
+                    .collect();
+            }
+            self.release_slack(&mut current_lines);
+            // keep the direction of each cell's text to itself
+            if self.bidi == Bidi::Isolate {
+                for line in current_lines.iter_mut() {
+                    for (_, text) in line.iter_mut().take(self.displayed().len()) {
+                        *text = isolate(text);
+                    }
+                }
+            }
+            // link the cells of columns with hyperlinks
+            let header = index == 0 && self.header_row;
+            if !(self.plain_text || header) {
+                for (position, i) in self.displayed().into_iter().enumerate() {
+                    let target = match &self.columns[i].hyperlink {
+                        Some(Hyperlink(f)) => f(index, &row[i]),
+                        None => None,
+                    };
+                    if let Some(target) = target {
+                        for line in current_lines.iter_mut() {
+                            line[position].1 = link(&target, &line[position].1);
+                        }
+                    }
+                }
+            }
+            // color the styled cells
+            let row_style = match &self.row_style {
+                Some(RowStyle(f)) => f(index, row),
+                None => None,
+            };
+            let row_style = match (row.first().and_then(|c| self.stripe(c)), row_style) {
+                (Some(stripe), Some(style)) => Some(style.over(stripe)),
+                (stripe, style) => style.or_else(|| stripe.cloned()),
+            };
+            for (position, i) in self.displayed().into_iter().enumerate() {
+                let style = [
+                    &self.default_style,
+                    &self.columns[i].style,
+                    &row_style,
+                    &row[i].style,
+                ]
+                .iter()
+                .copied()
+                .flatten()
+                .fold(None, |under: Option<Style>, style| match under {
+                    Some(under) => Some(style.over(&under)),
+                    None => Some(style.clone()),
+                });
+                if let Some(style) = style.filter(|_| !self.plain_text) {
+                    for line in current_lines.iter_mut() {
+                        line[position].1 = style.paint(&line[position].1);
+                    }
+                }
+            }
+            // add row-separating lines
+            if !last_row {
+                for _ in 0..self.spacing_after(index) {
+                    current_lines.push(self.separator_line(self.table_width()));
+                }
+            }
+        }
+        // add the right margins as pieces of their own
+        let displayed = self.displayed();
+        for line in current_lines.iter_mut() {
+            if line.len() < displayed.len() {
+                // a separator or group header
+                continue;
+            }
+            for (position, &i) in displayed.iter().enumerate().rev() {
+                let width = self.right_margin_width(i);
+                if width > 0 {
+                    let margin = " ".repeat(width);
+                    line.insert(position + 1, (margin, String::new()));
+                }
+            }
+        }
+        if self.borders.visible() && !row.first().is_some_and(|c| c.spanning || c.rule) {
+            self.frame(&mut current_lines, index, last_row);
+        }
+        // mark the lines continuing the row in the gutter
+        if let Some(gutter) = self.wrap_gutter {
+            for (j, line) in current_lines.iter_mut().enumerate() {
+                if !(line.len() == 1 && line[0].1.is_empty()) {
+                    let mark = if j == 0 { ' ' } else { gutter };
+                    line[0].0 = format!("{} {}", mark, line[0].0);
+                }
+            }
+        }
+        buffer.push(current_lines);
+        Ok(())
+    }
+    // draw the borders around the lines of a row of cells: bars in the margins and rules before
+    // and after the row
+    fn frame(&self, lines: &mut Vec<Vec<(String, String)>>, index: usize, last_row: bool) {
+        let borders = &self.borders;
+        let separators = if last_row {
+            0
+        } else {
+            self.spacing_after(index)
+        };
+        let body = lines.len().saturating_sub(separators);
+        if let Some(bar) = borders.bars {
+            let columns = self.piece_columns();
+            for line in lines.iter_mut().take(body) {
+                for (piece, column) in line.iter_mut().zip(columns.iter()) {
+                    let chars = piece.0.chars().count();
+                    if let Some(offset) = Borders::bar_offset(chars, column.is_some()) {
+                        piece.0 = piece
+                            .0
+                            .chars()
+                            .enumerate()
+                            .map(|(i, c)| if i == offset { bar } else { c })
+                            .collect();
+                    }
+                }
+            }
+        }
+        let header = index == 0 && self.header_row;
+        let after = match (header, borders.header_rule) {
+            (true, Some(rule)) => Some(rule),
+            _ if last_row => borders.edge_rules,
+            _ => borders.row_rules,
+        };
+        if let Some(rule) = after {
+            lines.insert(body, self.border_rule(rule));
+        }
+        if let Some(rule) = borders.edge_rules.filter(|_| index == 0) {
+            lines.insert(0, self.border_rule(rule));
+        }
+    }
+    // a rule of the given character following the columns and crossing any bars
+    fn border_rule(&self, rule: char) -> Vec<(String, String)> {
+        let mut line: Vec<char> = vec![' '; self.gutter_width()];
+        let mut bars = vec![];
+        for i in self.displayed() {
+            let margin = self.margin_width(i);
+            if let Some(offset) = Borders::bar_offset(margin, true) {
+                bars.push(line.len() + offset);
+            }
+            line.extend(std::iter::repeat_n(' ', margin));
+            line.extend(std::iter::repeat_n(rule, self.state(i).width));
+            let margin = self.right_margin_width(i);
+            if let Some(offset) = Borders::bar_offset(margin, false) {
+                bars.push(line.len() + offset);
+            }
+            line.extend(std::iter::repeat_n(' ', margin));
+        }
+        if self.borders.bars.is_some() {
+            if let (Some(&first), Some(&last)) = (bars.first(), bars.last()) {
+                for c in line[first..last].iter_mut() {
+                    *c = rule;
+                }
+                let junction = self.borders.junction.or(self.borders.bars);
+                for &b in bars.iter() {
+                    line[b] = junction.unwrap_or(rule);
+                }
+            }
+        }
+        vec![(line.into_iter().collect(), String::new())]
+    }
+    /// Erase column widths established by a previous `tabulate` or `macerate`.
+    ///
+    /// Note that adjusting any configuration that may affect the horizontal layout of data
+    /// has an equivalent effect, forcing a fresh layout of the columns.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Alignment, Colonnade};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(3, 80)?;
+    /// colonnade.alignment(Alignment::Right);
+    /// for line in colonnade.tabulate(&[[100, 200, 300]])? {
+    ///     println!("{}", line);
+    /// }
+    /// // 100 200 300
+    /// for line in colonnade.tabulate(&[[1, 2, 3]])? {
+    ///     println!("{}", line);
+    /// }
+    /// //   1   2   3
+    /// colonnade.reset();
+    /// for line in colonnade.tabulate(&[[1, 2, 3]])? {
+    ///     println!("{}", line);
+    /// }
+    /// // 1 2 3
+    /// # Ok(()) }
+    /// ```
+    pub fn reset(&mut self) {
+        self.layout.adjusted = false;
+        self.layout.columns = self
+            .columns
+            .iter()
+            .map(|c| ColumnState {
+                viewport: self.width,
+                literal: match &c.literal {
+                    Some(literal) => true_width(literal, &self.widths),
+                    None => 0,
+                },
+                revision: c.revision,
+                ..ColumnState::default()
+            })
+            .collect();
+    }
+    // the computed state of a column
+    fn state(&self, i: usize) -> &ColumnState {
+        self.layout.columns.get(i).unwrap_or(&UNLAID)
+    }
+    fn adjusted(&self) -> bool {
+        self.layout.adjusted
+            && self.layout.columns.len() == self.len()
+            && self
+                .columns
+                .iter()
+                .zip(self.layout.columns.iter())
+                .all(|(c, s)| c.revision == s.revision)
+    }
+    // determine the optimal widths of the columns given the data and the specified constraints
+    fn lay_out<'a>(
+        &mut self,
+        mut owned_table: Vec<Vec<Cell<'a>>>,
+    ) -> Result<Vec<Vec<Cell<'a>>>, ColonnadeError> {
+        self.layout.overflows.clear();
+        self.layout.grew = false;
+        if self.adjusted() && self.overflow_policy == OverflowPolicy::Grow {
+            self.layout.grew = self.grow(&owned_table);
+            self.layout.overflows = self.overflowing(&owned_table);
+            return Ok(owned_table);
+        }
+        if self.adjusted() && !self.starved(&owned_table) {
+            if self.overflow_policy == OverflowPolicy::Wrap {
+                self.layout.overflows = self.overflowing(&owned_table);
+                return Ok(owned_table);
+            }
+            match self.overflow(&owned_table) {
+                None => return Ok(owned_table),
+                Some((r, c)) => match self.overflow_policy {
+                    OverflowPolicy::Error => return Err(ColonnadeError::Overflow(r, c)),
+                    OverflowPolicy::Truncate => {
+                        self.truncate_overflow(&mut owned_table);
+                        return Ok(owned_table);
+                    }
+                    _ => {
+                        trace!(
+                            row = r,
+                            column = c,
+                            "relaying out columns for overflowing data"
+                        );
+                    }
+                },
+            }
+        }
+        self.reset();
+        self.validate(&owned_table)?;
+        trace!(
+            viewport = self.width,
+            available = self.available_width(),
+            rows = owned_table.len(),
+            "laying out columns"
+        );
+        let measures = self.measures(&owned_table);
+        let mut ref_table = measures
+            .iter()
+            .map(|v| v.iter().map(|c| c.as_ref()).collect::<Vec<&str>>())
+            .collect::<Vec<Vec<&str>>>();
+        self.share_needs(&mut ref_table);
+        let table = &ref_table;
+        loop {
+            match self.fit(table) {
+                Err(ColonnadeError::InsufficientSpace(_))
+                    if self.degradation != Degradation::Error && self.drop_column() =>
+                {
+                    for state in self.layout.columns.iter_mut() {
+                        state.width = 0;
+                    }
+                }
+                result => {
+                    result?;
+                    break;
+                }
+            }
+        }
+        for (c, state) in self.columns.iter().zip(self.layout.columns.iter_mut()) {
+            state.width = c.effective_width(state);
+        }
+        match self.layout_strategy {
+            LayoutStrategy::Legacy => (),
+            LayoutStrategy::MinHeight => self.minimize_height(&table[..owned_table.len()])?,
+            LayoutStrategy::Proportional => self.proportion(&table[..owned_table.len()])?,
+        }
+        self.layout.target_height_met = match self.target_height {
+            Some(target) => {
+                let rows = &table[..owned_table.len()];
+                if self.table_height(rows) > target
+                    && self.layout_strategy != LayoutStrategy::MinHeight
+                {
+                    self.minimize_height(rows)?;
+                }
+                Some(self.table_height(rows) <= target)
+            }
+            None => None,
+        };
+        self.equalize_shared_widths();
+        self.apply_width_ratios();
+        trace!(widths = ?self.widths(), "final widths");
+        self.mark_adjusted();
+        Ok(owned_table)
+    }
+    // add rows to the table giving every column sharing a width the widest text and longest word
+    // of its group, so they expand and shrink alike
+    fn share_needs(&self, table: &mut Vec<Vec<&str>>) {
+        if self.shared_widths.is_empty() {
+            return;
+        }
+        let mut widest = vec![""; self.len()];
+        let mut longest = vec![""; self.len()];
+        for group in &self.shared_widths {
+            for row in table.iter() {
+                for &c in group {
+                    if self.width_after_normalization(row[c])
+                        > self.width_after_normalization(widest[group[0]])
+                    {
+                        widest[group[0]] = row[c];
+                    }
+                    if longest_word(row[c], &self.widths)
+                        > longest_word(longest[group[0]], &self.widths)
+                    {
+                        longest[group[0]] = row[c];
+                    }
+                }
+            }
+            for &c in group {
+                widest[c] = widest[group[0]];
+                longest[c] = longest[group[0]];
+            }
+        }
+        table.push(widest);
+        table.push(longest);
+    }
+    // give the displayed columns of each group sharing a width the widest of their widths, or if
+    // there isn't room for that, the narrowest
+    fn equalize_shared_widths(&mut self) {
+        for group in self.shared_widths.clone() {
+            let group: Vec<usize> = group
+                .into_iter()
+                .filter(|&c| self.columns[c].is_displayed(self.state(c), self.width))
+                .collect();
+            let widths = group.iter().map(|&c| self.state(c).width);
+            let (narrowest, widest) = match (widths.clone().min(), widths.max()) {
+                (Some(narrowest), Some(widest)) => (narrowest, widest),
+                _ => continue,
+            };
+            let extra = (widest - narrowest) * group.len();
+            let width = if self.required_width().saturating_add(extra) <= self.available_width() {
+                widest
+            } else {
+                group
+                    .iter()
+                    .map(|&c| self.columns[c].minimum_width(self.state(c)))
+                    .fold(narrowest, usize::max)
+            };
+            for &c in &group {
+                self.layout.columns[c].width = width;
+            }
+        }
+    }
+    // the index of the first column with the given name
+    fn named_column(&self, name: &str) -> Option<usize> {
+        self.columns
+            .iter()
+            .position(|c| c.name.as_deref() == Some(name))
+    }
+    // put the displayed columns of each ratio in proportion, widening whichever is too narrow if
+    // there is room, otherwise dividing their combined width between them
+    fn apply_width_ratios(&mut self) {
+        for (column, of, ratio) in self.width_ratios.clone() {
+            let (column, of) = match (self.named_column(&column), self.named_column(&of)) {
+                (Some(column), Some(of)) => (column, of),
+                _ => continue,
+            };
+            if !(self.columns[column].is_displayed(self.state(column), self.width)
+                && self.columns[of].is_displayed(self.state(of), self.width))
+            {
+                continue;
+            }
+            let (w, v) = (self.state(column).width, self.state(of).width);
+            let (wide_w, wide_v) = if (w as f64) < ratio * v as f64 {
+                ((ratio * v as f64).round() as usize, v)
+            } else {
+                (w, (w as f64 / ratio).round() as usize)
+            };
+            let extra = (wide_w - w) + (wide_v - v);
+            let fits = |c: &Column, width: usize| {
+                c.max(self.state(c.index)).unwrap_or(usize::MAX) >= width
+            };
+            let (w, v) = if self.required_width().saturating_add(extra) <= self.available_width()
+                && fits(&self.columns[column], wide_w)
+                && fits(&self.columns[of], wide_v)
+            {
+                (wide_w, wide_v)
+            } else {
+                let total = w + v;
+                let v = ((total as f64) / (1.0 + ratio)).round() as usize;
+                let v = v
+                    .max(self.columns[of].narrowest(self.state(of)))
+                    .max(
+                        total.saturating_sub(
+                            self.columns[column]
+                                .max(self.state(column))
+                                .unwrap_or(total),
+                        ),
+                    )
+                    .min(self.columns[of].max(self.state(of)).unwrap_or(total))
+                    .min(total.saturating_sub(self.columns[column].narrowest(self.state(column))));
+                (total - v, v)
+            };
+            self.layout.columns[column].width = w;
+            self.layout.columns[of].width = v;
+        }
+    }
+    // whether some displayed column has no room for text in its padding though there is text to display
+    fn starved(&self, table: &[Vec<Cell>]) -> bool {
+        self.columns.iter().enumerate().any(|(i, c)| {
+            c.is_displayed(self.state(c.index), self.width)
+                && self.state(c.index).width <= c.horizontal_padding()
+                && table.iter().any(
+                    |row| matches!(row.get(i), Some(cell) if !to_words(&cell.measure(&self.widths)).is_empty()),
+                )
+        })
+    }
+    // make sure every row has the expected number of columns and every column has room for text
+    fn validate(&self, table: &[Vec<Cell>]) -> Result<(), ColonnadeError> {
+        for i in self.displayed() {
+            let c = &self.columns[i];
+            let max = c.max(self.state(c.index)).unwrap_or(usize::MAX);
+            if (self.strict && max <= c.horizontal_padding())
+                || max < c.min_in(self.width).unwrap_or(0)
+            {
+                return Err(ColonnadeError::MinGreaterThanMax(i, c.name.clone()));
+            }
+        }
+        if self.strict && self.required_width() == usize::MAX {
+            return Err(ColonnadeError::Arithmetic("required width"));
+        }
+        for (column, of, ratio) in &self.width_ratios {
+            for name in [column, of] {
+                if self.named_column(name).is_none() {
+                    return Err(ColonnadeError::UnknownColumn(name.clone()));
+                }
+            }
+            if column == of || !(*ratio > 0.0 && ratio.is_finite()) {
+                return Err(ColonnadeError::InvalidRatio(
+                    column.clone(),
+                    of.clone(),
+                    *ratio,
+                ));
+            }
+        }
+        for (i, row) in table.iter().enumerate() {
+            if row.len() != self.len() {
+                return Err(ColonnadeError::InconsistentColumns(
+                    i,
+                    row.len(),
+                    self.len(),
+                ));
+            }
+        }
+        Ok(())
+    }
+    // the row and column of the first cell which won't fit on one line given the current column widths
+    fn overflow(&self, table: &[Vec<Cell>]) -> Option<(usize, usize)> {
+        self.overflowing_cells(table).next()
+    }
+    // the rows and columns of all the cells which won't fit on one line given the current column widths
+    fn overflowing(&self, table: &[Vec<Cell>]) -> Vec<(usize, usize)> {
+        self.overflowing_cells(table).collect()
+    }
+    fn overflowing_cells<'a>(
+        &'a self,
+        table: &'a [Vec<Cell>],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        table.iter().enumerate().flat_map(move |(r, row)| {
+            row.iter()
+                .zip(self.columns.iter())
+                .enumerate()
+                .filter(move |(_, (cell, column))| {
+                    column.is_displayed(self.state(column.index), self.width)
+                        && self
+                            .width_after_normalization(&cell.measure(&self.widths))
+                            .saturating_add(column.horizontal_padding())
+                            > self.state(column.index).width
+                })
+                .map(move |(c, _)| (r, c))
+        })
+    }
+    // widen the columns of an existing layout to fit their widest cells as far as the viewport
+    // allows, never narrowing any; returns whether any column grew
+    fn grow(&mut self, table: &[Vec<Cell>]) -> bool {
+        let mut slack = self.available_width().saturating_sub(self.required_width());
+        let mut grew = false;
+        for i in self.displayed() {
+            let column = &self.columns[i];
+            let needed = table
+                .iter()
+                .filter_map(|row| row.get(i))
+                .filter(|cell| !cell.spanning)
+                .map(|cell| {
+                    self.width_after_normalization(&cell.measure(&self.widths))
+                        .saturating_add(column.horizontal_padding())
+                })
+                .max()
+                .unwrap_or(0)
+                .min(column.max(self.state(column.index)).unwrap_or(usize::MAX));
+            let growth = needed
+                .saturating_sub(self.state(column.index).width)
+                .min(slack);
+            if growth > 0 {
+                trace!(column = i, growth, "widened column for overflowing data");
+                self.layout.columns[i].width += growth;
+                slack -= growth;
+                grew = true;
+            }
+        }
+        grew
+    }
+    // cut overflowing cells down to the width of their columns
+    fn truncate_overflow(&self, table: &mut [Vec<Cell>]) {
+        for row in table.iter_mut() {
+            for (cell, column) in row.iter_mut().zip(self.columns.iter()) {
+                let available = self
+                    .state(column.index)
+                    .width
+                    .saturating_sub(column.horizontal_padding());
+                if !cell.spanning && self.width_after_normalization(&cell.text) > available {
+                    cell.text =
+                        truncate(&to_words(&cell.text).join(" "), available, &self.widths).into();
+                }
+            }
+        }
+    }
+    // drop the lowest priority column still displayed, the rightmost if there is a tie
+    // returns whether any column could be dropped; at least one column is always retained
+    fn drop_column(&mut self) -> bool {
+        let displayed = self.displayed();
+        if displayed.len() < 2 {
+            return false;
+        }
+        // max_by_key returns the last maximum
+        let victim = displayed
+            .into_iter()
+            .max_by_key(|&i| self.columns[i].priority)
+            .unwrap();
+        self.layout.columns[victim].dropped = true;
+        trace!(column = victim, "dropped column for lack of space");
+        true
+    }
+    // fit the displayed columns into the viewport
+    fn fit(&mut self, table: &[Vec<&str>]) -> Result<(), ColonnadeError> {
+        if !self.sufficient_space() {
+            return Err(self.insufficient_space());
+        }
+        // first try to do it all without splitting
+        for row in table.iter() {
+            for (c, cell) in row.iter().enumerate() {
+                if !self.columns[c].is_displayed(self.state(c), self.width) {
+                    continue;
+                }
+                let m = self
+                    .width_after_normalization(cell)
+                    .saturating_add(self.columns[c].horizontal_padding());
+                if m >= self.state(c).width {
+                    // to force initial expansion to min width
+                    self.columns[c].expand(&mut self.layout.columns[c], m);
+                }
+            }
+        }
+        trace!(widths = ?self.widths(), "initial widths");
+        if self.required_width() <= self.available_width() {
+            if self.fill_viewport {
+                self.fill()?;
+            }
+            return Ok(());
+        }
+        let mut modified_columns: Vec<usize> = Vec::with_capacity(self.len());
+        // try shrinking columns to their longest word by order of priority
+        for p in self.priorities() {
+            for c in self.displayed() {
+                if self.columns[c].priority == p && self.columns[c].is_shrinkable(self.state(c)) {
+                    modified_columns.push(c);
+                    self.columns[c].shrink(&mut self.layout.columns[c], 0);
+                    for row in table.iter() {
+                        let m = longest_word(row[c], &self.widths)
+                            .saturating_add(self.columns[c].horizontal_padding());
+                        if m > self.state(c).width {
+                            self.columns[c].expand(&mut self.layout.columns[c], m);
                         }
-                        for _ in 0..lines_to_move {
-                            let pair = rotator.remove(rotator.len() - 1);
-                            rotator.insert(0, pair);
+                    }
+                }
+            }
+            trace!(
+                priority = p,
+                widths = ?self.widths(),
+                "shrank columns to their longest words"
+            );
+            if self.required_width() <= self.available_width() {
+                break;
+            }
+        }
+        if self.required_width() > self.available_width() {
+            // forcibly truncate long columns
+            let mut truncatable_columns = self.columns.iter().enumerate().collect::<Vec<_>>();
+            truncatable_columns.retain(|(_, c)| {
+                c.is_displayed(self.state(c.index), self.width)
+                    && c.is_shrinkable(self.state(c.index))
+            });
+            let truncatable_columns: Vec<usize> =
+                truncatable_columns.iter().map(|(i, _)| *i).collect();
+            let mut priorities: Vec<usize> = truncatable_columns
+                .iter()
+                .map(|&i| self.columns[i].priority)
+                .collect();
+            priorities.sort_unstable();
+            priorities.dedup();
+            priorities.reverse();
+            'outer: for p in priorities {
+                let mut shrinkables: Vec<&usize> = truncatable_columns
+                    .iter()
+                    .filter(|&&i| self.columns[i].priority == p)
+                    .collect();
+                loop {
+                    let excess = self.sub(
+                        self.required_width(),
+                        self.available_width(),
+                        "excess width",
+                    )?;
+                    if excess == 0 {
+                        break 'outer;
+                    }
+                    if excess <= shrinkables.len() {
+                        shrinkables.retain(|&&i| {
+                            let least = self.least_width(i);
+                            self.columns[i].shrink_by(&mut self.layout.columns[i], 1, least)
+                        });
+                    } else {
+                        let share = excess / shrinkables.len();
+                        shrinkables.retain(|&&i| {
+                            let least = self.least_width(i);
+                            self.columns[i].shrink_by(&mut self.layout.columns[i], share, least)
+                        });
+                    }
+                    if shrinkables.is_empty() {
+                        break;
+                    }
+                }
+            }
+            trace!(widths = ?self.widths(), "truncated columns");
+            if self.required_width() > self.available_width() {
+                return Err(self.insufficient_space());
+            }
+        } else if self.required_width() < self.available_width() {
+            // try to give back surplus space
+            trace!(
+                surplus = self.available_width() - self.required_width(),
+                "distributing surplus among shrunken columns"
+            );
+            modified_columns.retain(|&i| self.columns[i].is_expandable(self.state(i)));
+            if !modified_columns.is_empty() {
+                while self.required_width() < self.available_width() {
+                    // find highest priority among modified columns
+                    if let Some(priority) = modified_columns
+                        .iter()
+                        .map(|&i| self.columns[i].priority)
+                        .min()
+                    {
+                        // there are still some modified columns we haven't restored any space to
+                        let mut winners: Vec<&usize> = modified_columns
+                            .iter()
+                            .filter(|&&i| self.columns[i].priority == priority)
+                            .collect();
+                        let surplus = self.sub(
+                            self.available_width(),
+                            self.required_width(),
+                            "surplus width",
+                        )?;
+                        if surplus <= winners.len() {
+                            // give one column back to as many of the winners as possible and call it a day
+                            // we will necessarily break out of the loop after this
+                            for &&i in winners.iter().take(surplus) {
+                                self.layout.columns[i].width += 1;
+                            }
+                        } else {
+                            // give a share back to each winner
+                            loop {
+                                let surplus = self.sub(
+                                    self.available_width(),
+                                    self.required_width(),
+                                    "surplus width",
+                                )?;
+                                if surplus == 0 {
+                                    break;
+                                }
+                                winners.retain(|&&i| self.columns[i].is_expandable(self.state(i)));
+                                if winners.is_empty() {
+                                    break;
+                                }
+                                if surplus <= winners.len() {
+                                    for &&i in winners.iter().take(surplus) {
+                                        self.layout.columns[i].width += 1;
+                                    }
+                                    break;
+                                }
+                                let mut changed = false;
+                                let share = surplus / winners.len();
+                                for &&i in winners.iter() {
+                                    let change = self.columns[i]
+                                        .expand_by(&mut self.layout.columns[i], share);
+                                    changed = changed || change;
+                                }
+                                if !changed {
+                                    break;
+                                }
+                            }
+                            modified_columns.retain(|&i| self.columns[i].priority != priority);
                         }
-                        for i in top_pointer..end {
-                            current_lines[i].insert(c.index, rotator.remove(0));
+                    } else {
+                        break;
+                    }
+                }
+            }
+        }
+        if self.fill_viewport {
+            self.fill()?;
+        }
+        trace!(widths = ?self.widths(), "fitted widths");
+        Ok(())
+    }
+    // give each displayed column room for its longest word and share the remaining width in
+    // proportion to the room each still needs, if the columns can't all have all they need
+    fn proportion(&mut self, table: &[Vec<&str>]) -> Result<(), ColonnadeError> {
+        let displayed = self.displayed();
+        let widths: Vec<usize> = displayed.iter().map(|&c| self.state(c).width).collect();
+        let budget = self.width_budget(&widths);
+        let bounds = self.width_bounds(table, &displayed, &widths, budget);
+        let mut spare = budget.saturating_sub(bounds.iter().map(|&(n, _)| n).sum());
+        let needs: Vec<usize> = bounds.iter().map(|&(n, w)| w - n).collect();
+        let need: usize = needs.iter().sum();
+        if spare >= need {
+            return Ok(());
+        }
+        let mut proportioned: Vec<usize> = bounds
+            .iter()
+            .zip(&needs)
+            .map(|(&(narrowest, _), &n)| narrowest + spare * n / need)
+            .collect();
+        spare -= proportioned
+            .iter()
+            .zip(&bounds)
+            .map(|(&w, &(narrowest, _))| w - narrowest)
+            .sum::<usize>();
+        // what rounding leaves over goes to the columns by priority
+        let mut by_priority: Vec<usize> = (0..proportioned.len()).collect();
+        by_priority.sort_by_key(|&k| self.columns[displayed[k]].priority);
+        for k in by_priority {
+            if spare > 0 && proportioned[k] < bounds[k].1 {
+                proportioned[k] += 1;
+                spare -= 1;
+            }
+        }
+        for (&c, &width) in displayed.iter().zip(&proportioned) {
+            self.layout.columns[c].width = width;
+        }
+        trace!(widths = ?self.widths(), "proportioned widths");
+        if self.fill_viewport {
+            self.fill()?;
+        }
+        Ok(())
+    }
+    // the lines the rows of the table occupy at the current widths, with their vertical padding
+    // and the blank lines between them
+    fn table_height(&self, table: &[Vec<&str>]) -> usize {
+        let displayed = self.displayed();
+        let rows: usize = table
+            .iter()
+            .map(|row| {
+                displayed
+                    .iter()
+                    .map(|&c| {
+                        let column = &self.columns[c];
+                        wrap(
+                            row[c],
+                            column.inner_width(self.state(column.index)),
+                            &self.widths,
+                        )
+                        .len()
+                            + column.vertical_padding()
+                    })
+                    .max()
+                    .unwrap_or(0)
+            })
+            .sum();
+        rows + (1..table.len())
+            .map(|i| self.spacing_after(i - 1))
+            .sum::<usize>()
+    }
+    // the total width the displayed columns may have, given their current widths
+    fn width_budget(&self, widths: &[usize]) -> usize {
+        widths.iter().sum::<usize>() + self.available_width().saturating_sub(self.required_width())
+    }
+    // the narrowest each displayed column may be without splitting words, unless it is already
+    // narrower, and the widest it need be to display its text on one line
+    fn width_bounds(
+        &self,
+        table: &[Vec<&str>],
+        displayed: &[usize],
+        widths: &[usize],
+        budget: usize,
+    ) -> Vec<(usize, usize)> {
+        displayed
+            .iter()
+            .zip(widths)
+            .map(|(&c, &width)| {
+                let column = &self.columns[c];
+                let padding = column.horizontal_padding();
+                let word = table
+                    .iter()
+                    .map(|row| longest_word(row[c], &self.widths))
+                    .max();
+                let text = table
+                    .iter()
+                    .map(|row| self.width_after_normalization(row[c]))
+                    .max();
+                let narrowest = (word.unwrap_or(0) + padding)
+                    .max(column.narrowest(self.state(column.index)))
+                    .min(width);
+                let widest = (text.unwrap_or(0) + padding)
+                    .min(column.max(self.state(column.index)).unwrap_or(usize::MAX))
+                    .min(budget)
+                    .max(width);
+                (narrowest, widest)
+            })
+            .collect()
+    }
+    // redistribute the width of the displayed columns so the table has as few lines as a greedy
+    // search can find, keeping the layout found by fit if the search does no better
+    fn minimize_height(&mut self, table: &[Vec<&str>]) -> Result<(), ColonnadeError> {
+        let displayed = self.displayed();
+        let widths: Vec<usize> = displayed.iter().map(|&c| self.state(c).width).collect();
+        let budget = self.width_budget(&widths);
+        let bounds = self.width_bounds(table, &displayed, &widths, budget);
+        // the lines each cell occupies at each width its column may have, its words measured once
+        let lines: Vec<Vec<Vec<usize>>> = displayed
+            .iter()
+            .zip(&bounds)
+            .map(|(&c, &(narrowest, widest))| {
+                let padding = self.columns[c].horizontal_padding();
+                let words: Vec<Vec<Vec<usize>>> = table
+                    .iter()
+                    .map(|row| measure_words(row[c], &self.widths))
+                    .collect();
+                (narrowest..=widest)
+                    .map(|w| {
+                        words
+                            .iter()
+                            .map(|words| wrapped_height(words, w.saturating_sub(padding)))
+                            .collect()
+                    })
+                    .collect()
+            })
+            .collect();
+        let height = |widths: &[usize]| -> usize {
+            (0..table.len())
+                .map(|r| {
+                    (0..widths.len())
+                        .map(|k| lines[k][widths[k] - bounds[k].0][r])
+                        .max()
+                        .unwrap_or(0)
+                })
+                .sum()
+        };
+        let legacy = height(&widths);
+        if legacy <= table.len() {
+            return Ok(());
+        }
+        // start narrow and repeatedly widen whichever column saves the most lines per character
+        let column_lines: Vec<Vec<usize>> = lines
+            .iter()
+            .map(|ls| ls.iter().map(|l| l.iter().sum()).collect())
+            .collect();
+        let mut best: Vec<usize> = bounds.iter().map(|&(narrowest, _)| narrowest).collect();
+        let mut spare = budget.saturating_sub(best.iter().sum());
+        let mut lowest = height(&best);
+        loop {
+            let mut choice: Option<(usize, usize, usize)> = None;
+            for k in 0..best.len() {
+                let (narrowest, widest) = bounds[k];
+                let current = column_lines[k][best[k] - narrowest];
+                // the next width at which the column itself takes fewer lines
+                let next = (best[k] + 1..=widest.min(best[k] + spare))
+                    .find(|&w| column_lines[k][w - narrowest] < current);
+                if let Some(w) = next {
+                    let mut trial = best.clone();
+                    trial[k] = w;
+                    let h = height(&trial);
+                    if h >= lowest {
+                        continue;
+                    }
+                    // compare the lines saved per character of width
+                    let better = match choice {
+                        Some((j, v, g)) => {
+                            (lowest - h) * (v - best[j]) > (lowest - g) * (w - best[k])
                         }
+                        None => true,
+                    };
+                    if better {
+                        choice = Some((k, w, h));
                     }
                 }
             }
-            // add row-separating lines
-            if !last_row {
-                for _ in 0..self.spaces_between_rows {
-                    current_lines.push(vec![(self.blank_line(), String::new())]);
-                }
+            match choice {
+                Some((k, w, h)) => {
+                    spare -= w - best[k];
+                    best[k] = w;
+                    lowest = h;
+                }
+                None => break,
+            }
+        }
+        if lowest >= legacy {
+            return Ok(());
+        }
+        // space saving no lines goes to the columns by priority
+        let mut by_priority: Vec<usize> = (0..best.len()).collect();
+        by_priority.sort_by_key(|&k| self.columns[displayed[k]].priority);
+        for k in by_priority {
+            let increase = (bounds[k].1 - best[k]).min(spare);
+            best[k] += increase;
+            spare -= increase;
+        }
+        for (&c, &width) in displayed.iter().zip(&best) {
+            self.layout.columns[c].width = width;
+        }
+        trace!(widths = ?self.widths(), lines = lowest, "minimized height");
+        if self.fill_viewport {
+            self.fill()?;
+        }
+        Ok(())
+    }
+    // distribute any remaining space evenly among the columns that can still expand
+    fn fill(&mut self) -> Result<(), ColonnadeError> {
+        trace!(
+            surplus = self.available_width().saturating_sub(self.required_width()),
+            "filling viewport"
+        );
+        loop {
+            let surplus = self.sub(
+                self.available_width(),
+                self.required_width(),
+                "surplus width",
+            )?;
+            if surplus == 0 {
+                break;
+            }
+            let expandable: Vec<usize> = self
+                .displayed()
+                .into_iter()
+                .filter(|&i| self.columns[i].is_expandable(self.state(i)))
+                .collect();
+            if expandable.is_empty() {
+                break;
+            }
+            if surplus <= expandable.len() {
+                for &i in expandable.iter().take(surplus) {
+                    self.layout.columns[i].width += 1;
+                }
+                break;
+            }
+            let share = surplus / expandable.len();
+            for &i in expandable.iter() {
+                self.columns[i].expand_by(&mut self.layout.columns[i], share);
+            }
+        }
+        Ok(())
+    }
+    // a difference of widths which should never be negative; in strict mode a negative difference
+    // is an error, otherwise it is treated as 0
+    fn sub(&self, a: usize, b: usize, computation: &'static str) -> Result<usize, ColonnadeError> {
+        match a.checked_sub(b) {
+            Some(d) => Ok(d),
+            None if self.strict => Err(ColonnadeError::Arithmetic(computation)),
+            None => Ok(0),
+        }
+    }
+    // the widths of the displayed columns, for tracing
+    #[cfg(feature = "trace")]
+    fn widths(&self) -> Vec<usize> {
+        self.displayed()
+            .into_iter()
+            .map(|i| self.state(i).width)
+            .collect()
+    }
+    fn mark_adjusted(&mut self) {
+        self.layout.adjusted = true;
+    }
+    /// Specify a number of blank lines to insert between table rows.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - A number of spaces.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(4, 100)?;
+    /// // we want rows to be separated by a single blank line
+    /// colonnade.spaces_between_rows(1);
+    /// # Ok(()) }
+    /// ```
+    pub fn spaces_between_rows(&mut self, n: usize) -> &mut Self {
+        self.spaces_between_rows = n;
+        self.row_spacing = None;
+        self
+    }
+    /// Specify the number of blank lines between each pair of rows with a function of their
+    /// indices, so spacing can differ between sections of a table -- more space before a row of
+    /// totals, say. The indices are those of the rows as displayed, so a [header row](#method.header_row),
+    /// [group headers](#method.group_by), and [totals](#method.total_style) are counted. This
+    /// replaces the spacing set by [`spaces_between_rows`](#method.spaces_between_rows), and
+    /// setting that replaces this.
+    ///
+    /// # Arguments
+    ///
+    /// * `spacing` - A function from the indices of two consecutive rows to the number of blank
+    ///   lines between them.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// let data = [["apples", "3"], ["pears", "2"], ["total", "5"]];
+    /// let last = data.len() - 1;
+    /// colonnade.row_spacing(move |_, next| if next == last { 1 } else { 0 });
+    /// assert_eq!(
+    ///     vec!["apples 3", "pears  2", "", "total  5"],
+    ///     colonnade.tabulate(data)?
+    /// );
+    /// # Ok(()) }
+    /// ```
+    pub fn row_spacing<F>(&mut self, spacing: F) -> &mut Self
+    where
+        F: Fn(usize, usize) -> usize + Send + Sync + 'static,
+    {
+        self.row_spacing = Some(RowSpacing(Arc::new(spacing)));
+        self
+    }
+    // the number of blank lines following the row with the given index
+    fn spacing_after(&self, index: usize) -> usize {
+        match &self.row_spacing {
+            Some(RowSpacing(f)) => f(index, index + 1),
+            None => self.spaces_between_rows,
+        }
+    }
+    /// Change the width of the viewport. This forces a fresh layout of the columns.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - Viewport size in characters
+    ///
+    /// # Errors
+    ///
+    /// * `ColonnadeError::InsufficientSpace` - the viewport isn't wide enough for the columns and their margins
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(4, 100)?;
+    /// // the terminal has been resized
+    /// colonnade.viewport(60)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn viewport(&mut self, width: usize) -> Result<&mut Self, ColonnadeError> {
+        self.width = width;
+        self.layout.adjusted = false;
+        if !self.sufficient_space() {
+            Err(self.insufficient_space())
+        } else {
+            Ok(self)
+        }
+    }
+    /// Toggle whether all the space in the viewport is distributed among the columns. By default
+    /// columns take only as much space as their data requires. If `fill_viewport` is `true`, any
+    /// surplus is shared among the columns so that the table is exactly as wide as the viewport,
+    /// which is useful when painting full-width backgrounds or frames. Maximum column widths are
+    /// still respected, so if every column is at its maximum the table may be narrower than the viewport.
+    ///
+    /// # Arguments
+    ///
+    /// * `fill_viewport` - Whether the table should be stretched to the width of the viewport.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 20)?;
+    /// colonnade.fill_viewport(true);
+    /// for line in colonnade.tabulate(&[["a", "b"]])? {
+    ///     assert_eq!(20, line.len());
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn fill_viewport(&mut self, fill_viewport: bool) -> &mut Self {
+        self.fill_viewport = fill_viewport;
+        self.layout.adjusted = false;
+        self
+    }
+    /// Specify what to do when the viewport is too narrow to display all the columns. By default
+    /// layout fails with `ColonnadeError::InsufficientSpace`. Alternatively, the lowest priority
+    /// columns -- the rightmost first if priorities are tied -- can be dropped until the remaining
+    /// columns fit, so narrow terminals still show something useful. At least one column is always
+    /// displayed.
+    ///
+    /// # Arguments
+    ///
+    /// * `degradation` - The policy to apply.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Colonnade, Degradation};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(3, 10)?;
+    /// colonnade.degradation(Degradation::DropColumnsWithNote);
+    /// colonnade.min_width(4);
+    /// let lines = colonnade.tabulate(&[["foo", "bar", "baz"]])?;
+    /// assert_eq!(vec!["foo  bar ", "+1 more"], lines);
+    /// # Ok(()) }
+    /// ```
+    pub fn degradation(&mut self, degradation: Degradation) -> &mut Self {
+        self.degradation = degradation;
+        self.layout.adjusted = false;
+        self
+    }
+    /// Specify how column widths are chosen when the data does not fit the viewport at its natural
+    /// widths. By default columns are shrunk by priority, which can leave one column very tall
+    /// while others have room to spare; `LayoutStrategy::MinHeight` instead searches for widths
+    /// giving the table as few lines as it can find, never more than the default layout, and
+    /// `LayoutStrategy::Proportional` shares the space in proportion to the columns' needs. Data
+    /// which fits the viewport at its natural widths is laid out the same way by every strategy.
+    ///
+    /// See [`LayoutStrategy`](enum.LayoutStrategy.html).
+    ///
+    /// # Arguments
+    ///
+    /// * `layout_strategy` - The strategy to apply.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Colonnade, LayoutStrategy};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(3, 30)?;
+    /// let data = [
+    ///     ["id", "a fairly long description of the thing in question", "notes about it"],
+    ///     ["2", "short", "more notes which go on for a while"],
+    /// ];
+    /// assert_eq!(9, colonnade.tabulate(&data)?.len());
+    /// colonnade.layout_strategy(LayoutStrategy::MinHeight);
+    /// assert_eq!(7, colonnade.tabulate(&data)?.len());
+    /// # Ok(()) }
+    /// ```
+    pub fn layout_strategy(&mut self, layout_strategy: LayoutStrategy) -> &mut Self {
+        self.layout_strategy = layout_strategy;
+        self.layout.adjusted = false;
+        self
+    }
+    /// Try to keep the table within a number of lines. If the layout chosen by the
+    /// [layout strategy](#method.layout_strategy) would make the table taller than this, width is
+    /// moved from columns which wrap little to those which wrap a lot, within the columns' limits,
+    /// as by `LayoutStrategy::MinHeight`. The lines counted are those of the rows, including their
+    /// vertical padding, and the blank lines between them. Whether the target was met is reported by
+    /// [`target_height_met`](#method.target_height_met). By default there is no target.
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - The number of lines the table should not exceed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(3, 30)?;
+    /// colonnade.target_height(8);
+    /// let data = [
+    ///     ["id", "a fairly long description of the thing in question", "notes about it"],
+    ///     ["2", "short", "more notes which go on for a while"],
+    /// ];
+    /// assert_eq!(7, colonnade.tabulate(&data)?.len());
+    /// assert_eq!(Some(true), colonnade.target_height_met());
+    /// # Ok(()) }
+    /// ```
+    pub fn target_height(&mut self, lines: usize) -> &mut Self {
+        self.target_height = Some(lines);
+        self.layout.adjusted = false;
+        self
+    }
+    /// Specify what happens when the columns have already been laid out and the data being tabulated
+    /// does not fit them. By default overflowing text is wrapped onto additional lines.
+    ///
+    /// See [`OverflowPolicy`](enum.OverflowPolicy.html).
+    ///
+    /// # Arguments
+    ///
+    /// * `overflow_policy` - The policy to apply.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Colonnade, OverflowPolicy};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// colonnade.overflow_policy(OverflowPolicy::Truncate);
+    /// colonnade.tabulate(&[["name", "size"]])?;
+    /// let lines = colonnade.tabulate(&[["colonnade", "1024"]])?;
+    /// assert_eq!(vec!["col… 1024"], lines);
+    /// # Ok(()) }
+    /// ```
+    pub fn overflow_policy(&mut self, overflow_policy: OverflowPolicy) -> &mut Self {
+        self.overflow_policy = overflow_policy;
+        self
+    }
+    /// Toggle whether data that cannot fit the columns already laid out causes them to be laid out
+    /// afresh. This is shorthand for the [overflow policy](#method.overflow_policy)
+    /// `OverflowPolicy::Relayout`: turning it on sets that policy, and turning it off restores the
+    /// default policy, `OverflowPolicy::Wrap`, if the policy is `OverflowPolicy::Relayout`.
+    ///
+    /// # Arguments
+    ///
+    /// * `auto_relayout` - Whether to lay out the columns afresh for data that cannot fit them.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// colonnade.auto_relayout(true);
+    /// colonnade.tabulate(&[["name", "size"]])?;
+    /// let lines = colonnade.tabulate(&[["colonnade", "1024"]])?;
+    /// assert_eq!(vec!["colonnade 1024"], lines);
+    /// # Ok(()) }
+    /// ```
+    pub fn auto_relayout(&mut self, auto_relayout: bool) -> &mut Self {
+        if auto_relayout {
+            self.overflow_policy(OverflowPolicy::Relayout)
+        } else if self.overflow_policy == OverflowPolicy::Relayout {
+            self.overflow_policy(OverflowPolicy::Wrap)
+        } else {
+            self
+        }
+    }
+    /// Specify whether rows should be displayed as blocks of `header: value` lines rather than as
+    /// lines of columns. By default rows are always displayed as columns.
+    ///
+    /// See [`ExpandedDisplay`](enum.ExpandedDisplay.html).
+    ///
+    /// # Arguments
+    ///
+    /// * `expanded_display` - When to display rows as records.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Colonnade, ExpandedDisplay};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 20)?;
+    /// colonnade.expanded_display(ExpandedDisplay::Always);
+    /// colonnade.columns[0].name("name");
+    /// colonnade.columns[1].name("size");
+    /// let lines = colonnade.tabulate(&[["foo", "1"], ["bar", "2"]])?;
+    /// assert_eq!(vec!["name: foo", "size: 1  ", "", "name: bar", "size: 2  "], lines);
+    /// # Ok(()) }
+    /// ```
+    pub fn expanded_display(&mut self, expanded_display: ExpandedDisplay) -> &mut Self {
+        self.expanded_display = expanded_display;
+        self
+    }
+    /// Limit the number of rows displayed. If there are more rows than this, only the first `n` are
+    /// laid out and displayed, followed by a line such as `… and 4,213 more rows` wrapped to the width of
+    /// the table. By default all rows are displayed.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The maximum number of rows to display.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// colonnade.max_rows(1);
+    /// let lines = colonnade.tabulate(&[["apple", "banana"], ["c", "d"], ["e", "f"]])?;
+    /// assert_eq!(vec!["apple banana", "… and 2 more", "rows"], lines);
+    /// # Ok(()) }
+    /// ```
+    pub fn max_rows(&mut self, n: usize) -> &mut Self {
+        self.max_rows = Some(n);
+        self
+    }
+    /// Make every row of data exactly `n` lines tall. Shorter rows are padded with blank lines,
+    /// placed according to each column's [vertical alignment](struct.Column.html#method.vertical_alignment),
+    /// and taller rows are truncated, each cell keeping its top, middle, or bottom lines likewise.
+    /// Group headers and separators are not affected. A height of 0 is treated as 1. By default
+    /// rows are as tall as their contents.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of lines in each row.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Colonnade, VerticalAlignment};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 9)?;
+    /// colonnade.row_height(2);
+    /// colonnade.columns[0].vertical_alignment(VerticalAlignment::Bottom);
+    /// let lines = colonnade.tabulate(&[["a", "b"], ["c", "one two three"]])?;
+    /// assert_eq!(vec!["   b     ", "a        ", "   one   ", "c  two   "], lines);
+    /// # Ok(()) }
+    /// ```
+    pub fn row_height(&mut self, n: usize) -> &mut Self {
+        self.row_height = Some(n.max(1));
+        self
+    }
+    /// Toggle whether a marker such as `…+3` is appended to each line when some columns are not
+    /// displayed -- because they are hidden, collapsed, or dropped for lack of space -- so readers know
+    /// data was omitted. Space for the marker is reserved in the layout. By default there is no marker.
+    ///
+    /// # Arguments
+    ///
+    /// * `omission_marker` - Whether to mark lines when columns are omitted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(3, 80)?;
+    /// colonnade.omission_marker(true);
+    /// colonnade.columns[1].hidden(true);
+    /// assert_eq!(vec!["a c …+1"], colonnade.tabulate(&[["a", "b", "c"]])?);
+    /// # Ok(()) }
+    /// ```
+    pub fn omission_marker(&mut self, omission_marker: bool) -> &mut Self {
+        self.omission_marker = omission_marker;
+        self.layout.adjusted = false;
+        self
+    }
+    /// Set a character to display in a gutter at the left edge of the table, marking the lines
+    /// which continue a row wrapped onto several, so readers can tell a new row from a wrapped
+    /// one at a glance. The gutter and the space following it are reserved in the layout. By
+    /// default there is no gutter.
+    ///
+    /// # Arguments
+    ///
+    /// * `marker` - The character marking continuation lines, or `None` for no gutter.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 14)?;
+    /// colonnade.wrap_gutter(Some('↳'));
+    /// let data = [["one", "a short row"], ["two", "a somewhat longer row"]];
+    /// assert_eq!(
+    ///     vec![
+    ///         "  one a short ",
+    ///         "↳     row     ",
+    ///         "  two a       ",
+    ///         "↳     somewhat",
+    ///         "↳     longer  ",
+    ///         "↳     row     ",
+    ///     ],
+    ///     colonnade.tabulate(data)?
+    /// );
+    /// # Ok(()) }
+    /// ```
+    pub fn wrap_gutter(&mut self, marker: Option<char>) -> &mut Self {
+        self.wrap_gutter = marker;
+        self.layout.adjusted = false;
+        self
+    }
+    /// Specify what to do with rows that have more or fewer cells than there are columns. By default
+    /// short rows are padded with empty cells and long rows are an error.
+    ///
+    /// See [`RaggedRows`](enum.RaggedRows.html).
+    ///
+    /// # Arguments
+    ///
+    /// * `ragged_rows` - The policy for rows of the wrong length.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Colonnade, RaggedRows};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// colonnade.ragged_rows(RaggedRows::PadOrTruncate);
+    /// let lines = colonnade.tabulate(&[vec!["a"], vec!["b", "c", "d"]])?;
+    /// assert_eq!(vec!["a  ", "b c"], lines);
+    /// # Ok(()) }
+    /// ```
+    pub fn ragged_rows(&mut self, ragged_rows: RaggedRows) -> &mut Self {
+        self.ragged_rows = ragged_rows;
+        self
+    }
+    /// Specify how text written right to left is treated. A cell's text is written right to left
+    /// if its first letter is of a script written right to left, such as Arabic or Hebrew. By
+    /// default all text is treated alike.
+    ///
+    /// See [`Bidi`](enum.Bidi.html).
+    ///
+    /// # Arguments
+    ///
+    /// * `bidi` - The treatment of text written right to left.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Bidi, Colonnade};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// colonnade.bidi(Bidi::Align);
+    /// let data = [["en", "peace"], ["he", "שלום"]];
+    /// assert_eq!(vec!["en peace", "he  שלום"], colonnade.tabulate(data)?);
+    /// # Ok(()) }
+    /// ```
+    pub fn bidi(&mut self, bidi: Bidi) -> &mut Self {
+        self.bidi = bidi;
+        self
+    }
+    /// Measure text as a terminal with the given profile renders it. Widths given by
+    /// [`override_char_width`](#method.override_char_width) take precedence. By default every
+    /// grapheme cluster is one column wide.
+    ///
+    /// # Arguments
+    ///
+    /// * `profile` - How the terminal renders emoji and East Asian characters.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Colonnade, WidthProfile};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// colonnade.width_profile(WidthProfile::Kitty);
+    /// assert_eq!(
+    ///     vec!["🚀   launched", "done ok      "],
+    ///     colonnade.tabulate(&[["🚀", "launched"], ["done", "ok"]])?
+    /// );
+    /// # Ok(()) }
+    /// ```
+    #[cfg(feature = "unicode-width")]
+    pub fn width_profile(&mut self, profile: WidthProfile) -> &mut Self {
+        self.widths.profile = profile;
+        self.layout.adjusted = false;
+        self
+    }
+    /// Override the width of a range of characters, for terminals whose rendering disagrees with
+    /// the Unicode data, such as those displaying the glyphs of Nerd Fonts two columns wide. A
+    /// grapheme cluster beginning with a character in the range takes the width given, wherever
+    /// this table measures, wraps, splits, truncates, or pads text. Ranges overridden later take
+    /// precedence, and all take precedence over any [width profile](#method.width_profile).
+    ///
+    /// # Arguments
+    ///
+    /// * `chars` - The characters whose width is overridden.
+    /// * `width` - Their width in columns.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// // the private use area, where Nerd Fonts keep their icons
+    /// colonnade.override_char_width('\u{e000}'..='\u{f8ff}', 2);
+    /// assert_eq!(
+    ///     vec!["\u{e5ff} src   ", "\u{f48a} README"],
+    ///     colonnade.tabulate(&[["\u{e5ff}", "src"], ["\u{f48a}", "README"]])?
+    /// );
+    /// # Ok(()) }
+    /// ```
+    pub fn override_char_width(&mut self, chars: RangeInclusive<char>, width: usize) -> &mut Self {
+        self.widths.overrides.insert(0, (chars, width));
+        self.layout.adjusted = false;
+        self
+    }
+    /// Discard all overrides of the widths of characters made by
+    /// [`override_char_width`](#method.override_char_width).
+    pub fn clear_char_width_overrides(&mut self) -> &mut Self {
+        self.widths.overrides.clear();
+        self.layout.adjusted = false;
+        self
+    }
+    /// Toggle whether columns are laid out from right to left, as for an interface written
+    /// entirely right to left. The last column is displayed at the left edge and the first at the
+    /// right, and the margins trade sides, so each line is the mirror image of the line the table
+    /// would otherwise produce. The text within cells and the alignment of columns are unchanged;
+    /// see [`bidi`](#method.bidi). By default columns are laid out from left to right.
+    ///
+    /// # Arguments
+    ///
+    /// * `mirrored` - Whether to lay columns out from right to left.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(3, 80)?;
+    /// colonnade.columns[0].left_margin(2);
+    /// colonnade.mirrored(true);
+    /// assert_eq!(vec!["c b a  "], colonnade.tabulate([["a", "b", "c"]])?);
+    /// # Ok(()) }
+    /// ```
+    pub fn mirrored(&mut self, mirrored: bool) -> &mut Self {
+        self.mirrored = mirrored;
+        self.layout.adjusted = false;
+        self
+    }
+    /// Sort the rows by the cells in a column before they are displayed, leaving the data itself
+    /// untouched. Cells which hold numbers are compared as numbers and precede those which don't;
+    /// other cells are compared as text. The sort is stable. By default rows are displayed in the
+    /// order given.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the column to sort by.
+    /// * `order` - Whether to sort in ascending or descending order.
+    ///
+    /// # Errors
+    ///
+    /// * `ColonnadeError::OutOfBounds` - There is no column with the given index.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Colonnade, Order};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// colonnade.sort_by_column(1, Order::Desc)?;
+    /// let data = [["a", "9"], ["b", "10"], ["c", "2"]];
+    /// assert_eq!(vec!["b 10", "a 9 ", "c 2 "], colonnade.tabulate(data)?);
+    /// # Ok(()) }
+    /// ```
+    pub fn sort_by_column(
+        &mut self,
+        index: usize,
+        order: Order,
+    ) -> Result<&mut Self, ColonnadeError> {
+        self.sort_by(vec![SortKey::new(index, order)])
+    }
+    /// Sort the rows by several keys before they are displayed, leaving the data itself
+    /// untouched. Rows are compared by the first key, ties are broken by the second, and so on.
+    /// The sort is stable. An empty list of keys turns sorting off.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The keys to sort by, most significant first.
+    ///
+    /// # Errors
+    ///
+    /// * `ColonnadeError::OutOfBounds` - Some key's column does not exist.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Colonnade, Order, SortKey};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// colonnade.sort_by(vec![
+    ///     SortKey::new(0, Order::Asc)
+    ///         .comparator(|a, b| a.text().len().cmp(&b.text().len())),
+    ///     SortKey::new(1, Order::Desc),
+    /// ])?;
+    /// let data = [["ccc", "1"], ["a", "1"], ["bb", "2"], ["d", "3"]];
+    /// assert_eq!(
+    ///     vec!["d   3", "a   1", "bb  2", "ccc 1"],
+    ///     colonnade.tabulate(data)?
+    /// );
+    /// # Ok(()) }
+    /// ```
+    pub fn sort_by(&mut self, keys: Vec<SortKey>) -> Result<&mut Self, ColonnadeError> {
+        if let Some(key) = keys.iter().find(|key| key.column >= self.len()) {
+            return Err(ColonnadeError::OutOfBounds(key.column, None));
+        }
+        self.sort = keys;
+        Ok(self)
+    }
+    /// Group the rows by the values in a column. Rows are sorted by the column, before any other
+    /// [sort keys](#method.sort_by), and each group is preceded by a header line spanning the
+    /// table which displays the group's value. The value is not repeated in the rows of the
+    /// group. By default rows are not grouped.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The index of the column to group by, or `None` for no grouping.
+    ///
+    /// # Errors
+    ///
+    /// * `ColonnadeError::OutOfBounds` - There is no column with the given index.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(3, 80)?;
+    /// colonnade.group_by(Some(0))?;
+    /// let data = [
+    ///     ["fruit", "apple", "3"],
+    ///     ["vegetable", "kale", "1"],
+    ///     ["fruit", "pear", "2"],
+    /// ];
+    /// assert_eq!(
+    ///     vec!["fruit   ", " apple 3", " pear  2", "vegetable", " kale  1"],
+    ///     colonnade.tabulate(data)?
+    /// );
+    /// # Ok(()) }
+    /// ```
+    pub fn group_by(&mut self, column: Option<usize>) -> Result<&mut Self, ColonnadeError> {
+        if let Some(c) = column.filter(|&c| c >= self.len()) {
+            return Err(ColonnadeError::OutOfBounds(c, None));
+        }
+        self.group_by = column;
+        Ok(self)
+    }
+    /// Stripe the table by [group](#method.group_by) rather than by row: the rows of each group,
+    /// its header and any subtotals included, take the next of the given styles, cycling back to
+    /// the first when they run out. A stripe is [resolved](#method.default_style) with the styles
+    /// of the table's cells as part of the style of each row, beneath any
+    /// [row style](#method.row_style). This has no effect unless rows are grouped. By default
+    /// groups are not striped.
+    ///
+    /// # Arguments
+    ///
+    /// * `stripes` - The styles of successive groups, or an empty list for no striping.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Color, Colonnade, Style};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// colonnade.group_by(Some(0))?.group_stripes(vec![
+    ///     Style::new(),
+    ///     Style::new().background(Color::Ansi256(236)),
+    /// ]);
+    /// let data = [["fruit", "apple"], ["vegetable", "kale"]];
+    /// assert_eq!(
+    ///     vec![
+    ///         "fruit ",
+    ///         " apple",
+    ///         "\u{1b}[48;5;236mvegetable\u{1b}[0m",
+    ///         " \u{1b}[48;5;236mkale \u{1b}[0m",
+    ///     ],
+    ///     colonnade.tabulate(data)?
+    /// );
+    /// # Ok(()) }
+    /// ```
+    pub fn group_stripes(&mut self, stripes: Vec<Style>) -> &mut Self {
+        self.group_stripes = stripes;
+        self
+    }
+    /// Set the style of the rows of subtotals and totals produced by
+    /// [aggregates](struct.Column.html#method.aggregate), to set them off from the data. By
+    /// default they are not styled.
+    ///
+    /// # Arguments
+    ///
+    /// * `style` - The style of the cells of total rows, or `None` for no styling.
+    pub fn total_style(&mut self, style: Option<Style>) -> &mut Self {
+        self.total_style = style;
+        self
+    }
+    /// Set the style of the [header row](#method.header_row) in styled output, such as that of
+    /// [`tabulate_styled`](#method.tabulate_styled), where it takes the place of the
+    /// [`Styler`](trait.Styler.html). Plain output is unaffected. By default the header row is
+    /// bold and underlined.
+    ///
+    /// # Arguments
+    ///
+    /// * `style` - The style of the cells of the header row, or `None` to leave them to the
+    ///   `Styler`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{CellContext, Colonnade};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(1, 80)?;
+    /// colonnade.header_row(true).columns[0].name("name");
+    /// let plain = |_: CellContext, text: &str| text.to_string();
+    /// assert_eq!(
+    ///     vec!["\u{1b}[1m\u{1b}[4mname\u{1b}[0m", "Ada "],
+    ///     colonnade.tabulate_styled(&[["Ada"]], &plain)?
+    /// );
+    /// colonnade.header_style(None);
+    /// assert_eq!(vec!["name", "Ada "], colonnade.tabulate_styled(&[["Ada"]], &plain)?);
+    /// # Ok(()) }
+    /// ```
+    pub fn header_style(&mut self, style: Option<Style>) -> &mut Self {
+        self.header_style = style;
+        self
+    }
+    /// Set the style of the lines separating rows, whether [separators](#method.separator) or
+    /// [empty rows](#method.separator_rows) standing for them, in styled output, such as that of
+    /// [`tabulate_styled`](#method.tabulate_styled). Lines of only whitespace are left unstyled.
+    /// Plain output is unaffected. By default separators are not styled.
+    ///
+    /// # Arguments
+    ///
+    /// * `style` - The style of separating lines, or `None` for no styling.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Colonnade, Separator, Style};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(1, 80)?;
+    /// colonnade
+    ///     .spaces_between_rows(1)
+    ///     .separator(Separator::Fill("-".to_string()))
+    ///     .separator_style(Some(Style::new().dim(true)));
+    /// assert_eq!(
+    ///     vec!["a", "\u{1b}[2m-\u{1b}[0m", "b"],
+    ///     colonnade.tabulate_styled(&[["a"], ["b"]], &Style::new())?
+    /// );
+    /// # Ok(()) }
+    /// ```
+    pub fn separator_style(&mut self, style: Option<Style>) -> &mut Self {
+        self.separator_style = style;
+        self
+    }
+    /// Set the default style of the cells of the table. This is the bottom layer of the styles
+    /// resolved into the style of a cell. From the bottom up the layers are
+    ///
+    /// 1. the table's default style
+    /// 2. the style of the cell's [column](struct.Column.html#method.style)
+    /// 3. the style of the cell's [row](#method.row_style), laid over the [stripe](#method.group_stripes)
+    ///    of its group
+    /// 4. the style of the [cell](struct.Cell.html#method.style) itself, including those given
+    ///    by [heat maps](struct.Column.html#method.heat) and to [totals](#method.total_style)
+    ///
+    /// Each layer's colors replace those of the layers below it, and its attributes, such as
+    /// bold or underline, are added to theirs. By default the table has no style.
+    ///
+    /// # Arguments
+    ///
+    /// * `style` - The style of the table's cells, or `None` for no styling.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Color, Colonnade, Style};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// colonnade
+    ///     .default_style(Some(Style::new().foreground(Color::White)))
+    ///     .row_style(|_, row| {
+    ///         if row[1].text() == "down" {
+    ///             Some(Style::new().foreground(Color::Red))
+    ///         } else {
+    ///             None
+    ///         }
+    ///     });
+    /// colonnade.columns[0].style(Some(Style::new().bold(true)));
+    /// assert_eq!(
+    ///     vec![
+    ///         "\u{1b}[1m\u{1b}[37mweb\u{1b}[0m \u{1b}[37mup  \u{1b}[0m",
+    ///         "\u{1b}[1m\u{1b}[31mdb \u{1b}[0m \u{1b}[31mdown\u{1b}[0m",
+    ///     ],
+    ///     colonnade.tabulate(&[["web", "up"], ["db", "down"]])?
+    /// );
+    /// # Ok(()) }
+    /// ```
+    pub fn default_style(&mut self, style: Option<Style>) -> &mut Self {
+        self.default_style = style;
+        self
+    }
+    /// Style rows according to their indices and cells. This is the layer of the styles
+    /// [resolved](#method.default_style) into the style of a cell above the column's style. By
+    /// default rows have no style.
+    ///
+    /// # Arguments
+    ///
+    /// * `style` - A function of the index of a row as displayed, the [header row](#method.header_row)
+    ///   counted first, and its cells, which returns the style of the row, if any.
+    pub fn row_style<F>(&mut self, style: F) -> &mut Self
+    where
+        F: Fn(usize, &[Cell]) -> Option<Style> + Send + Sync + 'static,
+    {
+        self.row_style = Some(RowStyle(Arc::new(style)));
+        self
+    }
+    /// Add a column at the end of the table. If a [column order](#method.column_order) is set,
+    /// the new column is displayed last. The column has the default configuration.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let verbose = true;
+    /// let mut colonnade = Colonnade::new(1, 80)?;
+    /// if verbose {
+    ///     colonnade.push_column().name("details");
+    /// }
+    /// assert_eq!(vec!["a b"], colonnade.tabulate([["a", "b"]])?);
+    /// # Ok(()) }
+    /// ```
+    pub fn push_column(&mut self) -> &mut Column {
+        let index = self.len();
+        self.columns.push(Column::default(index));
+        if let Some(order) = &mut self.order {
+            order.push(index);
+        }
+        self.layout.adjusted = false;
+        &mut self.columns[index]
+    }
+    /// Remove a column, returning it. Later columns move down an index, and settings referring to
+    /// columns by index, such as the [sort keys](#method.sort_by), are adjusted to match. The
+    /// first column keeps the left margin of the table.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the column to remove.
+    ///
+    /// # Errors
+    ///
+    /// * `ColonnadeError::OutOfBounds` - There is no column with the given index.
+    /// * `ColonnadeError::InsufficientColumns` - The column is the only one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(3, 80)?;
+    /// colonnade.remove_column(0)?;
+    /// assert_eq!(vec!["b c"], colonnade.tabulate([["b", "c"]])?);
+    /// # Ok(()) }
+    /// ```
+    pub fn remove_column(&mut self, index: usize) -> Result<Column, ColonnadeError> {
+        if index >= self.len() {
+            return Err(ColonnadeError::OutOfBounds(index, None));
+        }
+        if self.len() == 1 {
+            return Err(ColonnadeError::InsufficientColumns);
+        }
+        let margin = self.columns[0].left_margin;
+        let removed = self.columns.remove(index);
+        self.columns[0].left_margin = margin;
+        for (i, c) in self.columns.iter_mut().enumerate() {
+            c.index = i;
+        }
+        self.layout.adjusted = false;
+        // the index a column formerly at i now has, if it remains
+        let shift = |i: usize| match i.cmp(&index) {
+            Ordering::Less => Some(i),
+            Ordering::Equal => None,
+            Ordering::Greater => Some(i - 1),
+        };
+        self.group_by = self.group_by.and_then(shift);
+        self.sort = self
+            .sort
+            .drain(..)
+            .filter_map(|mut key| {
+                key.column = shift(key.column)?;
+                Some(key)
+            })
+            .collect();
+        self.shared_widths = self
+            .shared_widths
+            .drain(..)
+            .map(|group| group.into_iter().filter_map(shift).collect::<Vec<_>>())
+            .filter(|group| group.len() > 1)
+            .collect();
+        if let Some(name) = &removed.name {
+            if self.named_column(name).is_none() {
+                self.width_ratios
+                    .retain(|(column, of, _)| column != name && of != name);
+            }
+        }
+        if let Some(order) = self.order.take() {
+            let order: Vec<usize> = order.into_iter().filter_map(shift).collect();
+            if order.is_empty() {
+                for c in self.columns.iter_mut() {
+                    c.deselected = false;
+                }
+            } else {
+                self.order = Some(order);
+            }
+        }
+        Ok(removed)
+    }
+    /// Configure each column in turn with a function of its index and the column itself, rather than
+    /// looping over the indices and borrowing `columns[i]` repeatedly. The function may use the
+    /// fallible column setters; the first error it returns stops the configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `configure` - A function configuring the column with the given index.
+    ///
+    /// # Errors
+    ///
+    /// * Any error returned by `configure`.
+    /// * `ColonnadeError::InsufficientSpace` - The configured columns require more space than is available in the viewport.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Alignment, Colonnade};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(3, 80)?;
+    /// colonnade.configure_columns(|i, column| {
+    ///     if i > 0 {
+    ///         column.alignment(Alignment::Right).min_width(3)?;
+    ///     }
+    ///     Ok(())
+    /// })?;
+    /// assert_eq!(vec!["a   b   c"], colonnade.tabulate([["a", "b", "c"]])?);
+    /// # Ok(()) }
+    /// ```
+    pub fn configure_columns<F>(&mut self, mut configure: F) -> Result<&mut Self, ColonnadeError>
+    where
+        F: FnMut(usize, &mut Column) -> Result<(), ColonnadeError>,
+    {
+        for (i, column) in self.columns.iter_mut().enumerate() {
+            configure(i, column)?;
+        }
+        if !self.sufficient_space() {
+            Err(self.insufficient_space())
+        } else {
+            Ok(self)
+        }
+    }
+    /// Make a group of columns share one width, the widest any of them needs, such as paired
+    /// "before" and "after" columns. When the columns must shrink they shrink alike. A column
+    /// belongs to at most one group: grouping it again moves it to the new group.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - The indices of the columns sharing a width.
+    ///
+    /// # Errors
+    ///
+    /// * `ColonnadeError::OutOfBounds` - Some index is not the index of a column.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(3, 80)?;
+    /// colonnade.share_width(&[1, 2])?;
+    /// let data = [["price", "9.99", "12.50"], ["stock", "120", "7"]];
+    /// assert_eq!(
+    ///     vec!["price 9.99  12.50", "stock 120   7    "],
+    ///     colonnade.tabulate(data)?
+    /// );
+    /// # Ok(()) }
+    /// ```
+    pub fn share_width(&mut self, columns: &[usize]) -> Result<&mut Self, ColonnadeError> {
+        if let Some(&i) = columns.iter().find(|&&i| i >= self.len()) {
+            return Err(ColonnadeError::OutOfBounds(i, None));
+        }
+        let mut group: Vec<usize> = Vec::with_capacity(columns.len());
+        for &i in columns {
+            if !group.contains(&i) {
+                group.push(i);
+            }
+        }
+        for g in self.shared_widths.iter_mut() {
+            g.retain(|i| !group.contains(i));
+        }
+        self.shared_widths.retain(|g| g.len() > 1);
+        if group.len() > 1 {
+            self.shared_widths.push(group);
+        }
+        self.layout.adjusted = false;
+        Ok(self)
+    }
+    /// Keep the width of one column in proportion to that of another, such as a pane of comments
+    /// twice as wide as the pane of code beside it. Once the columns are laid out, whichever of
+    /// the pair is too narrow for the proportion is widened if there is room; otherwise their
+    /// combined width is divided between them in proportion. Minimum and maximum widths still
+    /// apply, so where they conflict with the ratio the proportion is only approximate. A column
+    /// has at most one ratio: assigning another replaces it.
+    ///
+    /// Columns are identified by their [names](struct.Column.html#method.name), so a ratio follows
+    /// its columns wherever they are displayed, and is ignored while either is hidden. Where
+    /// several columns share a name, the first is meant. The ratio is checked when the data is
+    /// laid out.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The name of the column whose width is kept in proportion.
+    /// * `of` - The name of the column it is kept in proportion to.
+    /// * `ratio` - The width of the first column divided by the width of the second.
+    ///
+    /// # Errors
+    ///
+    /// Laying out the data fails with
+    ///
+    /// * `ColonnadeError::UnknownColumn` - No column has one of the names.
+    /// * `ColonnadeError::InvalidRatio` - The ratio is not a positive number, or the two names are the same.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 31)?;
+    /// colonnade.columns[0].name("code");
+    /// colonnade.columns[1].name("comment");
+    /// colonnade
+    ///     .fill_viewport(true)
+    ///     .width_ratio("comment", "code", 2.0);
+    /// let lines = colonnade.tabulate([["let x = 1;", "the answer, or near it"]])?;
+    /// assert_eq!(
+    ///     vec!["let x = 1; the answer, or near ", "           it                  "],
+    ///     lines
+    /// );
+    /// # Ok(()) }
+    /// ```
+    pub fn width_ratio(&mut self, column: &str, of: &str, ratio: f64) -> &mut Self {
+        self.width_ratios.retain(|(c, _, _)| c != column);
+        self.width_ratios
+            .push((column.to_string(), of.to_string(), ratio));
+        self.layout.adjusted = false;
+        self
+    }
+    /// Set the order in which columns are displayed, without changing the order of the cells in the
+    /// rows of data. Columns not in the order are not displayed. Each column keeps its
+    /// configuration wherever it is displayed, except that whatever column is displayed first
+    /// takes the first column's left margin. An empty order restores the columns' own order.
+    ///
+    /// # Arguments
+    ///
+    /// * `order` - The indices of the columns to display, in order. Repeated indices are ignored.
+    ///
+    /// # Errors
+    ///
+    /// * `ColonnadeError::OutOfBounds` - Some index is not the index of a column.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(3, 80)?;
+    /// colonnade.column_order(&[2, 0])?;
+    /// assert_eq!(vec!["c a"], colonnade.tabulate([["a", "b", "c"]])?);
+    /// # Ok(()) }
+    /// ```
+    pub fn column_order(&mut self, order: &[usize]) -> Result<&mut Self, ColonnadeError> {
+        if let Some(&i) = order.iter().find(|&&i| i >= self.len()) {
+            return Err(ColonnadeError::OutOfBounds(i, None));
+        }
+        let mut unique: Vec<usize> = Vec::with_capacity(order.len());
+        for &i in order {
+            if !unique.contains(&i) {
+                unique.push(i);
             }
         }
-        buffer.push(current_lines);
+        for c in self.columns.iter_mut() {
+            c.deselected = !unique.is_empty() && !unique.contains(&c.index);
+        }
+        self.layout.adjusted = false;
+        self.order = if unique.is_empty() {
+            None
+        } else {
+            Some(unique)
+        };
+        Ok(self)
+    }
+    /// Toggle whether trailing spaces are trimmed from the lines returned by
+    /// [`tabulate`](#method.tabulate) and its relatives. The pieces returned by
+    /// [`macerate`](#method.macerate) keep their widths. By default lines are not trimmed, so
+    /// each is padded out to the width of the table.
+    ///
+    /// # Arguments
+    ///
+    /// * `trim_trailing` - Whether to trim trailing spaces.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// colonnade.trim_trailing(true);
+    /// let data = [["a", "bbb"], ["ccc", "d"]];
+    /// assert_eq!(vec!["a   bbb", "ccc d"], colonnade.tabulate(data)?);
+    /// # Ok(()) }
+    /// ```
+    pub fn trim_trailing(&mut self, trim_trailing: bool) -> &mut Self {
+        self.trim_trailing = trim_trailing;
+        self
+    }
+    /// Toggle whether every line returned by [`tabulate`](#method.tabulate) and its relatives,
+    /// including the blank lines separating rows, is padded with spaces to the width of the
+    /// table, as is useful for painting backgrounds or drawing boxes. This takes precedence over
+    /// [`trim_trailing`](#method.trim_trailing). By default blank lines are empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `pad_lines` - Whether to pad lines to the width of the table.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// colonnade.pad_lines(true).spaces_between_rows(1);
+    /// let data = [["a", "bbb"], ["ccc", "d"]];
+    /// assert_eq!(vec!["a   bbb", "       ", "ccc d  "], colonnade.tabulate(data)?);
+    /// # Ok(()) }
+    /// ```
+    pub fn pad_lines(&mut self, pad_lines: bool) -> &mut Self {
+        self.pad_lines = pad_lines;
+        self
+    }
+    /// Toggle whether the spaces aligning text within its column belong to the margins of the
+    /// [maceration](#method.macerate) rather than to the text of the cells, so a background given
+    /// to the text covers only its characters and the column's padding. The slack before the
+    /// text joins the margin of its piece and the slack after it the margin of the following
+    /// piece; each line of a row ends with a piece holding any slack of the last column. A line
+    /// of a cell with no text is all slack. Justified text keeps the spaces between its words.
+    /// The lines of [`tabulate`](#method.tabulate) are unchanged except for styled cells. By
+    /// default the slack belongs to the text.
+    ///
+    /// # Arguments
+    ///
+    /// * `slack_in_margins` - Whether alignment spaces belong to the margins.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Alignment, Colonnade};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// colonnade.slack_in_margins(true);
+    /// colonnade.columns[1].alignment(Alignment::Right);
+    /// let pieces = colonnade.macerate(&[["a", "bbb"], ["ccc", "d"]])?;
+    /// let piece = |margin: &str, text: &str| (margin.to_string(), text.to_string());
+    /// assert_eq!(
+    ///     vec![piece("", "a"), piece("   ", "bbb"), piece("", "")],
+    ///     pieces[0][0]
+    /// );
+    /// assert_eq!(
+    ///     vec![piece("", "ccc"), piece("   ", "d"), piece("", "")],
+    ///     pieces[1][0]
+    /// );
+    /// # Ok(()) }
+    /// ```
+    pub fn slack_in_margins(&mut self, slack_in_margins: bool) -> &mut Self {
+        self.slack_in_margins = slack_in_margins;
+        self
+    }
+    /// Toggle plain text mode, which guarantees that no ANSI escape sequences appear in the
+    /// output, as is wanted for logs and files. Escape sequences are always stripped from the
+    /// cells of the data before they are measured; in plain text mode they are also stripped from
+    /// [computed](struct.Column.html#method.computed) and [decorative](struct.Column.html#method.literal)
+    /// cells, and cells are not styled, whether by [heat maps](struct.Column.html#method.heat),
+    /// [totals](#method.total_style), or a [`Styler`](trait.Styler.html). This forces a fresh
+    /// layout of the columns. By default plain text mode is off.
+    ///
+    /// # Arguments
+    ///
+    /// * `plain_text` - Whether to keep escape sequences out of the output.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Cell, Colonnade};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// colonnade.plain_text(true);
+    /// colonnade.columns[1].computed(|row| Cell::new(format!("\u{1b}[31m{}\u{1b}[0m", row[0].text())));
+    /// assert_eq!(vec!["stop stop"], colonnade.tabulate(&[["\u{1b}[1mstop\u{1b}[0m"]])?);
+    /// # Ok(()) }
+    /// ```
+    pub fn plain_text(&mut self, plain_text: bool) -> &mut Self {
+        self.plain_text = plain_text;
+        self.layout.adjusted = false;
+        self
+    }
+    /// Toggle whether the vertical padding on the outer edges of the table is suppressed: the top
+    /// padding of the first row and the bottom padding of the last row. Padding between rows is
+    /// unaffected. By default the padding is kept, so a table with vertical padding begins and
+    /// ends with blank lines.
+    ///
+    /// # Arguments
+    ///
+    /// * `suppress` - Whether to drop the padding above the first row and below the last.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// colonnade.padding_vertical(1).suppress_outer_padding(true);
+    /// let data = [["a", "b"], ["c", "d"]];
+    /// assert_eq!(vec!["a b", "   ", "   ", "c d"], colonnade.tabulate(data)?);
+    /// # Ok(()) }
+    /// ```
+    pub fn suppress_outer_padding(&mut self, suppress: bool) -> &mut Self {
+        self.suppress_outer_padding = suppress;
+        self
+    }
+    /// Set how the blank lines separating rows, as set by
+    /// [`spaces_between_rows`](#method.spaces_between_rows), are represented, consistently in the
+    /// output of both [`tabulate`](#method.tabulate) and [`macerate`](#method.macerate). In a
+    /// maceration a separator line is a single piece whose margin holds the representation and
+    /// whose text is empty. By default `tabulate` gives empty strings and `macerate` gives spaces.
+    ///
+    /// # Arguments
+    ///
+    /// * `separator` - The representation of separator lines.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Colonnade, Separator};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// colonnade
+    ///     .spaces_between_rows(1)
+    ///     .separator(Separator::Fill("-".to_string()));
+    /// let data = [["a", "bbb"], ["ccc", "d"]];
+    /// assert_eq!(vec!["a   bbb", "-------", "ccc d  "], colonnade.tabulate(data)?);
+    /// # Ok(()) }
+    /// ```
+    pub fn separator(&mut self, separator: Separator) -> &mut Self {
+        self.separator = Some(separator);
+        self
+    }
+    /// Set whether empty data rows stand for separators, and if so, how they are represented. A
+    /// row all of whose cells are blank, or which has no cells, is then rendered as a single line
+    /// spanning the table, so the data itself can mark the boundaries between groups of rows.
+    /// Such a line is represented in a maceration like a
+    /// [row-separating line](#method.separator): a single piece whose margin holds the
+    /// representation and whose text is empty. Separator rows are not sorted or grouped
+    /// meaningfully, so they are best used with tables in their given order. By default empty
+    /// rows are ordinary rows.
+    ///
+    /// # Arguments
+    ///
+    /// * `separator` - The representation of empty rows, or `None` to treat them as ordinary rows.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Colonnade, Separator};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// colonnade.separator_rows(Some(Separator::Fill("=".to_string())));
+    /// let data = [["a", "bbb"], ["", ""], ["ccc", "d"]];
+    /// assert_eq!(vec!["a   bbb", "=======", "ccc d  "], colonnade.tabulate(data)?);
+    /// # Ok(()) }
+    /// ```
+    pub fn separator_rows(&mut self, separator: Option<Separator>) -> &mut Self {
+        self.separator_rows = separator;
+        self
+    }
+    /// Frame the cells of the table with bars and rules. By default there are no borders.
+    ///
+    /// See [`Borders`](struct.Borders.html).
+    ///
+    /// # Arguments
+    ///
+    /// * `borders` - The bars and rules to draw.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Borders, Colonnade};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// colonnade.header_row(true).borders(Borders::new().header_rule('-'));
+    /// colonnade.columns[0].name("fruit");
+    /// colonnade.columns[1].name("count");
+    /// let lines = colonnade.tabulate(&[("apple", 3)])?;
+    /// assert_eq!(vec!["fruit count", "----- -----", "apple     3"], lines);
+    /// # Ok(()) }
+    /// ```
+    pub fn borders(&mut self, borders: Borders) -> &mut Self {
+        self.borders = borders;
+        self
+    }
+    /// Toggle strict mode. Layout and rendering never panic on width arithmetic: a width computation
+    /// which would overflow or underflow is saturated. In strict mode such a computation is instead
+    /// reported as `ColonnadeError::Arithmetic`, so a configuration which cannot be laid out exactly
+    /// is surfaced rather than approximated. Strict mode also keeps every line within the viewport:
+    /// each column is given room for at least one character inside its padding, a maximum width
+    /// which leaves no such room is reported as `ColonnadeError::MinGreaterThanMax`, and the note
+    /// listing dropped columns is wrapped. By default strict mode is off.
+    ///
+    /// # Arguments
+    ///
+    /// * `strict` - Whether to report impossible width computations as errors.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Colonnade, ColonnadeError};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, usize::MAX)?;
+    /// colonnade.strict(true);
+    /// colonnade.columns[1].left_margin(usize::MAX);
+    /// match colonnade.tabulate(&[["a", "b"]]) {
+    ///     Err(ColonnadeError::Arithmetic(_)) => (),
+    ///     _ => panic!("expected an arithmetic error"),
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn strict(&mut self, strict: bool) -> &mut Self {
+        self.strict = strict;
+        self
+    }
+    /// Toggle whether the [names](struct.Column.html#method.name) of the columns are displayed as the
+    /// first row of the table. Unnamed columns have blank headers. The header row is not counted
+    /// toward [`max_rows`](#method.max_rows), and it is omitted when rows are displayed as records,
+    /// which are labeled with the names anyway. By default there is no header row.
+    ///
+    /// # Arguments
+    ///
+    /// * `header_row` - Whether to display the column names as the first row.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// colonnade.header_row(true);
+    /// colonnade.columns[0].name("fruit");
+    /// colonnade.columns[1].name("count");
+    /// let lines = colonnade.tabulate(&[("apple", 3)])?;
+    /// assert_eq!(vec!["fruit count", "apple     3"], lines);
+    /// # Ok(()) }
+    /// ```
+    pub fn header_row(&mut self, header_row: bool) -> &mut Self {
+        self.header_row = header_row;
+        self.layout.adjusted = false;
+        self
+    }
+    /// Style the table after one of the named formats of python's `tabulate` library, to ease
+    /// migration: `"plain"`, `"simple"`, `"github"`, or `"grid"`. See
+    /// [`Preset::named`](struct.Preset.html#method.named).
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the format.
+    ///
+    /// # Errors
+    ///
+    /// * `ColonnadeError::UnsupportedFormat` - There is no such format.
+    /// * `ColonnadeError::InsufficientSpace` - The styled columns require more space than is available in the viewport.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// colonnade.format("simple")?;
+    /// colonnade.columns[0].name("fruit");
+    /// colonnade.columns[1].name("count");
+    /// let lines = colonnade.tabulate(&[("apple", 3)])?;
+    /// assert_eq!(vec!["fruit  count", "-----  -----", "apple      3"], lines);
+    /// colonnade.format("github")?;
+    /// let lines = colonnade.tabulate(&[("apple", 3)])?;
+    /// assert_eq!(
+    ///     vec!["| fruit | count |", "|-------|-------|", "| apple |     3 |"],
+    ///     lines
+    /// );
+    /// # Ok(()) }
+    /// ```
+    pub fn format(&mut self, name: &str) -> Result<&mut Self, ColonnadeError> {
+        let preset = Preset::named(name)
+            .ok_or_else(|| ColonnadeError::UnsupportedFormat(name.to_string()))?;
+        preset.apply(self)?;
+        Ok(self)
+    }
+    /// Returns what the most recent layout computed: the width of each column and the columns
+    /// dropped for lack of space. The layout is kept apart from the columns' specifications, so a
+    /// copied `Column` carries no widths, and it is reused for later data until some part of the
+    /// specification changes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// colonnade.tabulate([["a", "bbb"]])?;
+    /// assert_eq!(vec![1, 3], colonnade.layout().widths());
+    /// colonnade.columns[0].min_width(2)?;
+    /// colonnade.tabulate([["a", "bbb"]])?;
+    /// assert_eq!(vec![2, 3], colonnade.layout().widths());
+    /// # Ok(()) }
+    /// ```
+    pub fn layout(&self) -> &Layout {
+        &self.layout
     }
-    /// Erase column widths established by a previous `tabulate` or `macerate`.
+    /// Returns the row and column indices of any cells which overflowed their columns in the most
+    /// recent `tabulate` or `macerate` and were wrapped onto additional lines.
     ///
-    /// Note that adjusting any configuration that may affect the horizontal layout of data
-    /// has an equivalent effect, forcing a fresh layout of the columns.
+    /// This only happens when the columns have already been laid out and the overflow policy is
+    /// `OverflowPolicy::Wrap`, the default, or `OverflowPolicy::Grow`; a fresh layout makes room
+    /// for the data as best it can.
+    /// Row indices are relative to the data most recently tabulated.
+    ///
+    /// See [`overflow_policy`](#method.overflow_policy).
     ///
     /// # Example
     ///
     /// ```rust
     /// # extern crate colonnade;
-    /// # use colonnade::{Alignment, Colonnade};
+    /// # use colonnade::Colonnade;
     /// # use std::error::Error;
     /// # fn demo() -> Result<(), Box<dyn Error>> {
-    /// let mut colonnade = Colonnade::new(3, 80)?;
-    /// colonnade.alignment(Alignment::Right);
-    /// for line in colonnade.tabulate(&[[100, 200, 300]])? {
-    ///     println!("{}", line);
-    /// }
-    /// // 100 200 300
-    /// for line in colonnade.tabulate(&[[1, 2, 3]])? {
-    ///     println!("{}", line);
-    /// }
-    /// //   1   2   3
-    /// colonnade.reset();
-    /// for line in colonnade.tabulate(&[[1, 2, 3]])? {
-    ///     println!("{}", line);
-    /// }
-    /// // 1 2 3
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// colonnade.tabulate(&[["name", "size"]])?;
+    /// assert!(colonnade.overflows().is_empty());
+    /// colonnade.tabulate(&[["short", "1"], ["colonnade", "1024"]])?;
+    /// assert_eq!(vec![(0, 0), (1, 0)], colonnade.overflows());
     /// # Ok(()) }
     /// ```
-    pub fn reset(&mut self) {
+    pub fn overflows(&self) -> Vec<(usize, usize)> {
+        self.layout.overflows.clone()
+    }
+    /// Returns the row and column indices of any cells whose values were outside the range of
+    /// their column's [`Percentage`](struct.Percentage.html#method.clamp) in the most recent
+    /// `tabulate` or `macerate`, and so were displayed as the nearest limit. Row indices are
+    /// relative to the data most recently tabulated.
+    pub fn clamped(&self) -> Vec<(usize, usize)> {
+        self.layout.clamped.clone()
+    }
+    /// Returns the indices of any columns dropped from the most recent layout for lack of space.
+    ///
+    /// See [`degradation`](#method.degradation).
+    pub fn dropped_columns(&self) -> Vec<usize> {
+        (0..self.len()).filter(|&i| self.state(i).dropped).collect()
+    }
+    /// Returns whether the most recent layout kept the table within its
+    /// [target height](#method.target_height), or `None` if there is no target or nothing has
+    /// been laid out.
+    pub fn target_height_met(&self) -> Option<bool> {
+        self.layout.target_height_met
+    }
+    /// Returns whether the most recent `tabulate` or `macerate` widened any column of an existing
+    /// layout, which only happens when the overflow policy is `OverflowPolicy::Grow`. Lines
+    /// displayed before the growth, such as a header, no longer span the full width of the
+    /// columns, so a caller streaming batches of data may wish to display them again.
+    ///
+    /// See [`overflow_policy`](#method.overflow_policy).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Colonnade, OverflowPolicy};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 80)?;
+    /// colonnade.overflow_policy(OverflowPolicy::Grow);
+    /// assert_eq!(vec!["name size"], colonnade.tabulate(&[["name", "size"]])?);
+    /// assert_eq!(vec!["a    1   "], colonnade.tabulate(&[["a", "1"]])?);
+    /// assert!(!colonnade.grew());
+    /// assert_eq!(vec!["colonnade 1024"], colonnade.tabulate(&[["colonnade", "1024"]])?);
+    /// assert!(colonnade.grew());
+    /// # Ok(()) }
+    /// ```
+    pub fn grew(&self) -> bool {
+        self.layout.grew
+    }
+    /// Returns the number of columns the given text occupies when displayed, as measured during
+    /// layout. By default each grapheme cluster counts as one column and bidirectional control
+    /// characters as none; see [`width_profile`](#method.width_profile) and
+    /// [`override_char_width`](#method.override_char_width).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let colonnade = Colonnade::new(1, 80)?;
+    /// assert_eq!(5, colonnade.display_width("cafe\u{301}s"));
+    /// # Ok(()) }
+    /// ```
+    pub fn display_width(&self, text: &str) -> usize {
+        true_width(text, &self.widths)
+    }
+    /// Returns the number of lines each row occupied in the most recent `tabulate`, `macerate`,
+    /// or `render`, including its padding and the lines separating it from the next row, so
+    /// positions in the output can be mapped back to rows of data. If the
+    /// [header row](#method.header_row) is displayed its height comes first. Notes following the
+    /// rows, such as that for [`max_rows`](#method.max_rows), are not counted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(2, 10)?;
+    /// colonnade.spaces_between_rows(1);
+    /// colonnade.tabulate(&[["a", "b"], ["c", "several words here"]])?;
+    /// assert_eq!(vec![2, 3], colonnade.row_heights());
+    /// # Ok(()) }
+    /// ```
+    pub fn row_heights(&self) -> Vec<usize> {
+        self.layout.row_heights.clone()
+    }
+    /// Assign the same priority to all columns. By default, all columns have the lowest priority.
+    ///
+    /// Priority determines the order in which columns give up space when the viewport lacks sufficient
+    /// space to display all columns without wrapping. Lower priority columns give up space first.
+    ///
+    /// # Arguments
+    ///
+    /// * `priority` - The common priority. Lower numbers confer higher priority; 0 is the highest priority.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(4, 100)?;
+    /// // assign all columns the highest priority
+    /// colonnade.priority(0);
+    /// // now demote the last column
+    /// colonnade.columns[3].priority(1);
+    /// # Ok(()) }
+    /// ```
+    pub fn priority(&mut self, priority: usize) -> &mut Self {
         for i in 0..self.len() {
-            self.columns[i].adjusted = false;
-            self.columns[i].width = 0;
+            self.columns[i].priority = priority;
         }
+        self
     }
-    fn adjusted(&self) -> bool {
-        self.columns.iter().all(|c| c.adjusted)
-    }
-    // determine the optimal widths of the columns given the data and the specified constraints
-    fn lay_out<T, U, V, W, X>(&mut self, table: T) -> Result<Vec<Vec<String>>, ColonnadeError>
-    where
-        T: IntoIterator<Item = U, IntoIter = V>,
-        U: IntoIterator<Item = W, IntoIter = X>,
-        V: Iterator<Item = U>,
-        W: ToString,
-        X: Iterator<Item = W>,
-    {
-        let owned_table = self.own_table(table);
-        if self.adjusted() {
-            return Ok(owned_table);
+    /// Assign the same maximum width to all columns. By default columns have no maximum width.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_width` - The common maximum width.
+    ///
+    /// # Errors
+    ///
+    /// * `ColonnadeError::MinGreaterThanMax` - Assigning a maximum width in conflict with some assigned minimum width.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(4, 100)?;
+    /// // assign all columns a maximum width of 20
+    /// colonnade.max_width(20)?;
+    /// // at most we will now use only 83 of the characters provided by the viewport (until we mess with margins)
+    /// # Ok(()) }
+    /// ```
+    pub fn max_width(&mut self, max_width: usize) -> Result<&mut Self, ColonnadeError> {
+        for i in 0..self.len() {
+            self.columns[i].max_width(max_width)?;
         }
-        self.reset();
-        let ref_table = Colonnade::ref_table(&owned_table);
-        let table = &ref_table;
-        // validate table
-        for i in 0..table.len() {
-            let row = &table[i];
-            if row.len() != self.len() {
-                return Err(ColonnadeError::InconsistentColumns(
-                    i,
-                    row.len(),
-                    self.len(),
-                ));
-            }
+        Ok(self)
+    }
+    /// Assign the same minimum width to all columns. By default columns have no minimum width.
+    ///
+    /// # Arguments
+    ///
+    /// * `min_width` - The common minimum width.
+    ///
+    /// # Errors
+    ///
+    /// * `ColonnadeError::MinGreaterThanMax` - Assigning a maximum width in conflict with some assigned minimum width.
+    /// * `ColonnadeError::InsufficientSpace` - Assigning this minimum width means the columns require more space than the viewport provides.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(4, 100)?;
+    /// // assign all columns a minimum width of 20
+    /// colonnade.min_width(20)?;
+    /// // we will now use at least 83 of the characters provided by the viewport (until we mess with margins)
+    /// # Ok(()) }
+    /// ```
+    pub fn min_width(&mut self, min_width: usize) -> Result<&mut Self, ColonnadeError> {
+        for i in 0..self.len() {
+            self.columns[i].min_width(min_width)?;
         }
         if !self.sufficient_space() {
-            return Err(ColonnadeError::InsufficientSpace);
-        }
-        // first try to do it all without splitting
-        for i in 0..table.len() {
-            for c in 0..self.len() {
-                let m = Colonnade::width_after_normalization(&table[i][c])
-                    + self.columns[c].horizontal_padding();
-                if m >= self.columns[c].width {
-                    // to force initial expansion to min width
-                    self.columns[c].expand(m);
-                }
-            }
-        }
-        if self.required_width() <= self.width {
-            self.mark_adjusted();
-            return Ok(owned_table);
-        }
-        let mut modified_columns: Vec<usize> = Vec::with_capacity(self.len());
-        // try shrinking columns to their longest word by order of priority
-        for p in self.priorities() {
-            for c in 0..self.len() {
-                if self.columns[c].priority == p && self.columns[c].is_shrinkable() {
-                    modified_columns.push(c);
-                    self.columns[c].shrink(0);
-                    for r in 0..table.len() {
-                        let m = longest_word(&table[r][c]) + self.columns[c].horizontal_padding();
-                        if m > self.columns[c].width {
-                            self.columns[c].expand(m);
-                        }
-                    }
-                }
-            }
-            if self.required_width() <= self.width {
-                break;
-            }
+            Err(self.insufficient_space())
+        } else {
+            Ok(self)
         }
-        if self.required_width() > self.width {
-            // forcibly truncate long columns
-            let mut truncatable_columns = self.columns.iter().enumerate().collect::<Vec<_>>();
-            truncatable_columns.retain(|(_, c)| c.is_shrinkable());
-            let truncatable_columns: Vec<usize> =
-                truncatable_columns.iter().map(|(i, _)| *i).collect();
-            let mut priorities: Vec<usize> = truncatable_columns
-                .iter()
-                .map(|&i| self.columns[i].priority)
-                .collect();
-            priorities.sort_unstable();
-            priorities.dedup();
-            priorities.reverse();
-            'outer: for p in priorities {
-                let mut shrinkables: Vec<&usize> = truncatable_columns
-                    .iter()
-                    .filter(|&&i| self.columns[i].priority == p)
-                    .collect();
-                loop {
-                    let excess = self.required_width() - self.width;
-                    if excess == 0 {
-                        break 'outer;
-                    }
-                    if excess <= shrinkables.len() {
-                        shrinkables.retain(|&&i| self.columns[i].shrink_by(1));
-                    } else {
-                        let share = excess / shrinkables.len();
-                        shrinkables.retain(|&&i| self.columns[i].shrink_by(share));
-                    }
-                    if shrinkables.is_empty() {
-                        break;
-                    }
-                }
-            }
-            if self.required_width() > self.width {
-                return Err(ColonnadeError::InsufficientSpace);
-            }
-        } else if self.required_width() < self.width {
-            // try to give back surplus space
-            modified_columns.retain(|&i| self.columns[i].is_expandable());
-            if !modified_columns.is_empty() {
-                while self.required_width() < self.width {
-                    // find highest priority among modified columns
-                    if let Some(priority) = modified_columns
-                        .iter()
-                        .map(|&i| self.columns[i].priority)
-                        .min()
-                    {
-                        // there are still some modified columns we haven't restored any space to
-                        let mut winners: Vec<&usize> = modified_columns
-                            .iter()
-                            .filter(|&&i| self.columns[i].priority == priority)
-                            .collect();
-                        let surplus = self.width - self.required_width();
-                        if surplus <= winners.len() {
-                            // give one column back to as many of the winners as possible and call it a day
-                            // we will necessarily break out of the loop after this
-                            for &&i in winners.iter().take(surplus) {
-                                self.columns[i].width += 1;
-                            }
-                        } else {
-                            // give a share back to each winner
-                            loop {
-                                let surplus = self.width - self.required_width();
-                                if surplus == 0 {
-                                    break;
-                                }
-                                winners.retain(|&&i| self.columns[i].is_expandable());
-                                if winners.is_empty() {
-                                    break;
-                                }
-                                if surplus <= winners.len() {
-                                    for &&i in winners.iter().take(surplus) {
-                                        self.columns[i].width += 1;
-                                    }
-                                    break;
-                                }
-                                let mut changed = false;
-                                let share = surplus / winners.len();
-                                for &&i in winners.iter() {
-                                    let change = self.columns[i].expand_by(share);
-                                    changed = changed || change;
-                                }
-                                if !changed {
-                                    break;
-                                }
-                            }
-                            modified_columns.retain(|&i| self.columns[i].priority != priority);
-                        }
-                    } else {
-                        break;
-                    }
-                }
-            }
+    }
+    /// Assign the same maximum and minimum width to all columns. By default columns have neither a maximum nor a minimum width.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - The common width.
+    ///
+    /// # Errors
+    ///
+    /// This method is a convenience method which assigns all columns the same maximum and minimum width. Therefore
+    /// the errors thrown are those thrown by [`max_width`](#method.max_width) and [`min_width`](#method.min_width).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(4, 100)?;
+    /// // assign all columns a width of 20
+    /// colonnade.fixed_width(20)?;
+    /// // we will now use at exactly 83 of the characters provided by the viewport (until we mess with margins)
+    /// # Ok(()) }
+    /// ```
+    pub fn fixed_width(&mut self, width: usize) -> Result<&mut Self, ColonnadeError> {
+        for i in 0..self.len() {
+            self.columns[i].fixed_width(width)?;
         }
-        self.mark_adjusted();
-        Ok(owned_table)
+        Ok(self)
     }
-    fn mark_adjusted(&mut self) {
+    /// Remove any maximum or minimum column widths.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::Colonnade;
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(4, 100)?;
+    /// // assign all columns a width of 20
+    /// colonnade.fixed_width(20)?;
+    /// // later ...
+    /// colonnade.clear_limits();
+    /// # Ok(()) }
+    /// ```
+    pub fn clear_limits(&mut self) -> &mut Self {
         for i in 0..self.len() {
-            self.columns[i].adjusted = true;
+            self.columns[i].clear_limits();
         }
+        self
     }
-    /// Specify a number of blank lines to insert between table rows.
+    /// Assign all columns the same alignment. The default alignment is left.
     ///
     /// # Arguments
     ///
-    /// * `n` - A number of spaces.
+    /// * `alignment` - The desired alignment.
     ///
     /// # Example
     ///
     /// ```rust
     /// # extern crate colonnade;
-    /// # use colonnade::Colonnade;
+    /// # use colonnade::{Alignment,Colonnade};
     /// # use std::error::Error;
     /// # fn demo() -> Result<(), Box<dyn Error>> {
     /// let mut colonnade = Colonnade::new(4, 100)?;
-    /// // we want rows to be separated by a single blank line
-    /// colonnade.spaces_between_rows(1);
+    /// // all columns should be right-aligned (they're numeric)
+    /// colonnade.alignment(Alignment::Right);
     /// # Ok(()) }
     /// ```
-    pub fn spaces_between_rows(&mut self, n: usize) -> &mut Self {
-        self.spaces_between_rows = n;
+    pub fn alignment(&mut self, alignment: Alignment) -> &mut Self {
+        for i in 0..self.len() {
+            self.columns[i].alignment(alignment.clone());
+        }
         self
     }
-    /// Assign the same priority to all columns. By default, all columns have the lowest priority.
-    ///
-    /// Priority determines the order in which columns give up space when the viewport lacks sufficient
-    /// space to display all columns without wrapping. Lower priority columns give up space first.
+    /// Assign all columns the same vertical alignment. The default alignment is top.
     ///
     /// # Arguments
     ///
-    /// * `priority` - The common priority. Lower numbers confer higher priority; 0 is the highest priority.
+    /// * `vertical_alignment` - The desired alignment.
     ///
     /// # Example
     ///
     /// ```rust
     /// # extern crate colonnade;
-    /// # use colonnade::Colonnade;
+    /// # use colonnade::{Alignment, VerticalAlignment, Colonnade};
     /// # use std::error::Error;
     /// # fn demo() -> Result<(), Box<dyn Error>> {
     /// let mut colonnade = Colonnade::new(4, 100)?;
-    /// // assign all columns the highest priority
-    /// colonnade.priority(0);
-    /// // now demote the last column
-    /// colonnade.columns[3].priority(1);
+    /// // all columns should be right-aligned (they're numeric)
+    /// colonnade.vertical_alignment(VerticalAlignment::Middle);
     /// # Ok(()) }
     /// ```
-    pub fn priority(&mut self, priority: usize) -> &mut Self {
+    pub fn vertical_alignment(&mut self, vertical_alignment: VerticalAlignment) -> &mut Self {
         for i in 0..self.len() {
-            self.columns[i].priority = priority;
+            self.columns[i].vertical_alignment(vertical_alignment.clone());
         }
         self
     }
-    /// Assign the same maximum width to all columns. By default columns have no maximum width.
+    /// Assign all columns the same left margin. The left margin is a number of blank spaces
+    /// before the content of the column. By default the first column has a left margin of 0
+    /// and the other columns have a left margin of 1.
     ///
     /// # Arguments
     ///
-    /// * `max_width` - The common maximum width.
+    /// * `left_margin` - The width in blank spaces of the desired margin.
     ///
     /// # Errors
     ///
-    /// * `ColonnadeError::MinGreaterThanMax` - Assigning a maximum width in conflict with some assigned minimum width.
+    /// * `ColonnadeError::InsufficientSpace` - This margin will require more space than is available in the viewport.
     ///
     /// # Example
     ///
     /// ```rust
     /// # extern crate colonnade;
-    /// # use colonnade::Colonnade;
+    /// # use colonnade::{Alignment,Colonnade};
     /// # use std::error::Error;
     /// # fn demo() -> Result<(), Box<dyn Error>> {
     /// let mut colonnade = Colonnade::new(4, 100)?;
-    /// // assign all columns a maximum width of 20
-    /// colonnade.max_width(20)?;
-    /// // at most we will now use only 83 of the characters provided by the viewport (until we mess with margins)
+    /// colonnade.left_margin(2)?;
     /// # Ok(()) }
     /// ```
-    pub fn max_width(&mut self, max_width: usize) -> Result<&mut Self, ColonnadeError> {
+    pub fn left_margin(&mut self, left_margin: usize) -> Result<&mut Self, ColonnadeError> {
         for i in 0..self.len() {
-            match self.columns[i].max_width(max_width) {
-                Err(e) => return Err(e),
-                Ok(_) => (),
-            }
+            self.columns[i].left_margin(left_margin);
+        }
+        if !self.sufficient_space() {
+            Err(self.insufficient_space())
+        } else {
+            Ok(self)
         }
-        Ok(self)
     }
-    /// Assign the same minimum width to all columns. By default columns have no minimum width.
+    /// Set the number of blank spaces between adjacent columns: the left margin of every column
+    /// but the first, which keeps its own. By default columns are separated by 1 space.
     ///
     /// # Arguments
     ///
-    /// * `min_width` - The common minimum width.
+    /// * `spaces` - The width in blank spaces of the gaps between columns.
     ///
     /// # Errors
     ///
-    /// * `ColonnadeError::MinGreaterThanMax` - Assigning a maximum width in conflict with some assigned minimum width.
-    /// * `ColonnadeError::InsufficientSpace` - Assigning this minimum width means the columns require more space than the viewport provides.
+    /// * `ColonnadeError::InsufficientSpace` - These gaps will require more space than is available in the viewport.
     ///
     /// # Example
     ///
@@ -1570,85 +9350,187 @@ impl Colonnade {
     /// # use colonnade::Colonnade;
     /// # use std::error::Error;
     /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(3, 100)?;
+    /// colonnade.spaces_between_columns(3)?;
+    /// assert_eq!(vec!["a   b   c"], colonnade.tabulate([["a", "b", "c"]])?);
+    /// # Ok(()) }
+    /// ```
+    pub fn spaces_between_columns(&mut self, spaces: usize) -> Result<&mut Self, ColonnadeError> {
+        for i in 1..self.len() {
+            self.columns[i].left_margin(spaces);
+        }
+        if !self.sufficient_space() {
+            Err(self.insufficient_space())
+        } else {
+            Ok(self)
+        }
+    }
+    /// Assign all columns the same padding. The padding is a number of blank spaces
+    /// before and after the contents of the column and a number of blank lines above and below
+    /// it. By default the padding is 0. You most likely don't want any padding unless you are
+    /// colorizing the text -- text immediately after color transitions is more difficult to read
+    /// and less aesthetically pleasing.
+    ///
+    /// # Arguments
+    ///
+    /// * `padding` - The width in blank spaces/lines of the desired padding.
+    ///
+    /// # Errors
+    ///
+    /// * `ColonnadeError::InsufficientSpace` - This padding will require more space than is available in the viewport.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Alignment,Colonnade};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
     /// let mut colonnade = Colonnade::new(4, 100)?;
-    /// // assign all columns a minimum width of 20
-    /// colonnade.min_width(20)?;
-    /// // we will now use at least 83 of the characters provided by the viewport (until we mess with margins)
+    /// colonnade.padding(1)?;
     /// # Ok(()) }
     /// ```
-    pub fn min_width(&mut self, min_width: usize) -> Result<&mut Self, ColonnadeError> {
+    pub fn padding(&mut self, padding: usize) -> Result<&mut Self, ColonnadeError> {
         for i in 0..self.len() {
-            match self.columns[i].min_width(min_width) {
-                Err(e) => return Err(e),
-                Ok(_) => (),
-            }
+            self.columns[i].padding(padding);
         }
         if !self.sufficient_space() {
-            Err(ColonnadeError::InsufficientSpace)
+            Err(self.insufficient_space())
         } else {
             Ok(self)
         }
     }
-    /// Assign the same maximum and minimum width to all columns. By default columns have neither a maximum nor a minimum width.
+    /// Assign all columns the same horizontal padding -- space before and after the column's text.
+    ///
+    /// See [`padding`](#method.padding).
     ///
     /// # Arguments
     ///
-    /// * `width` - The common width.
+    /// * `padding` - The width in blank spaces/lines of the desired padding.
     ///
     /// # Errors
     ///
-    /// This method is a convenience method which assigns all columns the same maximum and minimum width. Therefore
-    /// the errors thrown are those thrown by [`max_width`](#method.max_width) and [`min_width`](#method.min_width).
+    /// * `ColonnadeError::InsufficientSpace` - This padding will require more space than is available in the viewport.
     ///
     /// # Example
     ///
     /// ```rust
     /// # extern crate colonnade;
-    /// # use colonnade::Colonnade;
+    /// # use colonnade::{Alignment,Colonnade};
     /// # use std::error::Error;
     /// # fn demo() -> Result<(), Box<dyn Error>> {
     /// let mut colonnade = Colonnade::new(4, 100)?;
-    /// // assign all columns a width of 20
-    /// colonnade.fixed_width(20)?;
-    /// // we will now use at exactly 83 of the characters provided by the viewport (until we mess with margins)
+    /// colonnade.padding_horizontal(1)?;
     /// # Ok(()) }
     /// ```
-    pub fn fixed_width(&mut self, width: usize) -> Result<&mut Self, ColonnadeError> {
+    pub fn padding_horizontal(&mut self, padding: usize) -> Result<&mut Self, ColonnadeError> {
         for i in 0..self.len() {
-            match self.columns[i].fixed_width(width) {
-                Err(e) => return Err(e),
-                Ok(_) => (),
-            }
+            self.columns[i].padding_horizontal(padding);
+        }
+        if !self.sufficient_space() {
+            Err(self.insufficient_space())
+        } else {
+            Ok(self)
         }
-        Ok(self)
     }
-    /// Remove any maximum or minimum column widths.
+    /// Assign all columns the same left padding -- space before the column's text.
+    ///
+    /// See [`padding`](#method.padding).
+    ///
+    /// # Arguments
+    ///
+    /// * `padding` - The width in blank spaces/lines of the desired padding.
+    ///
+    /// # Errors
+    ///
+    /// * `ColonnadeError::InsufficientSpace` - This padding will require more space than is available in the viewport.
     ///
     /// # Example
     ///
     /// ```rust
     /// # extern crate colonnade;
-    /// # use colonnade::Colonnade;
+    /// # use colonnade::{Alignment,Colonnade};
     /// # use std::error::Error;
     /// # fn demo() -> Result<(), Box<dyn Error>> {
     /// let mut colonnade = Colonnade::new(4, 100)?;
-    /// // assign all columns a width of 20
-    /// colonnade.fixed_width(20)?;
-    /// // later ...
-    /// colonnade.clear_limits();
+    /// colonnade.padding_left(1)?;
     /// # Ok(()) }
     /// ```
-    pub fn clear_limits(&mut self) -> &mut Self {
+    pub fn padding_left(&mut self, padding: usize) -> Result<&mut Self, ColonnadeError> {
         for i in 0..self.len() {
-            self.columns[i].clear_limits();
+            self.columns[i].padding_left(padding);
+        }
+        if !self.sufficient_space() {
+            Err(self.insufficient_space())
+        } else {
+            Ok(self)
+        }
+    }
+    /// Assign all columns the same right padding -- space after the column's text.
+    ///
+    /// See [`padding`](#method.padding).
+    ///
+    /// # Arguments
+    ///
+    /// * `padding` - The width in blank spaces/lines of the desired padding.
+    ///
+    /// # Errors
+    ///
+    /// * `ColonnadeError::InsufficientSpace` - This padding will require more space than is available in the viewport.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Alignment,Colonnade};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(4, 100)?;
+    /// colonnade.padding_right(1)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn padding_right(&mut self, padding: usize) -> Result<&mut Self, ColonnadeError> {
+        for i in 0..self.len() {
+            self.columns[i].padding_right(padding);
+        }
+        if !self.sufficient_space() {
+            Err(self.insufficient_space())
+        } else {
+            Ok(self)
+        }
+    }
+    /// Assign all columns the same vertical padding -- blank lines before and after the column's text.
+    ///
+    /// See [`padding`](#method.padding).
+    ///
+    /// # Arguments
+    ///
+    /// * `padding` - The width in blank spaces/lines of the desired padding.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate colonnade;
+    /// # use colonnade::{Alignment,Colonnade};
+    /// # use std::error::Error;
+    /// # fn demo() -> Result<(), Box<dyn Error>> {
+    /// let mut colonnade = Colonnade::new(4, 100)?;
+    /// colonnade.padding_vertical(1);
+    /// # Ok(()) }
+    /// ```
+    pub fn padding_vertical(&mut self, padding: usize) -> &mut Self {
+        for i in 0..self.len() {
+            self.columns[i].padding_vertical(padding);
         }
         self
     }
-    /// Assign all columns the same alignment. The default alignment is left.
+    /// Assign all columns the same top padding -- blank lines before the column's text.
+    ///
+    /// See [`padding`](#method.padding).
     ///
     /// # Arguments
     ///
-    /// * `alignment` - The desired alignment.
+    /// * `padding` - The width in blank spaces/lines of the desired padding.
     ///
     /// # Example
     ///
@@ -1658,293 +9540,1362 @@ impl Colonnade {
     /// # use std::error::Error;
     /// # fn demo() -> Result<(), Box<dyn Error>> {
     /// let mut colonnade = Colonnade::new(4, 100)?;
-    /// // all columns should be right-aligned (they're numeric)
-    /// colonnade.alignment(Alignment::Right);
+    /// colonnade.padding_top(1);
     /// # Ok(()) }
     /// ```
-    pub fn alignment(&mut self, alignment: Alignment) -> &mut Self {
+    pub fn padding_top(&mut self, padding: usize) -> &mut Self {
         for i in 0..self.len() {
-            self.columns[i].alignment(alignment.clone());
+            self.columns[i].padding_top(padding);
         }
         self
     }
-    /// Assign all columns the same vertical alignment. The default alignment is top.
+    /// Assign all columns the same bottom padding -- blank lines after the column's text.
+    ///
+    /// See [`padding`](#method.padding).
     ///
     /// # Arguments
     ///
-    /// * `vertical_alignment` - The desired alignment.
+    /// * `padding` - The width in blank spaces/lines of the desired padding.
     ///
     /// # Example
     ///
     /// ```rust
     /// # extern crate colonnade;
-    /// # use colonnade::{Alignment, VerticalAlignment, Colonnade};
+    /// # use colonnade::{Alignment,Colonnade};
     /// # use std::error::Error;
     /// # fn demo() -> Result<(), Box<dyn Error>> {
     /// let mut colonnade = Colonnade::new(4, 100)?;
-    /// // all columns should be right-aligned (they're numeric)
-    /// colonnade.vertical_alignment(VerticalAlignment::Middle);
+    /// colonnade.padding_bottom(1);
     /// # Ok(()) }
     /// ```
-    pub fn vertical_alignment(&mut self, vertical_alignment: VerticalAlignment) -> &mut Self {
+    pub fn padding_bottom(&mut self, padding: usize) -> &mut Self {
         for i in 0..self.len() {
-            self.columns[i].vertical_alignment(vertical_alignment.clone());
+            self.columns[i].padding_bottom(padding);
         }
         self
     }
-    /// Assign all columns the same left margin. The left margin is a number of blank spaces
-    /// before the content of the column. By default the first column has a left margin of 0
-    /// and the other columns have a left margin of 1.
+    /// Toggle the hyphenation of all columns.
+    ///
+    /// See [`Column::hyphenate`](struct.Column.html#method.hyphenate).
     ///
     /// # Arguments
     ///
-    /// * `left_margin` - The width in blank spaces of the desired margin.
+    /// * `hyphenate` - Whether long words will be hyphenated when split.
+    pub fn hyphenate(&mut self, hyphenate: bool) -> &mut Self {
+        for i in 0..self.len() {
+            self.columns[i].hyphenate(hyphenate);
+        }
+        self
+    }
+    /// Toggle whether wrapped text in any column avoids ending with a single word.
     ///
-    /// # Errors
+    /// See [`Column::avoid_orphans`](struct.Column.html#method.avoid_orphans).
     ///
-    /// * `ColonnadeError::InsufficientSpace` - This margin will require more space than is available in the viewport.
+    /// # Arguments
+    ///
+    /// * `avoid_orphans` - Whether to avoid orphans.
+    pub fn avoid_orphans(&mut self, avoid_orphans: bool) -> &mut Self {
+        for i in 0..self.len() {
+            self.columns[i].avoid_orphans(avoid_orphans);
+        }
+        self
+    }
+    /// Assign the same number format to all columns.
+    ///
+    /// See [`Column::number_format`](struct.Column.html#method.number_format).
+    ///
+    /// # Arguments
+    ///
+    /// * `number_format` - The formatting to apply to numbers.
+    pub fn number_format(&mut self, number_format: NumberFormat) -> &mut Self {
+        for i in 0..self.len() {
+            self.columns[i].number_format(number_format.clone());
+        }
+        self
+    }
+    /// Assign the same placeholder for empty cells to all columns.
+    ///
+    /// See [`Column::empty_placeholder`](struct.Column.html#method.empty_placeholder).
+    ///
+    /// # Arguments
+    ///
+    /// * `placeholder` - The text to display in empty cells.
     ///
     /// # Example
     ///
     /// ```rust
     /// # extern crate colonnade;
-    /// # use colonnade::{Alignment,Colonnade};
+    /// # use colonnade::Colonnade;
     /// # use std::error::Error;
     /// # fn demo() -> Result<(), Box<dyn Error>> {
-    /// let mut colonnade = Colonnade::new(4, 100)?;
-    /// colonnade.left_margin(2)?;
+    /// let mut colonnade = Colonnade::new(3, 80)?;
+    /// colonnade.empty_placeholder("—");
+    /// assert_eq!(vec!["a — c"], colonnade.tabulate([["a", "", "c"]])?);
     /// # Ok(()) }
     /// ```
-    pub fn left_margin(&mut self, left_margin: usize) -> Result<&mut Self, ColonnadeError> {
+    pub fn empty_placeholder(&mut self, placeholder: &str) -> &mut Self {
         for i in 0..self.len() {
-            self.columns[i].left_margin(left_margin);
+            self.columns[i].empty_placeholder(placeholder);
         }
-        if !self.sufficient_space() {
-            Err(ColonnadeError::InsufficientSpace)
-        } else {
-            Ok(self)
+        self
+    }
+    /// Assign the same date and time format to all columns.
+    ///
+    /// See [`Column::datetime_format`](struct.Column.html#method.datetime_format). This method
+    /// requires the `chrono` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `datetime_format` - The formatting to apply to dates and times.
+    #[cfg(feature = "chrono")]
+    pub fn datetime_format(&mut self, datetime_format: DateTimeFormat) -> &mut Self {
+        for i in 0..self.len() {
+            self.columns[i].datetime_format(datetime_format.clone());
         }
+        self
     }
-    /// Assign all columns the same padding. The padding is a number of blank spaces
-    /// before and after the contents of the column and a number of blank lines above and below
-    /// it. By default the padding is 0. You most likely don't want any padding unless you are
-    /// colorizing the text -- text immediately after color transitions is more difficult to read
-    /// and less aesthetically pleasing.
+    /// Display numbers in all columns as human-readable quantities.
+    ///
+    /// See [`Column::humanize`](struct.Column.html#method.humanize).
     ///
     /// # Arguments
     ///
-    /// * `padding` - The width in blank spaces/lines of the desired padding.
+    /// * `humanize` - The units of the numbers.
+    pub fn humanize(&mut self, humanize: Humanize) -> &mut Self {
+        for i in 0..self.len() {
+            self.columns[i].humanize(humanize.clone());
+        }
+        self
+    }
+}
+
+/// The state of the width limits of a [`ColumnConfig`](struct.ColumnConfig.html).
+///
+/// The state determines which width setters are available, so contradictory limits, such as a
+/// fixed width and a maximum width, are rejected by the compiler.
+pub trait WidthState {}
+
+/// The width state of a column configuration with no width limits.
+#[derive(Debug, Clone, Copy)]
+pub struct Flexible;
+
+/// The width state of a column configuration with a minimum width, a maximum width, or both.
+#[derive(Debug, Clone, Copy)]
+pub struct Bounded;
+
+/// The width state of a column configuration with a fixed width.
+#[derive(Debug, Clone, Copy)]
+pub struct Fixed;
+
+impl WidthState for Flexible {}
+impl WidthState for Bounded {}
+impl WidthState for Fixed {}
+
+/// The configuration of a single column collected by a [`ColonnadeBuilder`](struct.ColonnadeBuilder.html).
+///
+/// The type parameter tracks the column's width limits: a flexible column may be given a fixed
+/// width or minimum and maximum widths, but a column with a fixed width may be given no other
+/// limits. The remaining settings are checked when the builder is built, at which point they are
+/// applied with the corresponding [`Column`](struct.Column.html) methods.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate colonnade;
+/// # use colonnade::{ColonnadeBuilder, ColumnConfig};
+/// # use std::error::Error;
+/// # fn demo() -> Result<(), Box<dyn Error>> {
+/// let colonnade = ColonnadeBuilder::new(2, 80)
+///     .column(0, ColumnConfig::new().fixed_width(10))
+///     .column(1, ColumnConfig::new().min_width(5).max_width(20))
+///     .build()?;
+/// # Ok(()) }
+/// ```
+///
+/// A fixed width excludes further limits.
+///
+/// ```compile_fail
+/// # extern crate colonnade;
+/// # use colonnade::ColumnConfig;
+/// let config = ColumnConfig::new().fixed_width(10).max_width(20);
+/// ```
+///
+/// A configuration deserialized with the `serde` feature is flexible whatever its limits, which are
+/// checked only when the table is built.
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
+pub struct ColumnConfig<W: WidthState = Flexible> {
+    priority: Option<usize>,
+    min_width: Option<usize>,
+    max_width: Option<usize>,
+    alignment: Option<Alignment>,
+    vertical_alignment: Option<VerticalAlignment>,
+    left_margin: Option<usize>,
+    right_margin: Option<usize>,
+    padding_horizontal: Option<usize>,
+    padding_vertical: Option<usize>,
+    name: Option<String>,
+    hidden: Option<bool>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    width_state: PhantomData<W>,
+}
+
+impl Default for ColumnConfig<Flexible> {
+    fn default() -> Self {
+        ColumnConfig {
+            priority: None,
+            min_width: None,
+            max_width: None,
+            alignment: None,
+            vertical_alignment: None,
+            left_margin: None,
+            right_margin: None,
+            padding_horizontal: None,
+            padding_vertical: None,
+            name: None,
+            hidden: None,
+            width_state: PhantomData,
+        }
+    }
+}
+
+impl ColumnConfig<Flexible> {
+    /// Start a column configuration which changes nothing.
+    pub fn new() -> ColumnConfig<Flexible> {
+        ColumnConfig::default()
+    }
+    /// See [`Column::fixed_width`](struct.Column.html#method.fixed_width).
+    pub fn fixed_width(self, width: usize) -> ColumnConfig<Fixed> {
+        let mut config = self.into_state();
+        config.min_width = Some(width);
+        config.max_width = Some(width);
+        config
+    }
+    /// See [`Column::min_width`](struct.Column.html#method.min_width).
+    pub fn min_width(self, min_width: usize) -> ColumnConfig<Bounded> {
+        let mut config: ColumnConfig<Bounded> = self.into_state();
+        config.min_width = Some(min_width);
+        config
+    }
+    /// See [`Column::max_width`](struct.Column.html#method.max_width).
+    pub fn max_width(self, max_width: usize) -> ColumnConfig<Bounded> {
+        let mut config: ColumnConfig<Bounded> = self.into_state();
+        config.max_width = Some(max_width);
+        config
+    }
+}
+
+impl ColumnConfig<Bounded> {
+    /// See [`Column::min_width`](struct.Column.html#method.min_width).
     ///
     /// # Errors
     ///
-    /// * `ColonnadeError::InsufficientSpace` - This padding will require more space than is available in the viewport.
+    /// A minimum width greater than the maximum is reported as `ColonnadeError::MinGreaterThanMax`
+    /// when the table is built.
+    pub fn min_width(mut self, min_width: usize) -> ColumnConfig<Bounded> {
+        self.min_width = Some(min_width);
+        self
+    }
+    /// See [`Column::max_width`](struct.Column.html#method.max_width).
     ///
-    /// # Example
+    /// # Errors
     ///
-    /// ```rust
-    /// # extern crate colonnade;
-    /// # use colonnade::{Alignment,Colonnade};
-    /// # use std::error::Error;
-    /// # fn demo() -> Result<(), Box<dyn Error>> {
-    /// let mut colonnade = Colonnade::new(4, 100)?;
-    /// colonnade.padding(1)?;
-    /// # Ok(()) }
-    /// ```
-    pub fn padding(&mut self, padding: usize) -> Result<&mut Self, ColonnadeError> {
-        for i in 0..self.len() {
-            self.columns[i].padding(padding);
+    /// A maximum width less than the minimum is reported as `ColonnadeError::MinGreaterThanMax`
+    /// when the table is built.
+    pub fn max_width(mut self, max_width: usize) -> ColumnConfig<Bounded> {
+        self.max_width = Some(max_width);
+        self
+    }
+}
+
+impl<W: WidthState> ColumnConfig<W> {
+    // the same configuration in another width state
+    fn into_state<V: WidthState>(self) -> ColumnConfig<V> {
+        ColumnConfig {
+            priority: self.priority,
+            min_width: self.min_width,
+            max_width: self.max_width,
+            alignment: self.alignment,
+            vertical_alignment: self.vertical_alignment,
+            left_margin: self.left_margin,
+            right_margin: self.right_margin,
+            padding_horizontal: self.padding_horizontal,
+            padding_vertical: self.padding_vertical,
+            name: self.name,
+            hidden: self.hidden,
+            width_state: PhantomData,
         }
-        if !self.sufficient_space() {
-            Err(ColonnadeError::InsufficientSpace)
-        } else {
-            Ok(self)
+    }
+    /// See [`Column::priority`](struct.Column.html#method.priority).
+    pub fn priority(mut self, priority: usize) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+    /// See [`Column::alignment`](struct.Column.html#method.alignment).
+    pub fn alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = Some(alignment);
+        self
+    }
+    /// See [`Column::vertical_alignment`](struct.Column.html#method.vertical_alignment).
+    pub fn vertical_alignment(mut self, vertical_alignment: VerticalAlignment) -> Self {
+        self.vertical_alignment = Some(vertical_alignment);
+        self
+    }
+    /// See [`Column::left_margin`](struct.Column.html#method.left_margin).
+    pub fn left_margin(mut self, left_margin: usize) -> Self {
+        self.left_margin = Some(left_margin);
+        self
+    }
+    /// See [`Column::right_margin`](struct.Column.html#method.right_margin).
+    pub fn right_margin(mut self, right_margin: usize) -> Self {
+        self.right_margin = Some(right_margin);
+        self
+    }
+    /// See [`Column::padding_horizontal`](struct.Column.html#method.padding_horizontal).
+    pub fn padding_horizontal(mut self, padding: usize) -> Self {
+        self.padding_horizontal = Some(padding);
+        self
+    }
+    /// See [`Column::padding_vertical`](struct.Column.html#method.padding_vertical).
+    pub fn padding_vertical(mut self, padding: usize) -> Self {
+        self.padding_vertical = Some(padding);
+        self
+    }
+    /// See [`Column::name`](struct.Column.html#method.name).
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+    /// See [`Column::hidden`](struct.Column.html#method.hidden).
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.hidden = Some(hidden);
+        self
+    }
+    // apply the configuration to a column, checking the width limits
+    fn apply(&self, column: &mut Column) -> Result<(), ColonnadeError> {
+        if let Some(priority) = self.priority {
+            column.priority(priority);
+        }
+        if self.min_width.is_some() || self.max_width.is_some() {
+            column.clear_limits();
+        }
+        if let Some(min_width) = self.min_width {
+            column.min_width(min_width)?;
+        }
+        if let Some(max_width) = self.max_width {
+            column.max_width(max_width)?;
+        }
+        if let Some(alignment) = &self.alignment {
+            column.alignment(alignment.clone());
+        }
+        if let Some(vertical_alignment) = &self.vertical_alignment {
+            column.vertical_alignment(vertical_alignment.clone());
+        }
+        if let Some(left_margin) = self.left_margin {
+            column.left_margin(left_margin);
+        }
+        if let Some(right_margin) = self.right_margin {
+            column.right_margin(right_margin);
+        }
+        if let Some(padding) = self.padding_horizontal {
+            column.padding_horizontal(padding);
+        }
+        if let Some(padding) = self.padding_vertical {
+            column.padding_vertical(padding);
+        }
+        if let Some(name) = &self.name {
+            column.name(name);
+        }
+        if let Some(hidden) = self.hidden {
+            column.hidden(hidden);
+        }
+        Ok(())
+    }
+}
+
+/// A bundle of settings common to all the columns of a table -- margins, padding, alignment, and
+/// the representation of separator lines -- defining a house style once for any number of tables.
+///
+/// See [`Colonnade::with_preset`](struct.Colonnade.html#method.with_preset) and
+/// [`ColonnadeBuilder::preset`](struct.ColonnadeBuilder.html#method.preset). With the `serde`
+/// feature a preset can be saved and loaded like any other configuration.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate colonnade;
+/// # use colonnade::{Alignment, Colonnade, Preset, Separator};
+/// # use std::error::Error;
+/// # fn demo() -> Result<(), Box<dyn Error>> {
+/// let house_style = Preset::new()
+///     .spaces_between_columns(2)
+///     .spaces_between_rows(1)
+///     .separator(Separator::Fill("-".to_string()))
+///     .alignment(Alignment::Center);
+/// let mut colonnade = Colonnade::with_preset(&house_style, 2, 80)?;
+/// assert_eq!(
+///     vec!["a   bbb", "-------", "cc   d "],
+///     colonnade.tabulate([["a", "bbb"], ["cc", "d"]])?
+/// );
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct Preset {
+    left_margin: Option<usize>,
+    spaces_between_columns: Option<usize>,
+    spaces_between_rows: Option<usize>,
+    padding_horizontal: Option<usize>,
+    padding_vertical: Option<usize>,
+    alignment: Option<Alignment>,
+    separator: Option<Separator>,
+    right_margin: Option<usize>,
+    header_row: Option<bool>,
+    borders: Option<Borders>,
+}
+
+impl Preset {
+    /// Start a preset which changes nothing.
+    pub fn new() -> Preset {
+        Preset::default()
+    }
+    /// See [`Colonnade::left_margin`](struct.Colonnade.html#method.left_margin).
+    pub fn left_margin(mut self, left_margin: usize) -> Preset {
+        self.left_margin = Some(left_margin);
+        self
+    }
+    /// See [`Colonnade::spaces_between_columns`](struct.Colonnade.html#method.spaces_between_columns).
+    pub fn spaces_between_columns(mut self, spaces: usize) -> Preset {
+        self.spaces_between_columns = Some(spaces);
+        self
+    }
+    /// See [`Colonnade::spaces_between_rows`](struct.Colonnade.html#method.spaces_between_rows).
+    pub fn spaces_between_rows(mut self, n: usize) -> Preset {
+        self.spaces_between_rows = Some(n);
+        self
+    }
+    /// See [`Colonnade::padding_horizontal`](struct.Colonnade.html#method.padding_horizontal).
+    pub fn padding_horizontal(mut self, padding: usize) -> Preset {
+        self.padding_horizontal = Some(padding);
+        self
+    }
+    /// See [`Colonnade::padding_vertical`](struct.Colonnade.html#method.padding_vertical).
+    pub fn padding_vertical(mut self, padding: usize) -> Preset {
+        self.padding_vertical = Some(padding);
+        self
+    }
+    /// See [`Colonnade::alignment`](struct.Colonnade.html#method.alignment).
+    pub fn alignment(mut self, alignment: Alignment) -> Preset {
+        self.alignment = Some(alignment);
+        self
+    }
+    /// See [`Colonnade::separator`](struct.Colonnade.html#method.separator).
+    pub fn separator(mut self, separator: Separator) -> Preset {
+        self.separator = Some(separator);
+        self
+    }
+    /// The [right margin](struct.Column.html#method.right_margin) of the last column, which
+    /// leaves room for a border after it.
+    pub fn right_margin(mut self, right_margin: usize) -> Preset {
+        self.right_margin = Some(right_margin);
+        self
+    }
+    /// See [`Colonnade::header_row`](struct.Colonnade.html#method.header_row).
+    pub fn header_row(mut self, header_row: bool) -> Preset {
+        self.header_row = Some(header_row);
+        self
+    }
+    /// See [`Colonnade::borders`](struct.Colonnade.html#method.borders).
+    pub fn borders(mut self, borders: Borders) -> Preset {
+        self.borders = Some(borders);
+        self
+    }
+    /// The preset corresponding to one of the table formats of python's `tabulate` library:
+    ///
+    /// * `"plain"` - columns two spaces apart, with no margin, padding, or blank lines between rows
+    /// * `"simple"` - as `"plain"`, with the header row and a rule of `-` beneath it
+    /// * `"github"` - a pipe table: columns framed by `|` bars, with the header row and a rule of
+    ///   `-` beneath it
+    /// * `"grid"` - columns framed by `|` bars with a rule of `-` above, below, and between the
+    ///   rows, `=` beneath the header row, and `+` where bars cross rules
+    ///
+    /// See [`Colonnade::format`](struct.Colonnade.html#method.format).
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the format.
+    pub fn named(name: &str) -> Option<Preset> {
+        let unframed = Preset::new()
+            .left_margin(0)
+            .spaces_between_columns(2)
+            .right_margin(0)
+            .spaces_between_rows(0)
+            .padding_horizontal(0)
+            .padding_vertical(0);
+        let framed = Preset::new()
+            .left_margin(2)
+            .spaces_between_columns(3)
+            .right_margin(2)
+            .spaces_between_rows(0)
+            .padding_horizontal(0)
+            .padding_vertical(0)
+            .header_row(true);
+        match name {
+            "plain" => Some(unframed.header_row(false).borders(Borders::new())),
+            "simple" => Some(
+                unframed
+                    .header_row(true)
+                    .borders(Borders::new().header_rule('-')),
+            ),
+            "github" => Some(framed.borders(Borders::new().bars('|').header_rule('-'))),
+            "grid" => Some(
+                framed.borders(
+                    Borders::new()
+                        .bars('|')
+                        .junction('+')
+                        .header_rule('=')
+                        .row_rules('-')
+                        .edge_rules('-'),
+                ),
+            ),
+            _ => None,
+        }
+    }
+    /// Apply the preset to a table, overriding its settings for all columns.
+    ///
+    /// # Arguments
+    ///
+    /// * `colonnade` - The table to style.
+    ///
+    /// # Errors
+    ///
+    /// * `ColonnadeError::InsufficientSpace` - The styled columns require more space than is available in the viewport.
+    pub fn apply(&self, colonnade: &mut Colonnade) -> Result<(), ColonnadeError> {
+        self.configure(colonnade);
+        if !colonnade.sufficient_space() {
+            return Err(colonnade.insufficient_space());
+        }
+        Ok(())
+    }
+    // apply the preset without checking the space it requires
+    fn configure(&self, colonnade: &mut Colonnade) {
+        for column in colonnade.columns.iter_mut() {
+            if let Some(left_margin) = self.left_margin {
+                column.left_margin(left_margin);
+            }
+            if let Some(spaces) = self.spaces_between_columns {
+                if column.index > 0 {
+                    column.left_margin(spaces);
+                }
+            }
+            if let Some(padding) = self.padding_horizontal {
+                column.padding_horizontal(padding);
+            }
+            if let Some(padding) = self.padding_vertical {
+                column.padding_vertical(padding);
+            }
+            if let Some(alignment) = &self.alignment {
+                column.alignment(alignment.clone());
+            }
+        }
+        if let Some(n) = self.spaces_between_rows {
+            colonnade.spaces_between_rows(n);
+        }
+        if let Some(separator) = &self.separator {
+            colonnade.separator(separator.clone());
+        }
+        if let Some(right_margin) = self.right_margin {
+            if let Some(column) = colonnade.columns.last_mut() {
+                column.right_margin(right_margin);
+            }
+        }
+        if let Some(header_row) = self.header_row {
+            colonnade.header_row(header_row);
         }
+        if let Some(borders) = &self.borders {
+            colonnade.borders(borders.clone());
+        }
+    }
+}
+
+/// A builder collecting the viewport, margins, column configurations, and headers of a table,
+/// validating them all at once when the table is built rather than setting by setting.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate colonnade;
+/// # use colonnade::{Alignment, ColonnadeBuilder, ColumnConfig};
+/// # use std::error::Error;
+/// # fn demo() -> Result<(), Box<dyn Error>> {
+/// let mut colonnade = ColonnadeBuilder::new(2, 80)
+///     .spaces_between_columns(2)
+///     .headers(&["fruit", "count"])
+///     .column(1, ColumnConfig::new().alignment(Alignment::Right))
+///     .build()?;
+/// assert_eq!(
+///     vec!["fruit  count", "apple      3"],
+///     colonnade.tabulate(&[("apple", 3)])?
+/// );
+/// # Ok(()) }
+/// ```
+///
+/// With the `serde` feature the builder can be read from a configuration file. Each column
+/// configuration is an entry in the `column` list with the column's `index`.
+///
+/// ```rust
+/// # #[cfg(all(feature = "serde", feature = "json"))]
+/// # mod demo {
+/// # use colonnade::ColonnadeBuilder;
+/// # fn demo() -> Result<(), Box<dyn std::error::Error>> {
+/// let config = r#"{
+///     "columns": 2,
+///     "width": 80,
+///     "headers": ["fruit", "count"],
+///     "column": [{ "index": 1, "alignment": "Right" }]
+/// }"#;
+/// let builder: ColonnadeBuilder = serde_json::from_str(config)?;
+/// let mut colonnade = builder.build()?;
+/// # Ok(()) }
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ColonnadeBuilder {
+    columns: usize,
+    width: usize,
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    preset: Preset,
+    #[cfg_attr(feature = "serde", serde(default))]
+    headers: Option<Vec<String>>,
+    #[cfg_attr(feature = "serde", serde(default, rename = "column"))]
+    configs: Vec<IndexedConfig>,
+}
+
+// the configuration of a particular column
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct IndexedConfig {
+    index: usize,
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    config: ColumnConfig<Flexible>,
+}
+
+impl ColonnadeBuilder {
+    /// Start building a table with the given number of columns in a viewport of the given width.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - The number of columns in the table.
+    /// * `width` - The width of the viewport in characters.
+    pub fn new(columns: usize, width: usize) -> ColonnadeBuilder {
+        ColonnadeBuilder {
+            columns,
+            width,
+            preset: Preset::new(),
+            headers: None,
+            configs: vec![],
+        }
+    }
+    /// Start from a [preset](struct.Preset.html), replacing any settings common to all columns made so far.
+    pub fn preset(mut self, preset: Preset) -> ColonnadeBuilder {
+        self.preset = preset;
+        self
+    }
+    /// Set the width of the viewport. See [`Colonnade::viewport`](struct.Colonnade.html#method.viewport).
+    pub fn viewport(mut self, width: usize) -> ColonnadeBuilder {
+        self.width = width;
+        self
+    }
+    /// See [`Colonnade::left_margin`](struct.Colonnade.html#method.left_margin).
+    pub fn left_margin(mut self, left_margin: usize) -> ColonnadeBuilder {
+        self.preset = self.preset.left_margin(left_margin);
+        self
+    }
+    /// See [`Colonnade::spaces_between_columns`](struct.Colonnade.html#method.spaces_between_columns).
+    pub fn spaces_between_columns(mut self, spaces: usize) -> ColonnadeBuilder {
+        self.preset = self.preset.spaces_between_columns(spaces);
+        self
+    }
+    /// See [`Colonnade::spaces_between_rows`](struct.Colonnade.html#method.spaces_between_rows).
+    pub fn spaces_between_rows(mut self, n: usize) -> ColonnadeBuilder {
+        self.preset = self.preset.spaces_between_rows(n);
+        self
+    }
+    /// See [`Colonnade::padding_horizontal`](struct.Colonnade.html#method.padding_horizontal).
+    pub fn padding_horizontal(mut self, padding: usize) -> ColonnadeBuilder {
+        self.preset = self.preset.padding_horizontal(padding);
+        self
+    }
+    /// See [`Colonnade::padding_vertical`](struct.Colonnade.html#method.padding_vertical).
+    pub fn padding_vertical(mut self, padding: usize) -> ColonnadeBuilder {
+        self.preset = self.preset.padding_vertical(padding);
+        self
     }
-    /// Assign all columns the same horizontal padding -- space before and after the column's text.
-    ///
-    /// See [`padding`](#method.padding).
+    /// See [`Colonnade::alignment`](struct.Colonnade.html#method.alignment).
+    pub fn alignment(mut self, alignment: Alignment) -> ColonnadeBuilder {
+        self.preset = self.preset.alignment(alignment);
+        self
+    }
+    /// Name the columns in order and display the names as a
+    /// [header row](struct.Colonnade.html#method.header_row). There may be fewer names than
+    /// columns, in which case the remaining columns are unnamed.
+    pub fn headers<S: AsRef<str>>(mut self, names: &[S]) -> ColonnadeBuilder {
+        self.headers = Some(names.iter().map(|n| n.as_ref().to_string()).collect());
+        self
+    }
+    /// Configure a particular column. The configuration is applied after the settings common to all
+    /// columns, so it overrides them. Configuring a column again replaces the earlier configuration.
     ///
     /// # Arguments
     ///
-    /// * `padding` - The width in blank spaces/lines of the desired padding.
+    /// * `index` - The index of the column.
+    /// * `config` - The column's configuration.
+    pub fn column<W: WidthState>(
+        mut self,
+        index: usize,
+        config: ColumnConfig<W>,
+    ) -> ColonnadeBuilder {
+        self.configs.retain(|c| c.index != index);
+        self.configs.push(IndexedConfig {
+            index,
+            config: config.into_state(),
+        });
+        self
+    }
+    /// Build the table, checking the configuration.
     ///
     /// # Errors
     ///
-    /// * `ColonnadeError::InsufficientSpace` - This padding will require more space than is available in the viewport.
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// # extern crate colonnade;
-    /// # use colonnade::{Alignment,Colonnade};
-    /// # use std::error::Error;
-    /// # fn demo() -> Result<(), Box<dyn Error>> {
-    /// let mut colonnade = Colonnade::new(4, 100)?;
-    /// colonnade.padding_horizontal(1)?;
-    /// # Ok(()) }
-    /// ```
-    pub fn padding_horizontal(&mut self, padding: usize) -> Result<&mut Self, ColonnadeError> {
-        for i in 0..self.len() {
-            self.columns[i].padding_horizontal(padding);
+    /// * `ColonnadeError::InsufficientColumns` - The table has no columns.
+    /// * `ColonnadeError::OutOfBounds` - A column configuration or header refers to a column that does not exist.
+    /// * `ColonnadeError::MinGreaterThanMax` - A column's minimum width is greater than its maximum width.
+    /// * `ColonnadeError::InsufficientSpace` - The columns require more space than the viewport provides.
+    pub fn build(&self) -> Result<Colonnade, ColonnadeError> {
+        let mut colonnade = Colonnade::new(self.columns, usize::MAX)?;
+        colonnade.width = self.width;
+        self.preset.configure(&mut colonnade);
+        for IndexedConfig { index, config } in &self.configs {
+            let column = colonnade
+                .columns
+                .get_mut(*index)
+                .ok_or(ColonnadeError::OutOfBounds(*index, None))?;
+            config.apply(column)?;
         }
-        if !self.sufficient_space() {
-            Err(ColonnadeError::InsufficientSpace)
-        } else {
-            Ok(self)
+        if let Some(names) = &self.headers {
+            if let Some(name) = names.get(self.columns) {
+                return Err(ColonnadeError::OutOfBounds(
+                    self.columns,
+                    Some(name.clone()),
+                ));
+            }
+            for (column, name) in colonnade.columns.iter_mut().zip(names.iter()) {
+                column.name(name);
+            }
+            colonnade.header_row(true);
         }
+        if !colonnade.sufficient_space() {
+            return Err(colonnade.insufficient_space());
+        }
+        Ok(colonnade)
     }
-    /// Assign all columns the same left padding -- space before the column's text.
-    ///
-    /// See [`padding`](#method.padding).
+}
+
+/// A `Colonnade` paired with a function reporting the current width of the viewport. Whenever the
+/// reported width changes between renders, the viewport is resized and the columns laid out afresh.
+/// This is the building block for `watch`-style commands and redraw loops in terminal interfaces.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate colonnade;
+/// # use colonnade::{Colonnade, Responsive};
+/// # use std::cell::Cell;
+/// # use std::error::Error;
+/// # fn demo() -> Result<(), Box<dyn Error>> {
+/// let width = Cell::new(80);
+/// let mut responsive = Responsive::new(Colonnade::new(2, 80)?, || width.get());
+/// let data = [["some text", "some more text"]];
+/// assert_eq!(vec!["some text some more text"], responsive.tabulate(&data)?);
+/// // the terminal shrinks
+/// width.set(12);
+/// assert_eq!(vec!["some   some ", "text   more ", "       text "], responsive.tabulate(&data)?);
+/// # Ok(()) }
+/// ```
+pub struct Responsive<F: FnMut() -> usize> {
+    colonnade: Colonnade,
+    width: F,
+}
+
+impl<F: FnMut() -> usize> Responsive<F> {
+    /// Pair a `Colonnade` with a function reporting the current width of the viewport.
     ///
     /// # Arguments
     ///
-    /// * `padding` - The width in blank spaces/lines of the desired padding.
+    /// * `colonnade` - The `Colonnade` which will tabulate the data.
+    /// * `width` - A function returning the current width of the viewport in characters.
+    pub fn new(colonnade: Colonnade, width: F) -> Self {
+        Responsive { colonnade, width }
+    }
+    // resize the viewport if the reported width has changed
+    fn resize(&mut self) -> Result<(), ColonnadeError> {
+        let width = (self.width)();
+        if width != self.colonnade.width {
+            self.colonnade.viewport(width)?;
+        }
+        Ok(())
+    }
+    /// Like [`Colonnade::tabulate`](struct.Colonnade.html#method.tabulate), but first resizes the
+    /// viewport if its width has changed.
     ///
     /// # Errors
     ///
-    /// * `ColonnadeError::InsufficientSpace` - This padding will require more space than is available in the viewport.
+    /// Any errors of [`Colonnade::viewport`](struct.Colonnade.html#method.viewport) or
+    /// [`Colonnade::tabulate`](struct.Colonnade.html#method.tabulate).
+    pub fn tabulate<T, R>(&mut self, table: T) -> Result<Vec<String>, ColonnadeError>
+    where
+        T: IntoIterator<Item = R>,
+        R: ToRow,
+    {
+        self.resize()?;
+        self.colonnade.tabulate(table)
+    }
+    /// Like [`Colonnade::macerate`](struct.Colonnade.html#method.macerate), but first resizes the
+    /// viewport if its width has changed.
     ///
-    /// # Example
+    /// # Errors
     ///
-    /// ```rust
-    /// # extern crate colonnade;
-    /// # use colonnade::{Alignment,Colonnade};
-    /// # use std::error::Error;
-    /// # fn demo() -> Result<(), Box<dyn Error>> {
-    /// let mut colonnade = Colonnade::new(4, 100)?;
-    /// colonnade.padding_left(1)?;
-    /// # Ok(()) }
-    /// ```
-    pub fn padding_left(&mut self, padding: usize) -> Result<&mut Self, ColonnadeError> {
-        for i in 0..self.len() {
-            self.columns[i].padding_left(padding);
-        }
-        if !self.sufficient_space() {
-            Err(ColonnadeError::InsufficientSpace)
-        } else {
-            Ok(self)
-        }
+    /// Any errors of [`Colonnade::viewport`](struct.Colonnade.html#method.viewport) or
+    /// [`Colonnade::macerate`](struct.Colonnade.html#method.macerate).
+    pub fn macerate<T, R>(&mut self, table: T) -> Result<Maceration, ColonnadeError>
+    where
+        T: IntoIterator<Item = R>,
+        R: ToRow,
+    {
+        self.resize()?;
+        self.colonnade.macerate(table)
     }
-    /// Assign all columns the same right padding -- space after the column's text.
+    /// The `Colonnade`, for further configuration.
+    pub fn colonnade(&mut self) -> &mut Colonnade {
+        &mut self.colonnade
+    }
+    /// Discard the width function, returning the `Colonnade`.
+    pub fn into_inner(self) -> Colonnade {
+        self.colonnade
+    }
+}
+
+/// Two `Colonnade`s rendered next to each other in one viewport, for before and after or left
+/// and right comparisons. The viewport, less the gap between the tables, is split evenly between
+/// them. The rows of the two tables are aligned: each pair of rows begins on the same line, the
+/// shorter padded with blank lines.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate colonnade;
+/// # use colonnade::{Colonnade, SideBySide};
+/// # use std::error::Error;
+/// # fn demo() -> Result<(), Box<dyn Error>> {
+/// let mut pair = SideBySide::new(Colonnade::new(2, 80)?, Colonnade::new(2, 80)?);
+/// pair.gap(3);
+/// let before = [["a", "one two three"], ["b", "four"]];
+/// let after = [["a", "one"], ["b", "four five"]];
+/// assert_eq!(
+///     vec![
+///         "a   one      a   one   ",
+///         "    two      ",
+///         "    three    ",
+///         "b   four     b   four  ",
+///         "                 five  ",
+///     ],
+///     pair.tabulate(before, after, 23)?
+/// );
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SideBySide {
+    left: Colonnade,
+    right: Colonnade,
+    gap: usize,
+}
+
+impl SideBySide {
+    /// Pair two `Colonnade`s with a gap of 2 between them.
     ///
-    /// See [`padding`](#method.padding).
+    /// # Arguments
+    ///
+    /// * `left` - The `Colonnade` for the table on the left.
+    /// * `right` - The `Colonnade` for the table on the right.
+    pub fn new(left: Colonnade, right: Colonnade) -> Self {
+        SideBySide {
+            left,
+            right,
+            gap: 2,
+        }
+    }
+    /// Set the number of spaces between the tables.
     ///
     /// # Arguments
     ///
-    /// * `padding` - The width in blank spaces/lines of the desired padding.
+    /// * `gap` - The width of the gap.
+    pub fn gap(&mut self, gap: usize) -> &mut Self {
+        self.gap = gap;
+        self
+    }
+    /// The `Colonnade` on the left, for further configuration.
+    pub fn left(&mut self) -> &mut Colonnade {
+        &mut self.left
+    }
+    /// The `Colonnade` on the right, for further configuration.
+    pub fn right(&mut self) -> &mut Colonnade {
+        &mut self.right
+    }
+    /// Render the two tables next to each other.
     ///
-    /// # Errors
+    /// # Arguments
     ///
-    /// * `ColonnadeError::InsufficientSpace` - This padding will require more space than is available in the viewport.
+    /// * `left` - The data of the table on the left.
+    /// * `right` - The data of the table on the right.
+    /// * `width` - Viewport size in characters
     ///
-    /// # Example
+    /// # Errors
     ///
-    /// ```rust
-    /// # extern crate colonnade;
-    /// # use colonnade::{Alignment,Colonnade};
-    /// # use std::error::Error;
-    /// # fn demo() -> Result<(), Box<dyn Error>> {
-    /// let mut colonnade = Colonnade::new(4, 100)?;
-    /// colonnade.padding_right(1)?;
-    /// # Ok(()) }
-    /// ```
-    pub fn padding_right(&mut self, padding: usize) -> Result<&mut Self, ColonnadeError> {
-        for i in 0..self.len() {
-            self.columns[i].padding_right(padding);
+    /// Any errors of [`Colonnade::viewport`](struct.Colonnade.html#method.viewport) or
+    /// [`Colonnade::macerate`](struct.Colonnade.html#method.macerate) for either table.
+    pub fn tabulate<T, R, U, S>(
+        &mut self,
+        left: T,
+        right: U,
+        width: usize,
+    ) -> Result<Vec<String>, ColonnadeError>
+    where
+        T: IntoIterator<Item = R>,
+        R: ToRow,
+        U: IntoIterator<Item = S>,
+        S: ToRow,
+    {
+        let available = width.saturating_sub(self.gap);
+        self.left.viewport(available / 2)?;
+        self.right.viewport(available - available / 2)?;
+        let left = self.left.macerate(left)?;
+        let right = self.right.macerate(right)?;
+        let left_width = self.left.maceration_width(&left);
+        let gap = " ".repeat(self.gap);
+        let mut lines = vec![];
+        for i in 0..left.len().max(right.len()) {
+            let row = |colonnade: &Colonnade, maceration: &Maceration| match maceration.get(i) {
+                Some(row) => colonnade.lines(vec![row.clone()]),
+                None => vec![],
+            };
+            let (l, r) = (row(&self.left, &left), row(&self.right, &right));
+            for j in 0..l.len().max(r.len()) {
+                let l = l.get(j).map(String::as_str).unwrap_or("");
+                let r = r.get(j).map(String::as_str).unwrap_or("");
+                let padding =
+                    " ".repeat(left_width.saturating_sub(true_width(l, &self.left.widths)));
+                lines.push(format!("{}{}{}{}", l, padding, gap, r));
+            }
         }
-        if !self.sufficient_space() {
-            Err(ColonnadeError::InsufficientSpace)
-        } else {
-            Ok(self)
+        Ok(lines)
+    }
+}
+
+/// Delimited text aligned in the manner of elastic tabstops, like a `column -t` which wraps. The
+/// text is divided into blocks of consecutive lines containing the delimiter, each block laid out
+/// as a table with as many columns as its longest line has fields. Lines without the delimiter are
+/// passed through unchanged and end the block above them. Trailing whitespace is trimmed.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate colonnade;
+/// # use colonnade::ElasticTabstops;
+/// # use std::error::Error;
+/// # fn demo() -> Result<(), Box<dyn Error>> {
+/// let text = "name\tsize\nfoo\t3\nlong name\t1024\n\nx\ty\tz";
+/// assert_eq!(
+///     vec!["name      size", "foo       3", "long name 1024", "", "x y z"],
+///     ElasticTabstops::new().tabulate(text, 80)?
+/// );
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ElasticTabstops {
+    delimiter: char,
+    gap: usize,
+}
+
+impl Default for ElasticTabstops {
+    fn default() -> Self {
+        ElasticTabstops {
+            delimiter: '\t',
+            gap: 1,
         }
     }
-    /// Assign all columns the same vertical padding -- blank lines before and after the column's text.
-    ///
-    /// See [`padding`](#method.padding).
+}
+
+impl ElasticTabstops {
+    /// Align text delimited by tabs with a gap of 1 between columns.
+    pub fn new() -> Self {
+        ElasticTabstops::default()
+    }
+    /// Set the character separating fields.
     ///
     /// # Arguments
     ///
-    /// * `padding` - The width in blank spaces/lines of the desired padding.
+    /// * `delimiter` - The field delimiter, by default a tab.
+    pub fn delimiter(&mut self, delimiter: char) -> &mut Self {
+        self.delimiter = delimiter;
+        self
+    }
+    /// Set the number of spaces between columns.
     ///
-    /// # Example
+    /// # Arguments
     ///
-    /// ```rust
-    /// # extern crate colonnade;
-    /// # use colonnade::{Alignment,Colonnade};
-    /// # use std::error::Error;
-    /// # fn demo() -> Result<(), Box<dyn Error>> {
-    /// let mut colonnade = Colonnade::new(4, 100)?;
-    /// colonnade.padding_vertical(1);
-    /// # Ok(()) }
-    /// ```
-    pub fn padding_vertical(&mut self, padding: usize) -> &mut Self {
-        for i in 0..self.len() {
-            self.columns[i].padding_vertical(padding);
-        }
+    /// * `gap` - The width of the gaps between columns.
+    pub fn gap(&mut self, gap: usize) -> &mut Self {
+        self.gap = gap;
         self
     }
-    /// Assign all columns the same top padding -- blank lines before the column's text.
-    ///
-    /// See [`padding`](#method.padding).
+    /// Align the text.
     ///
     /// # Arguments
     ///
-    /// * `padding` - The width in blank spaces/lines of the desired padding.
+    /// * `text` - The delimited text.
+    /// * `width` - Viewport size in characters
     ///
-    /// # Example
+    /// # Errors
     ///
-    /// ```rust
-    /// # extern crate colonnade;
-    /// # use colonnade::{Alignment,Colonnade};
-    /// # use std::error::Error;
-    /// # fn demo() -> Result<(), Box<dyn Error>> {
-    /// let mut colonnade = Colonnade::new(4, 100)?;
-    /// colonnade.padding_top(1);
-    /// # Ok(()) }
-    /// ```
-    pub fn padding_top(&mut self, padding: usize) -> &mut Self {
-        for i in 0..self.len() {
-            self.columns[i].padding_top(padding);
+    /// * `ColonnadeError::InsufficientSpace` - Some block has more columns than fit in the viewport.
+    pub fn tabulate(&self, text: &str, width: usize) -> Result<Vec<String>, ColonnadeError> {
+        let mut lines = vec![];
+        let mut block: Vec<Vec<&str>> = vec![];
+        for line in text.lines() {
+            if line.contains(self.delimiter) {
+                block.push(line.split(self.delimiter).collect());
+            } else {
+                lines.extend(self.align(&block, width)?);
+                block.clear();
+                lines.push(line.trim_end().to_string());
+            }
         }
-        self
+        lines.extend(self.align(&block, width)?);
+        Ok(lines)
     }
-    /// Assign all columns the same bottom padding -- blank lines after the column's text.
-    ///
-    /// See [`padding`](#method.padding).
+    // lay out a block of lines as a table
+    fn align(&self, block: &[Vec<&str>], width: usize) -> Result<Vec<String>, ColonnadeError> {
+        let columns = block.iter().map(Vec::len).max().unwrap_or(0);
+        if columns == 0 {
+            return Ok(vec![]);
+        }
+        let mut colonnade = Colonnade::new(columns, width)?;
+        colonnade
+            .spaces_between_columns(self.gap)?
+            .trim_trailing(true);
+        colonnade.tabulate(block)
+    }
+}
+
+/// Split delimited text into rows of fields, one row per line. When `quoted` is true, fields may
+/// be enclosed in double quotes, as in CSV, to include the delimiter, line breaks, or, doubled,
+/// double quotes themselves.
+///
+/// See [`Colonnade::tabulate_text`](struct.Colonnade.html#method.tabulate_text).
+///
+/// # Arguments
+///
+/// * `input` - The delimited text.
+/// * `delimiter` - The character separating fields.
+/// * `quoted` - Whether double quotes enclose fields.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate colonnade;
+/// # use colonnade::split_delimited;
+/// let rows = split_delimited("a,\"b, c\"\nd,e", ',', true);
+/// assert_eq!(vec![vec!["a", "b, c"], vec!["d", "e"]], rows);
+/// ```
+pub fn split_delimited(input: &str, delimiter: char, quoted: bool) -> Vec<Vec<String>> {
+    let mut rows = vec![];
+    let mut row = vec![];
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes => {
+                if chars.peek() == Some(&'"') {
+                    field.push('"');
+                    chars.next();
+                } else {
+                    in_quotes = false;
+                }
+            }
+            '"' if quoted && field.is_empty() => in_quotes = true,
+            c if c == delimiter && !in_quotes => row.push(std::mem::take(&mut field)),
+            '\r' if !in_quotes && chars.peek() == Some(&'\n') => (),
+            '\n' if !in_quotes => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            c => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows
+}
+
+/// Recover the cells of a table from whitespace-aligned text, such as the output of
+/// [`Colonnade::tabulate`](struct.Colonnade.html#method.tabulate) or of another tool, so it can be
+/// laid out again at a different width.
+///
+/// Column boundaries are runs of at least `min_gap` positions blank in every line. A gap narrower
+/// than `min_gap` is taken to be a space within a column, so when columns are separated by wider
+/// gaps than the words within them, a larger `min_gap` keeps multi-word cells whole. Blank lines
+/// are skipped. A line whose first column is blank is taken to continue the row above, as when a
+/// cell has been wrapped, and its text is appended to the cells of that row.
+///
+/// # Arguments
+///
+/// * `lines` - The aligned text, one line per item.
+/// * `min_gap` - The narrowest run of blank positions taken as a column boundary.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate colonnade;
+/// # use colonnade::{parse_aligned, Colonnade};
+/// # use std::error::Error;
+/// # fn demo() -> Result<(), Box<dyn Error>> {
+/// let lines = vec!["name         size", "foo             3", "a long name  1024"];
+/// let rows = parse_aligned(&lines, 2);
+/// assert_eq!(vec!["a long name", "1024"], rows[2]);
+/// let mut colonnade = Colonnade::new(2, 10)?;
+/// assert_eq!(
+///     vec!["name  size", "foo   3   ", "a     1024", "long      ", "name      "],
+///     colonnade.tabulate(&rows)?
+/// );
+/// # Ok(()) }
+/// ```
+pub fn parse_aligned<S: AsRef<str>>(lines: &[S], min_gap: usize) -> Vec<Vec<String>> {
+    let lines: Vec<Vec<&str>> = lines
+        .iter()
+        .map(|l| UnicodeSegmentation::graphemes(l.as_ref(), true).collect())
+        .filter(|l: &Vec<&str>| l.iter().any(|g| !g.trim().is_empty()))
+        .collect();
+    // the positions where some line has text
+    let mut occupied = vec![false; lines.iter().map(Vec::len).max().unwrap_or(0)];
+    for line in &lines {
+        for (i, g) in line.iter().enumerate() {
+            if !g.trim().is_empty() {
+                occupied[i] = true;
+            }
+        }
+    }
+    // the spans of the columns, absorbing gaps narrower than the minimum
+    let mut spans: Vec<(usize, usize)> = vec![];
+    let mut i = 0;
+    while i < occupied.len() {
+        if !occupied[i] {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < occupied.len() && occupied[i] {
+            i += 1;
+        }
+        let gap = occupied[i..].iter().take_while(|&&o| !o).count();
+        match spans.last_mut() {
+            Some(span) if start - span.1 < min_gap.max(1) => span.1 = i,
+            _ => spans.push((start, i)),
+        }
+        i += gap;
+    }
+    let mut rows: Vec<Vec<String>> = vec![];
+    for line in &lines {
+        let cells: Vec<String> = spans
+            .iter()
+            .map(|&(start, end)| {
+                let end = end.min(line.len());
+                line.get(start..end)
+                    .unwrap_or(&[])
+                    .concat()
+                    .trim()
+                    .to_string()
+            })
+            .collect();
+        match rows.last_mut() {
+            Some(row) if spans.len() > 1 && cells[0].is_empty() => {
+                for (cell, text) in row.iter_mut().zip(cells) {
+                    if !text.is_empty() {
+                        if !cell.is_empty() {
+                            cell.push(' ');
+                        }
+                        cell.push_str(&text);
+                    }
+                }
+            }
+            _ => rows.push(cells),
+        }
+    }
+    rows
+}
+
+/// A `serde_json::Value` array of objects prepared for tabulation. The union of the objects' keys,
+/// in order of first appearance, provides the columns and their headers. Missing fields and nulls
+/// are rendered as a placeholder, by default the empty string. Strings are displayed without
+/// quotes, numbers are right-aligned, and nested arrays and objects are displayed as compact JSON.
+///
+/// This type requires the `json` feature.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate colonnade;
+/// # extern crate serde_json;
+/// # use colonnade::JsonTable;
+/// # use std::error::Error;
+/// # fn demo() -> Result<(), Box<dyn Error>> {
+/// let value = serde_json::json!([
+///     { "name": "apple", "count": 3 },
+///     { "name": "kumquat", "color": "orange" },
+/// ]);
+/// let lines = JsonTable::new(&value)?.placeholder("-").tabulate(80)?;
+/// assert_eq!(
+///     vec![
+///         "count name    color ",
+///         "    3 apple   -     ",
+///         "-     kumquat orange",
+///     ],
+///     lines
+/// );
+/// # Ok(()) }
+/// ```
+#[cfg(feature = "json")]
+#[derive(Debug, Clone)]
+pub struct JsonTable {
+    headers: Vec<String>,
+    rows: Vec<Vec<Option<Cell<'static>>>>,
+    placeholder: String,
+}
+
+#[cfg(feature = "json")]
+impl JsonTable {
+    /// Gather the headers and cells of a JSON array of objects.
     ///
     /// # Arguments
     ///
-    /// * `padding` - The width in blank spaces/lines of the desired padding.
+    /// * `value` - A JSON array of objects
     ///
-    /// # Example
+    /// # Errors
     ///
-    /// ```rust
-    /// # extern crate colonnade;
-    /// # use colonnade::{Alignment,Colonnade};
-    /// # use std::error::Error;
-    /// # fn demo() -> Result<(), Box<dyn Error>> {
-    /// let mut colonnade = Colonnade::new(4, 100)?;
-    /// colonnade.padding_bottom(1);
-    /// # Ok(()) }
-    /// ```
-    pub fn padding_bottom(&mut self, padding: usize) -> &mut Self {
-        for i in 0..self.len() {
-            self.columns[i].padding_bottom(padding);
+    /// * `ColonnadeError::Json` - the value is not an array of objects
+    pub fn new(value: &serde_json::Value) -> Result<JsonTable, ColonnadeError> {
+        let objects = value
+            .as_array()
+            .ok_or_else(|| ColonnadeError::Json("expected an array".to_string()))?;
+        let mut headers: Vec<String> = vec![];
+        for (i, object) in objects.iter().enumerate() {
+            let object = object
+                .as_object()
+                .ok_or_else(|| ColonnadeError::Json(format!("element {} is not an object", i)))?;
+            for key in object.keys() {
+                if !headers.contains(key) {
+                    headers.push(key.clone());
+                }
+            }
         }
-        self
+        let rows = objects
+            .iter()
+            .filter_map(|object| object.as_object())
+            .map(|object| {
+                headers
+                    .iter()
+                    .map(|key| object.get(key).and_then(JsonTable::cell))
+                    .collect()
+            })
+            .collect();
+        Ok(JsonTable {
+            headers,
+            rows,
+            placeholder: String::new(),
+        })
     }
-    /// Toggle the hyphenation of all columns.
+    // the cell for a JSON value; None for null
+    fn cell(value: &serde_json::Value) -> Option<Cell<'static>> {
+        match value {
+            serde_json::Value::Null => None,
+            serde_json::Value::String(s) => Some(Cell::new(s)),
+            serde_json::Value::Number(n) => Some(Cell::new(n).numeric(true)),
+            _ => Some(Cell::new(value)),
+        }
+    }
+    /// Set the text displayed for missing fields and nulls.
     ///
-    /// See [`Column::hyphenate`](struct.Column.html#method.hyphenate).
+    /// # Arguments
+    ///
+    /// * `placeholder` - The text to display in place of a missing value
+    pub fn placeholder(&mut self, placeholder: &str) -> &mut Self {
+        self.placeholder = placeholder.to_string();
+        self
+    }
+    /// The column headers: the keys of the objects in order of first appearance.
+    pub fn headers(&self) -> &[String] {
+        &self.headers
+    }
+    /// The rows of cells, with missing values replaced by the placeholder.
+    pub fn rows(&self) -> Vec<Vec<Cell<'static>>> {
+        self.rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| cell.clone().unwrap_or_else(|| Cell::new(&self.placeholder)))
+                    .collect()
+            })
+            .collect()
+    }
+    /// A `Colonnade` with a named column for each header and the header row enabled.
     ///
     /// # Arguments
     ///
-    /// * `hyphenate` - Whether long words will be hyphenated when split.
-    pub fn hyphenate(&mut self, hyphenate: bool) -> &mut Self {
-        for i in 0..self.len() {
-            self.columns[i].hyphenate(hyphenate);
+    /// * `width` - Viewport size in characters
+    ///
+    /// # Errors
+    ///
+    /// * `ColonnadeError::InsufficientColumns` - the objects have no keys
+    /// * `ColonnadeError::InsufficientSpace` - the viewport isn't wide enough for the columns and their margins
+    pub fn colonnade(&self, width: usize) -> Result<Colonnade, ColonnadeError> {
+        let mut colonnade = Colonnade::new(self.headers.len(), width)?;
+        for (c, header) in colonnade.columns.iter_mut().zip(self.headers.iter()) {
+            c.name(header);
         }
-        self
+        colonnade.header_row(true);
+        Ok(colonnade)
+    }
+    /// Tabulate the data with the default [`colonnade`](#method.colonnade).
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - Viewport size in characters
+    ///
+    /// # Errors
+    ///
+    /// Any errors of [`colonnade`](#method.colonnade) or
+    /// [`Colonnade::tabulate`](struct.Colonnade.html#method.tabulate).
+    pub fn tabulate(&self, width: usize) -> Result<Vec<String>, ColonnadeError> {
+        self.colonnade(width)?.tabulate(self.rows())
     }
 }
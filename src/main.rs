@@ -0,0 +1,197 @@
+//! The `colonnade` command: tabulate CSV, TSV, or JSON read from standard input.
+extern crate colonnade;
+use colonnade::{split_delimited, terminal_width, Alignment, Cell, Colonnade, Separator};
+use std::io::{self, Read, Write};
+use std::process;
+
+const USAGE: &str = "\
+usage: colonnade [options] < data
+
+Tabulate CSV, TSV, or JSON (an array of objects) read from standard input.
+
+options:
+  -f, --format FORMAT   csv, tsv, or json; by default guessed from the input
+  -w, --width N         the width of the viewport; by default $COLUMNS or 80
+  -a, --align ALIGN     left, right, center, or justify, for all columns, or
+                        a comma-separated list with one alignment per column
+  -H, --header          treat the first row of CSV or TSV as column headers
+  -g, --gap N           the number of spaces between columns
+  -r, --rule TEXT       separate rows with lines of TEXT repeated
+  -h, --help            show this message";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Csv,
+    Tsv,
+    Json,
+}
+
+#[derive(Debug, Default)]
+struct Options {
+    format: Option<Format>,
+    width: Option<usize>,
+    alignments: Vec<Alignment>,
+    header: bool,
+    gap: Option<usize>,
+    rule: Option<String>,
+}
+
+fn parse_args<I: Iterator<Item = String>>(mut args: I) -> Result<Options, String> {
+    let mut options = Options::default();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| format!("{} requires a value", name))
+        };
+        match arg.as_str() {
+            "-f" | "--format" => {
+                options.format = Some(match value(&arg)?.as_str() {
+                    "csv" => Format::Csv,
+                    "tsv" => Format::Tsv,
+                    "json" => Format::Json,
+                    other => return Err(format!("unknown format: {}", other)),
+                })
+            }
+            "-w" | "--width" => options.width = Some(number(&arg, &value(&arg)?)?),
+            "-a" | "--align" => {
+                options.alignments = value(&arg)?
+                    .split(',')
+                    .map(alignment)
+                    .collect::<Result<_, _>>()?
+            }
+            "-H" | "--header" => options.header = true,
+            "-g" | "--gap" => options.gap = Some(number(&arg, &value(&arg)?)?),
+            "-r" | "--rule" => options.rule = Some(value(&arg)?),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                process::exit(0);
+            }
+            other => return Err(format!("unknown option: {}\n\n{}", other, USAGE)),
+        }
+    }
+    Ok(options)
+}
+
+fn number(name: &str, value: &str) -> Result<usize, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{} requires a number, not {}", name, value))
+}
+
+fn alignment(name: &str) -> Result<Alignment, String> {
+    match name.trim() {
+        "left" => Ok(Alignment::Left),
+        "right" => Ok(Alignment::Right),
+        "center" => Ok(Alignment::Center),
+        "justify" => Ok(Alignment::Justify),
+        other => Err(format!("unknown alignment: {}", other)),
+    }
+}
+
+// JSON begins with a bracket, and tabs make TSV more likely than CSV
+fn guess_format(input: &str) -> Format {
+    match input.trim_start().chars().next() {
+        Some('[') => Format::Json,
+        _ if input.lines().next().is_some_and(|l| l.contains('\t')) => Format::Tsv,
+        _ => Format::Csv,
+    }
+}
+
+// the column headers, if any, and the rows of cells
+type Table = (Option<Vec<String>>, Vec<Vec<Cell<'static>>>);
+
+fn read_table(input: &str, format: Format, header: bool) -> Result<Table, String> {
+    let delimiter = match format {
+        Format::Csv => ',',
+        Format::Tsv => '\t',
+        Format::Json => return read_json(input),
+    };
+    let mut rows = split_delimited(input, delimiter, true);
+    let headers = if header && !rows.is_empty() {
+        Some(rows.remove(0))
+    } else {
+        None
+    };
+    let rows = rows
+        .into_iter()
+        .map(|row| row.into_iter().map(Cell::from).collect())
+        .collect();
+    Ok((headers, rows))
+}
+
+#[cfg(feature = "json")]
+fn read_json(input: &str) -> Result<Table, String> {
+    let value: serde_json::Value = serde_json::from_str(input).map_err(|e| e.to_string())?;
+    let table = colonnade::JsonTable::new(&value).map_err(|e| e.to_string())?;
+    Ok((Some(table.headers().to_vec()), table.rows()))
+}
+
+#[cfg(not(feature = "json"))]
+fn read_json(_input: &str) -> Result<Table, String> {
+    Err("JSON input requires the json feature".to_string())
+}
+
+fn run() -> Result<(), String> {
+    let options = parse_args(std::env::args().skip(1))?;
+    let mut input = String::new();
+    io::stdin()
+        .read_to_string(&mut input)
+        .map_err(|e| e.to_string())?;
+    let format = options.format.unwrap_or_else(|| guess_format(&input));
+    let (headers, rows) = read_table(&input, format, options.header)?;
+    let columns = rows
+        .iter()
+        .map(Vec::len)
+        .chain(headers.iter().map(Vec::len))
+        .max()
+        .unwrap_or(0);
+    if columns == 0 {
+        return Ok(());
+    }
+    let mut colonnade =
+        Colonnade::new(columns, terminal_width(options.width, 80)).map_err(|e| e.to_string())?;
+    if let Some(gap) = options.gap {
+        colonnade
+            .spaces_between_columns(gap)
+            .map_err(|e| e.to_string())?;
+    }
+    match options.alignments.as_slice() {
+        [] => (),
+        [alignment] => {
+            colonnade.alignment(alignment.clone());
+        }
+        alignments => {
+            for (column, alignment) in colonnade.columns.iter_mut().zip(alignments) {
+                column.alignment(alignment.clone());
+            }
+        }
+    }
+    if let Some(names) = headers {
+        for (column, name) in colonnade.columns.iter_mut().zip(names.iter()) {
+            column.name(name);
+        }
+        colonnade.header_row(true);
+    }
+    if let Some(rule) = options.rule {
+        colonnade
+            .spaces_between_rows(1)
+            .separator(Separator::Fill(rule));
+    }
+    let lines = colonnade.tabulate(&rows).map_err(|e| e.to_string())?;
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    for line in lines {
+        if writeln!(out, "{}", line).is_err() {
+            // the reader has gone away, as when piping to head
+            break;
+        }
+    }
+    Ok(())
+}
+
+fn main() {
+    if let Err(message) = run() {
+        eprintln!("colonnade: {}", message);
+        process::exit(1);
+    }
+}
@@ -1,5 +1,15 @@
+// the oldest tests predate these lints
+#![allow(
+    clippy::assertions_on_constants,
+    clippy::needless_borrows_for_generic_args,
+    clippy::needless_range_loop
+)]
 extern crate colonnade;
-use colonnade::{Alignment, Colonnade, VerticalAlignment};
+use colonnade::{
+    Alignment, BarChart, Colonnade, ColonnadeError, ColumnSpace, Degradation, ExpandedDisplay,
+    Humanize, NumberFormat, OverflowPolicy, Percentage, RaggedRows, VerticalAlignment,
+};
+use proptest::prelude::*;
 
 #[test]
 fn minimal_table() {
@@ -350,6 +360,35 @@ fn variable_length_rows() {
     assert_eq!("2 3", lines[1]);
 }
 
+#[test]
+fn ragged_rows() {
+    let data = [vec!["a"], vec!["b", "c"], vec!["d", "e", "f"]];
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    match colonnade.tabulate(&data) {
+        Err(ColonnadeError::InconsistentColumns(2, 3, 2)) => (),
+        result => panic!("expected long row error, got {:?}", result),
+    }
+    colonnade.ragged_rows(RaggedRows::Error);
+    match colonnade.tabulate(&data) {
+        Err(ColonnadeError::InconsistentColumns(0, 1, 2)) => (),
+        result => panic!("expected short row error, got {:?}", result),
+    }
+    colonnade.ragged_rows(RaggedRows::TruncateLong);
+    match colonnade.tabulate(&data) {
+        Err(ColonnadeError::InconsistentColumns(0, 1, 2)) => (),
+        result => panic!("expected short row error, got {:?}", result),
+    }
+    assert_eq!(vec!["b c", "d e"], colonnade.tabulate(&data[1..]).unwrap());
+    colonnade.ragged_rows(RaggedRows::PadOrTruncate);
+    assert_eq!(
+        vec!["a  ", "b c", "d e"],
+        colonnade.tabulate(&data).unwrap()
+    );
+    // the policy applies equally once the columns have been laid out
+    colonnade.ragged_rows(RaggedRows::Error);
+    assert!(colonnade.tabulate(&data[2..]).is_err());
+}
+
 #[test]
 fn reset() {
     let mut colonnade = Colonnade::new(3, 80).unwrap();
@@ -418,4 +457,2705 @@ fn nbsp() {
     assert_eq!(2, lines.len());
     assert_eq!("foo bar   ", lines[0]);
     assert_eq!("baz \u{00A0}plugh", lines[1]);
-}
\ No newline at end of file
+}
+#[test]
+fn fill_viewport() {
+    let mut colonnade = Colonnade::new(3, 11).unwrap();
+    colonnade.fill_viewport(true);
+    let lines = colonnade.tabulate([[1, 2, 3]]).unwrap();
+    assert_eq!(1, lines.len());
+    assert_eq!("1   2   3  ", lines[0]);
+}
+
+#[test]
+fn fill_viewport_respects_max_width() {
+    let mut colonnade = Colonnade::new(2, 20).unwrap();
+    colonnade.fill_viewport(true);
+    colonnade.columns[0].max_width(3).unwrap();
+    let lines = colonnade.tabulate([["a", "b"]]).unwrap();
+    assert_eq!("a   b", &lines[0][0..5]);
+    assert_eq!(20, lines[0].len());
+}
+
+#[test]
+fn degradation_error_by_default() {
+    let mut colonnade = Colonnade::new(3, 10).unwrap();
+    colonnade.min_width(4).unwrap_err();
+    assert!(colonnade.tabulate([["foo", "bar", "baz"]]).is_err());
+}
+
+#[test]
+fn degradation_drop_columns() {
+    let mut colonnade = Colonnade::new(3, 10).unwrap();
+    colonnade.degradation(Degradation::DropColumns);
+    colonnade.min_width(4).unwrap_err();
+    colonnade.priority(0);
+    colonnade.columns[1].priority(1);
+    let lines = colonnade.tabulate([["foo", "bar", "baz"]]).unwrap();
+    assert_eq!(vec!["foo  baz "], lines);
+    assert_eq!(vec![1], colonnade.dropped_columns());
+}
+
+#[test]
+fn degradation_drop_first_column() {
+    let mut colonnade = Colonnade::new(2, 5).unwrap();
+    colonnade.degradation(Degradation::DropColumnsWithNote);
+    colonnade.min_width(4).unwrap_err();
+    colonnade.columns[0].priority(1);
+    colonnade.columns[1].priority(0);
+    let lines = colonnade.tabulate([["foo", "bar"]]).unwrap();
+    assert_eq!(vec!["bar ", "+1 more"], lines);
+}
+
+#[test]
+fn overflow_wrap() {
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    colonnade.tabulate([["a", "b"]]).unwrap();
+    assert!(colonnade.overflows().is_empty());
+    let lines = colonnade.tabulate([["cc", "d"]]).unwrap();
+    assert_eq!(vec!["c d", "c  "], lines);
+    assert_eq!(vec![(0, 0)], colonnade.overflows());
+    colonnade.reset();
+    colonnade.tabulate([["cc", "d"]]).unwrap();
+    assert!(colonnade.overflows().is_empty());
+}
+
+#[test]
+fn overflow_truncate() {
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    colonnade.overflow_policy(OverflowPolicy::Truncate);
+    colonnade.tabulate([["abc", "b"]]).unwrap();
+    let lines = colonnade.tabulate([["a bcd", "d"]]).unwrap();
+    assert_eq!(vec!["a … d"], lines);
+}
+
+#[test]
+fn overflow_relayout() {
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    colonnade.overflow_policy(OverflowPolicy::Relayout);
+    colonnade.tabulate([["a", "b"]]).unwrap();
+    let lines = colonnade.tabulate([["cc", "d"]]).unwrap();
+    assert_eq!(vec!["cc d"], lines);
+    // narrower data does not trigger a fresh layout
+    let lines = colonnade.tabulate([["e", "f"]]).unwrap();
+    assert_eq!(vec!["e  f"], lines);
+}
+
+#[test]
+fn overflow_error() {
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    colonnade.overflow_policy(OverflowPolicy::Error);
+    colonnade.tabulate([["a", "b"]]).unwrap();
+    match colonnade.tabulate([["c", "d"], ["e", "ff"]]) {
+        Err(ColonnadeError::Overflow(1, 1)) => (),
+        result => panic!("expected overflow error, got {:?}", result),
+    }
+}
+
+#[test]
+fn collapse_below() {
+    let mut colonnade = Colonnade::new(3, 40).unwrap();
+    colonnade.columns[0].collapse_below(50);
+    colonnade.columns[1].left_margin(3);
+    let data = [["a", "b", "c"]];
+    assert_eq!(vec!["b c"], colonnade.tabulate(data).unwrap());
+    colonnade.viewport(50).unwrap();
+    assert_eq!(vec!["a   b c"], colonnade.tabulate(data).unwrap());
+    colonnade.viewport(49).unwrap();
+    assert_eq!(vec!["b c"], colonnade.tabulate(data).unwrap());
+}
+
+#[test]
+fn expanded_display_auto() {
+    let mut colonnade = Colonnade::new(3, 12).unwrap();
+    colonnade.expanded_display(ExpandedDisplay::Auto);
+    colonnade.columns[0].name("name");
+    colonnade.columns[1].name("size");
+    let lines = colonnade.tabulate([["a", "b", "c"]]).unwrap();
+    assert_eq!(vec!["a b c"], lines);
+    colonnade.reset();
+    let lines = colonnade
+        .tabulate([["colonnade", "1024", "text"], ["ab", "1", "c"]])
+        .unwrap();
+    assert_eq!(
+        vec![
+            "name: colon-",
+            "      nade  ",
+            "size: 1024  ",
+            "   2: text  ",
+            "",
+            "name: ab    ",
+            "size: 1     ",
+            "   2: c     ",
+        ],
+        lines
+    );
+}
+
+#[test]
+fn expanded_display_insufficient_space() {
+    let mut colonnade = Colonnade::new(3, 20).unwrap();
+    colonnade.expanded_display(ExpandedDisplay::Auto);
+    colonnade.min_width(8).unwrap_err();
+    let lines = colonnade.tabulate([["a", "b", "c"]]).unwrap();
+    assert_eq!(vec!["0: a", "1: b", "2: c"], lines);
+}
+
+#[test]
+fn regression2() {
+    let mut colonnade = Colonnade::new(3, 12).unwrap();
+    let lines = colonnade.tabulate([["colonnade", "1024", "text"]]).unwrap();
+    assert!(lines.iter().all(|l| l.len() <= 12), "no overflow");
+}
+
+#[test]
+fn hidden() {
+    let mut colonnade = Colonnade::new(3, 80).unwrap();
+    colonnade.columns[0].hidden(true);
+    colonnade.columns[2].alignment(Alignment::Right);
+    let data = vec![vec!["a", "b", "c"], vec!["dd", "ee", "ff"]];
+    assert_eq!(vec!["b   c", "ee ff"], colonnade.tabulate(&data).unwrap());
+    colonnade.columns[0].hidden(false);
+    assert_eq!(
+        vec!["a  b   c", "dd ee ff"],
+        colonnade.tabulate(&data).unwrap()
+    );
+}
+
+#[test]
+fn max_rows() {
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    colonnade.max_rows(2);
+    let data = (0..5000)
+        .map(|i| vec![format!("row {}", i), "some text".to_string()])
+        .collect::<Vec<_>>();
+    let lines = colonnade.tabulate(&data).unwrap();
+    assert_eq!(
+        vec![
+            "row 0 some text",
+            "row 1 some text",
+            "… and 4,998",
+            "more rows"
+        ],
+        lines
+    );
+    let lines = colonnade.tabulate(&data[0..3]).unwrap();
+    assert_eq!(vec!["… and 1 more", "row"], lines[2..].to_vec());
+    let lines = colonnade.tabulate(&data[0..2]).unwrap();
+    assert_eq!(2, lines.len());
+}
+
+#[test]
+fn omission_marker() {
+    let mut colonnade = Colonnade::new(3, 13).unwrap();
+    colonnade.omission_marker(true);
+    colonnade.degradation(Degradation::DropColumns);
+    colonnade.min_width(4).unwrap_err();
+    let lines = colonnade.tabulate([["a", "b", "c"]]).unwrap();
+    assert_eq!(vec!["a    b    …+1"], lines);
+    assert_eq!(Some(13), colonnade.width());
+    colonnade.viewport(12).unwrap_err();
+    let lines = colonnade.tabulate([["a", "b", "c"]]).unwrap();
+    assert_eq!(vec!["a    …+2"], lines);
+}
+
+#[test]
+fn degradation_drops_rightmost_first() {
+    let mut colonnade = Colonnade::new(3, 10).unwrap();
+    colonnade.degradation(Degradation::DropColumnsWithNote);
+    colonnade.min_width(4).unwrap_err();
+    let lines = colonnade.tabulate([["foo", "bar", "baz"]]).unwrap();
+    assert_eq!(vec!["foo  bar ", "+1 more"], lines);
+}
+
+#[test]
+fn tabulate_window() {
+    let mut colonnade = Colonnade::new(2, 8).unwrap();
+    colonnade.spaces_between_rows(1).max_rows(3);
+    let data = vec![
+        vec!["a", "b c d e"],
+        vec!["f", "g"],
+        vec!["h", "i"],
+        vec!["j", "k"],
+    ];
+    let all = colonnade.tabulate(&data).unwrap();
+    for start in 0..all.len() + 1 {
+        for count in 0..4 {
+            let window = colonnade.tabulate_window(&data, start, count).unwrap();
+            let end = (start + count).min(all.len());
+            assert_eq!(all[start..end].to_vec(), window);
+        }
+    }
+}
+
+#[test]
+fn insufficient_space_diagnostics() {
+    let mut colonnade = Colonnade::new(3, 20).unwrap();
+    colonnade.columns[0].min_width(8).unwrap();
+    colonnade.columns[1].padding(1);
+    colonnade.columns[2].left_margin(3);
+    match colonnade.viewport(10) {
+        Err(ColonnadeError::InsufficientSpace(diagnostics)) => {
+            assert_eq!(10, diagnostics.viewport);
+            assert_eq!(15, diagnostics.required);
+            assert_eq!(
+                vec![
+                    ColumnSpace {
+                        index: 0,
+                        name: None,
+                        margin: 0,
+                        padding: 0,
+                        minimum: 8
+                    },
+                    ColumnSpace {
+                        index: 1,
+                        name: None,
+                        margin: 1,
+                        padding: 2,
+                        minimum: 2
+                    },
+                    ColumnSpace {
+                        index: 2,
+                        name: None,
+                        margin: 3,
+                        padding: 0,
+                        minimum: 1
+                    },
+                ],
+                diagnostics.columns
+            );
+            assert_eq!(
+                "InsufficientSpace: need at least 15 columns, have 10; the widest is column 0 at 8",
+                ColonnadeError::InsufficientSpace(diagnostics).to_string()
+            );
+        }
+        result => panic!("expected insufficient space, got {:?}", result.err()),
+    }
+    let data = [["a", "b", "c"]];
+    assert!(colonnade.tabulate(data).is_err());
+    colonnade.viewport(15).unwrap();
+    assert!(colonnade.tabulate(data).is_ok());
+}
+
+#[test]
+fn strict() {
+    let mut colonnade = Colonnade::new(2, usize::MAX).unwrap();
+    colonnade.strict(true);
+    colonnade.columns[1].left_margin(usize::MAX);
+    match colonnade.tabulate([["a", "b"]]) {
+        Err(ColonnadeError::Arithmetic(_)) => (),
+        result => panic!("expected arithmetic error, got {:?}", result),
+    }
+    // a maximum width which leaves no room for text is an error rather than an endless loop
+    let mut colonnade = Colonnade::new(1, 10).unwrap();
+    colonnade
+        .strict(true)
+        .padding_left(2)
+        .unwrap()
+        .max_width(2)
+        .unwrap();
+    match colonnade.tabulate([["a"]]) {
+        Err(ColonnadeError::MinGreaterThanMax(0, None)) => (),
+        result => panic!("expected conflicting widths, got {:?}", result),
+    }
+}
+
+fn column_spec() -> impl Strategy<Value = (usize, usize, usize, Option<usize>, u8, bool)> {
+    (
+        0..4usize,
+        0..3usize,
+        0..3usize,
+        proptest::option::of(0..12usize),
+        0..12u8,
+        any::<bool>(),
+    )
+}
+
+proptest! {
+    #[test]
+    fn layout_never_panics(
+        specs in proptest::collection::vec(column_spec(), 1..4),
+        viewport in 0..40usize,
+        strict in any::<bool>(),
+        tables in proptest::collection::vec(
+            proptest::collection::vec(proptest::collection::vec("[a-z é漢]{0,12}", 0..4), 0..4),
+            2
+        ),
+    ) {
+        let mut colonnade = match Colonnade::new(specs.len(), viewport) {
+            Ok(colonnade) => colonnade,
+            Err(_) => return Ok(()),
+        };
+        colonnade
+            .strict(strict)
+            .ragged_rows(RaggedRows::PadOrTruncate)
+            .degradation(Degradation::DropColumnsWithNote)
+            .omission_marker(true);
+        for (c, (margin, padding, vertical_padding, max_width, style, hyphenate)) in
+            colonnade.columns.iter_mut().zip(specs.into_iter())
+        {
+            c.left_margin(margin)
+                .padding_left(padding)
+                .padding_right(padding)
+                .padding_top(vertical_padding)
+                .padding_bottom(vertical_padding)
+                .hyphenate(hyphenate)
+                .priority(style as usize % 3)
+                .alignment(match style % 4 {
+                    0 => Alignment::Left,
+                    1 => Alignment::Right,
+                    2 => Alignment::Center,
+                    _ => Alignment::Justify,
+                })
+                .vertical_alignment(match style % 3 {
+                    0 => VerticalAlignment::Top,
+                    1 => VerticalAlignment::Middle,
+                    _ => VerticalAlignment::Bottom,
+                });
+            if let Some(w) = max_width {
+                c.max_width(w).unwrap();
+            }
+        }
+        for table in tables {
+            if let Ok(lines) = colonnade.tabulate(&table) {
+                if !strict {
+                    // outside strict mode a note may still be wider than a tiny viewport
+                    continue;
+                }
+                for line in lines {
+                    prop_assert!(colonnade.display_width(&line) <= viewport, "{:?} is too wide", line);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "trace")]
+#[test]
+fn trace() {
+    use std::sync::{Arc, Mutex};
+    use tracing::{span, Event, Metadata, Subscriber};
+    // a subscriber which records the targets of events
+    struct Targets(Arc<Mutex<Vec<String>>>);
+    impl Subscriber for Targets {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+            span::Id::from_u64(1)
+        }
+        fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+        fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+        fn event(&self, event: &Event<'_>) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(event.metadata().target().to_string());
+        }
+        fn enter(&self, _: &span::Id) {}
+        fn exit(&self, _: &span::Id) {}
+    }
+    let targets = Arc::new(Mutex::new(vec![]));
+    tracing::subscriber::with_default(Targets(targets.clone()), || {
+        let mut colonnade = Colonnade::new(2, 10).unwrap();
+        colonnade
+            .tabulate([["a very long line", "another long line"]])
+            .unwrap();
+    });
+    let targets = targets.lock().unwrap();
+    assert!(targets.len() > 3, "traced each step of the layout");
+    assert!(targets.iter().all(|t| t == "colonnade"));
+}
+
+#[cfg(feature = "terminal")]
+#[test]
+fn for_terminal() {
+    let mut colonnade = Colonnade::for_terminal(2, 10).unwrap();
+    let lines = colonnade.tabulate([["a", "b"]]).unwrap();
+    assert_eq!(vec!["a b"], lines);
+    match Colonnade::for_terminal(0, 10) {
+        Err(ColonnadeError::InsufficientColumns) => (),
+        result => panic!("expected insufficient columns, got {:?}", result),
+    }
+}
+
+#[cfg(feature = "terminal")]
+#[test]
+fn terminal_width() {
+    use colonnade::terminal_width;
+    std::env::set_var("COLUMNS", "47");
+    assert_eq!(33, terminal_width(Some(33), 80));
+    assert_eq!(47, terminal_width(None, 80));
+    std::env::set_var("COLUMNS", "wide");
+    assert_ne!(0, terminal_width(None, 80));
+}
+
+#[test]
+fn responsive() {
+    use colonnade::Responsive;
+    use std::cell::Cell;
+    let width = Cell::new(80);
+    let mut responsive = Responsive::new(Colonnade::new(2, 80).unwrap(), || width.get());
+    let data = [["some text", "some more text"]];
+    let lines = responsive.tabulate(data).unwrap();
+    assert_eq!(vec!["some text some more text"], lines);
+    width.set(12);
+    let lines = responsive.tabulate(data).unwrap();
+    assert_eq!(vec!["some   some ", "text   more ", "       text "], lines);
+    width.set(2);
+    assert!(responsive.tabulate(data).is_err());
+}
+
+#[test]
+fn tuple_rows() {
+    let mut colonnade = Colonnade::new(3, 80).unwrap();
+    let data = vec![("apples", 3, 0.5), ("kumquats", 12, 10.25)];
+    let lines = colonnade.tabulate(&data).unwrap();
+    assert_eq!(vec!["apples    3   0.5", "kumquats 12 10.25"], lines);
+    // explicit alignment takes precedence
+    colonnade.columns[1].alignment(Alignment::Left);
+    let lines = colonnade.tabulate(data).unwrap();
+    assert_eq!(vec!["apples   3    0.5", "kumquats 12 10.25"], lines);
+    // other rows are aligned left by default
+    colonnade.reset();
+    let lines = colonnade.tabulate([[1, 22, 3], [333, 4, 55]]).unwrap();
+    assert_eq!(vec!["1   22 3 ", "333 4  55"], lines);
+    // newtypes opt in to numeric alignment; None is empty
+    use colonnade::{Cell, ToCell};
+    struct Grams(u32);
+    impl ToCell for Grams {
+        fn to_cell(&self) -> Cell<'_> {
+            Cell::new(self.0).numeric(true)
+        }
+    }
+    let mut colonnade = Colonnade::new(3, 80).unwrap();
+    let lines = colonnade
+        .tabulate([("fig", Grams(50), Some(&2)), ("melon", Grams(1500), None)])
+        .unwrap();
+    assert_eq!(vec!["fig     50 2", "melon 1500  "], lines);
+}
+
+#[test]
+fn to_row() {
+    use colonnade::{Cell, ToRow};
+    struct File {
+        name: String,
+        size: u64,
+    }
+    impl ToRow for File {
+        fn to_row(&self) -> Vec<Cell<'_>> {
+            vec![Cell::new(&self.name), Cell::new(self.size).numeric(true)]
+        }
+    }
+    let files = vec![
+        File {
+            name: "Cargo.toml".to_string(),
+            size: 512,
+        },
+        File {
+            name: "lib.rs".to_string(),
+            size: 102_400,
+        },
+    ];
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    let lines = colonnade.tabulate(&files).unwrap();
+    assert_eq!(vec!["Cargo.toml    512", "lib.rs     102400"], lines);
+    let lines = colonnade
+        .tabulate([[Cell::new("a"), Cell::new(1).numeric(true)]])
+        .unwrap();
+    assert_eq!(vec!["a               1"], lines);
+    assert_eq!("102400", files[1].to_row()[1].text());
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn derive_tabular() {
+    use colonnade::{Tabular, ToRow};
+    #[derive(Tabular)]
+    struct File {
+        name: &'static str,
+        #[tabular(header = "Size", align = "center")]
+        size: u64,
+        modified: u32,
+        #[tabular(skip)]
+        #[allow(dead_code)]
+        inode: u64,
+    }
+    assert_eq!(vec!["name", "Size", "modified"], File::headers());
+    assert_eq!(
+        vec![None, Some(Alignment::Center), None],
+        File::alignments()
+    );
+    let file = File {
+        name: "lib.rs",
+        size: 1024,
+        modified: 7,
+        inode: 42,
+    };
+    let row = file.to_row();
+    assert_eq!(
+        vec!["lib.rs", "1024", "7"],
+        row.iter().map(|c| c.text()).collect::<Vec<_>>()
+    );
+    let mut colonnade = Colonnade::new(3, 80).unwrap();
+    let lines = colonnade
+        .tabulate(vec![
+            file,
+            File {
+                name: "a",
+                size: 1,
+                modified: 10,
+                inode: 0,
+            },
+        ])
+        .unwrap();
+    assert_eq!(vec!["lib.rs 1024  7", "a         1 10"], lines);
+}
+
+#[test]
+fn header_row() {
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    colonnade.header_row(true).max_rows(1);
+    colonnade.columns[1].name("count");
+    let lines = colonnade.tabulate([("apple", 3), ("kumquat", 12)]).unwrap();
+    assert_eq!(
+        vec!["      count", "apple     3", "… and 1", "more row"],
+        lines
+    );
+    colonnade.expanded_display(ExpandedDisplay::Always);
+    let lines = colonnade.tabulate([("apple", 3)]).unwrap();
+    assert_eq!(vec!["    0: apple", "count: 3    "], lines);
+}
+
+#[test]
+fn for_tabular() {
+    use colonnade::{Cell, Tabular, ToRow};
+    struct Fruit {
+        name: &'static str,
+        count: usize,
+    }
+    impl ToRow for Fruit {
+        fn to_row(&self) -> Vec<Cell<'_>> {
+            vec![Cell::new(self.name), Cell::new(self.count).numeric(true)]
+        }
+    }
+    impl Tabular for Fruit {
+        fn headers() -> Vec<String> {
+            vec!["Fruit".to_string(), "Count".to_string()]
+        }
+        fn alignments() -> Vec<Option<Alignment>> {
+            vec![Some(Alignment::Right), None]
+        }
+    }
+    let fruit = vec![
+        Fruit {
+            name: "apple",
+            count: 3,
+        },
+        Fruit {
+            name: "kumquat",
+            count: 12,
+        },
+    ];
+    let lines = Colonnade::for_tabular::<Fruit>(80)
+        .unwrap()
+        .tabulate(&fruit)
+        .unwrap();
+    assert_eq!(
+        vec!["  Fruit Count", "  apple     3", "kumquat    12"],
+        lines
+    );
+}
+
+#[test]
+fn tabulate_map() {
+    use std::collections::HashMap;
+    let mut map = HashMap::new();
+    map.insert("version", "0.1");
+    map.insert("description", "format tabular data in columns");
+    let lines = Colonnade::tabulate_map(&map, 25).unwrap();
+    assert_eq!(
+        vec![
+            "description format       ",
+            "            tabular data ",
+            "            in columns   ",
+            "    version 0.1          ",
+        ],
+        lines
+    );
+    let pairs = vec![(2, "b"), (1, "a")];
+    let lines = Colonnade::tabulate_pairs(pairs, 80).unwrap();
+    assert_eq!(vec!["2 b", "1 a"], lines);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn json_table() {
+    use colonnade::JsonTable;
+    let value = serde_json::json!([
+        { "host": "alpha", "up": true, "load": 0.5 },
+        { "host": "beta", "tags": ["db", "primary"], "load": null },
+    ]);
+    let mut table = JsonTable::new(&value).unwrap();
+    assert_eq!(vec!["host", "load", "up", "tags"], table.headers());
+    let lines = table.placeholder("n/a").tabulate(80).unwrap();
+    assert_eq!(
+        vec![
+            "host  load up   tags            ",
+            "alpha  0.5 true n/a             ",
+            "beta  n/a  n/a  [\"db\",\"primary\"]",
+        ],
+        lines
+    );
+    match JsonTable::new(&serde_json::json!([1, 2])) {
+        Err(ColonnadeError::Json(_)) => (),
+        _ => panic!("expected a Json error"),
+    }
+}
+
+#[test]
+fn cells_from_strings() {
+    use colonnade::Cell;
+    let row = vec![
+        Cell::from("plain".to_string()),
+        Cell::from("\x1b[31mred\x1b[0m"),
+    ];
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    assert_eq!(vec!["plain red"], colonnade.tabulate([row]).unwrap());
+    let row = vec!["tab\tbed".to_string(), "two".to_string()];
+    colonnade.reset();
+    assert_eq!(vec!["tabbed two"], colonnade.tabulate([row]).unwrap());
+    // cells of tuples and of borrowed text share the text of the data
+    use colonnade::{ToCell, ToRow};
+    let name = "kumquat".to_string();
+    let pair = (&name, 3);
+    assert_eq!(name.as_ptr(), pair.to_row()[0].text().as_ptr());
+    let cells = vec![Cell::from(name.as_str()), 3.to_cell()];
+    assert_eq!(name.as_ptr(), cells.to_row()[0].text().as_ptr());
+    colonnade.reset();
+    assert_eq!(vec!["kumquat 3"], colonnade.tabulate([cells]).unwrap());
+}
+
+#[test]
+fn key_value() {
+    let mut colonnade = Colonnade::key_value(16).unwrap();
+    let settings = [("user", "root"), ("shell", "/usr/local/bin/fish")];
+    assert_eq!(
+        vec![" user root      ", "shell /usr/loca-", "      l/bin/fish"],
+        colonnade.tabulate(settings).unwrap()
+    );
+}
+
+#[test]
+fn number_format() {
+    let mut colonnade = Colonnade::new(3, 80).unwrap();
+    colonnade.columns[1].number_format(NumberFormat::new().thousands_separator(','));
+    colonnade.columns[2].number_format(NumberFormat::new().precision(2).scientific_threshold(1e6));
+    let data = [
+        ["widgets", "1234567", "0.5"],
+        ["gadgets", "-12", "25000000"],
+        ["gizmos", "n/a", "3"],
+    ];
+    assert_eq!(
+        vec![
+            "widgets 1,234,567   0.50",
+            "gadgets       -12 2.50e7",
+            "gizmos  n/a         3.00",
+        ],
+        colonnade.tabulate(data).unwrap()
+    );
+}
+
+#[test]
+fn number_format_separators() {
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    colonnade.columns[1].number_format(
+        NumberFormat::new()
+            .decimal_point(',')
+            .thousands_separator(' ')
+            .precision(1),
+    );
+    let data = [["Berlin", "3 850 809,0"], ["Potsdam", "183154,25"]];
+    assert_eq!(
+        vec!["Berlin  3 850 809,0", "Potsdam   183 154,2"],
+        colonnade.tabulate(data).unwrap()
+    );
+}
+
+#[test]
+fn empty_placeholder() {
+    use colonnade::Cell;
+    let mut colonnade = Colonnade::new(3, 80).unwrap();
+    colonnade.empty_placeholder("-");
+    colonnade.columns[2].empty_placeholder("none");
+    let data = vec![
+        vec![Cell::from("a"), Cell::from(Some(1)), Cell::from("  ")],
+        vec![Cell::from(""), Cell::from(None::<u8>)],
+    ];
+    assert_eq!(
+        vec!["a 1 none", "- - none"],
+        colonnade.tabulate(&data).unwrap()
+    );
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn datetime_format() {
+    use colonnade::DateTimeFormat;
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    colonnade.columns[1].datetime_format(DateTimeFormat::new("%Y-%m-%d %H:%M"));
+    let data = [
+        ["created", "2024-05-01T13:02:47+02:00"],
+        ["updated", "2024-05-01 09:15:00.250"],
+        ["expires", "never"],
+    ];
+    assert_eq!(
+        vec![
+            "created 2024-05-01 13:02",
+            "updated 2024-05-01 09:15",
+            "expires never           ",
+        ],
+        colonnade.tabulate(data).unwrap()
+    );
+    // formats the value cannot describe leave it as it is
+    let mut colonnade = Colonnade::new(1, 80).unwrap();
+    colonnade.datetime_format(DateTimeFormat::new("%H:%M %z"));
+    assert_eq!(
+        vec!["     12:30 +0000", "2024-05-01T12:30"],
+        colonnade
+            .tabulate([["2024-05-01T12:30:00Z"], ["2024-05-01T12:30"]])
+            .unwrap()
+    );
+}
+
+#[test]
+fn humanize() {
+    let mut colonnade = Colonnade::new(3, 80).unwrap();
+    colonnade.columns[1].humanize(Humanize::Bytes);
+    colonnade.columns[2].humanize(Humanize::Seconds);
+    let data = [
+        ["a", "1023", "59"],
+        ["b", "1024", "61.5"],
+        ["c", "5000000000000", "200000"],
+        ["d", "?", "0.25"],
+    ];
+    assert_eq!(
+        vec![
+            "a  1023 B    59s",
+            "b 1.0 KiB 1m 02s",
+            "c 4.5 TiB 2d 07h",
+            "d ?         0.2s",
+        ],
+        colonnade.tabulate(data).unwrap()
+    );
+}
+
+#[test]
+fn currency() {
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    colonnade.columns[1]
+        .currency("€")
+        .number_format(
+            NumberFormat::new()
+                .decimal_point(',')
+                .thousands_separator('.')
+                .precision(2),
+        )
+        .padding_horizontal(1);
+    let data = [["Miete", "950"], ["Strom", "-62,5"], ["Urlaub", "offen"]];
+    assert_eq!(
+        vec![
+            "Miete   € 950,00 ",
+            "Strom   € -62,50 ",
+            "Urlaub  offen    ",
+        ],
+        colonnade.tabulate(data).unwrap()
+    );
+    colonnade.columns[1].min_width(12).unwrap();
+    assert_eq!(
+        vec![
+            "Miete   €   950,00 ",
+            "Strom   €   -62,50 ",
+            "Urlaub  offen      ",
+        ],
+        colonnade.tabulate(data).unwrap()
+    );
+}
+
+#[test]
+fn percentage() {
+    let mut colonnade = Colonnade::new(3, 80).unwrap();
+    colonnade.columns[1].percentage(Percentage::new(0));
+    colonnade.columns[2].percentage(Percentage::new(2).ratio(false).clamp(0.0, 100.0));
+    let data = [
+        ["cpu", "0.425", "99.5"],
+        ["mem", "1.5", "-3"],
+        ["swap", "", "n/a"],
+    ];
+    assert_eq!(
+        vec!["cpu   42% 99.50%", "mem  150%  0.00%", "swap      n/a   ",],
+        colonnade.tabulate(data).unwrap()
+    );
+    assert_eq!(vec![(1, 2)], colonnade.clamped());
+}
+
+#[test]
+fn bar_chart() {
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    colonnade.header_row(true);
+    colonnade.columns[0].name("lang");
+    colonnade.columns[1]
+        .name("share")
+        .bar_chart(BarChart::new().max(50.0).values(false))
+        .fixed_width(6)
+        .unwrap();
+    let data = [("rust", 25.0), ("perl", 6.25), ("cobol", 100.0)];
+    assert_eq!(
+        vec![
+            "lang  share ",
+            "rust  ███   ",
+            "perl  ▊     ",
+            "cobol ██████",
+        ],
+        colonnade.tabulate(data).unwrap()
+    );
+}
+
+#[test]
+fn sparkline() {
+    use colonnade::Cell;
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    let data = vec![
+        vec![Cell::new("flat"), Cell::sparkline(&[3, 3, 3])],
+        vec![
+            Cell::new("rising"),
+            Cell::sparkline(&[0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0]),
+        ],
+        vec![Cell::new("none"), Cell::sparkline::<f64>(&[])],
+    ];
+    assert_eq!(
+        vec!["flat   ▁▁▁     ", "rising ▁▂▃▄▅▆▇█", "none           "],
+        colonnade.tabulate(&data).unwrap()
+    );
+    colonnade.columns[1].max_width(4).unwrap();
+    assert_eq!(
+        vec!["flat   ▁▁▁ ", "rising ▂▄▆█", "none       "],
+        colonnade.tabulate(&data).unwrap()
+    );
+}
+
+#[test]
+fn progress() {
+    use colonnade::{Cell, ProgressStyle};
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    let data = vec![
+        vec![Cell::new("a"), Cell::progress(0.0)],
+        vec![
+            Cell::new("b"),
+            Cell::progress(0.75).progress_style(ProgressStyle::new('|', '=', '-', '|')),
+        ],
+        vec![Cell::new("c"), Cell::progress(2.0)],
+    ];
+    assert_eq!(
+        vec![
+            "a [..........]   0%",
+            "b |========--|  75%",
+            "c [##########] 100%",
+        ],
+        colonnade.tabulate(&data).unwrap()
+    );
+    colonnade.columns[1].fixed_width(6).unwrap();
+    assert_eq!(
+        vec!["a     0%", "b    75%", "c   100%"],
+        colonnade.tabulate(&data).unwrap()
+    );
+}
+
+#[test]
+fn heat() {
+    use colonnade::HeatScale;
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    colonnade.columns[1].heat(HeatScale::new((0, 0, 255), (255, 0, 0)));
+    let data = [("a", 0), ("b", 5), ("c", 10)];
+    assert_eq!(
+        vec![
+            "a \u{1b}[38;2;0;0;255m 0\u{1b}[0m",
+            "b \u{1b}[38;2;128;0;128m 5\u{1b}[0m",
+            "c \u{1b}[38;2;255;0;0m10\u{1b}[0m",
+        ],
+        colonnade.tabulate(data).unwrap()
+    );
+    colonnade.columns[1].heat(
+        HeatScale::new((0, 0, 0), (200, 200, 200))
+            .range(0.0, 5.0)
+            .background(true),
+    );
+    assert_eq!(
+        vec![
+            "a \u{1b}[48;2;0;0;0m 0\u{1b}[0m",
+            "b \u{1b}[48;2;200;200;200m 5\u{1b}[0m",
+            "c \u{1b}[48;2;200;200;200m10\u{1b}[0m",
+        ],
+        colonnade.tabulate(data).unwrap()
+    );
+}
+
+#[test]
+fn tree() {
+    use colonnade::Cell;
+    let mut colonnade = Colonnade::new(1, 12).unwrap();
+    let data = vec![
+        vec![Cell::node(0, "animals")],
+        vec![Cell::node(1, "cats and dogs")],
+        vec![Cell::node(2, "tabby")],
+        vec![Cell::node(1, "birds")],
+    ];
+    assert_eq!(
+        vec![
+            "animals     ",
+            "├─cats and  ",
+            "│ dogs      ",
+            "│ └─tabby   ",
+            "└─birds     ",
+        ],
+        colonnade.tabulate(&data).unwrap()
+    );
+}
+
+#[test]
+fn computed_column() {
+    use colonnade::Cell;
+    let mut colonnade = Colonnade::new(3, 80).unwrap();
+    colonnade.columns[0].computed(|row| Cell::new(row[0].text().len() + row[1].text().len()));
+    let data = vec![vec!["a", "bc"], vec!["def", "ghij"]];
+    assert_eq!(
+        vec!["3 a   bc  ", "7 def ghij"],
+        colonnade.tabulate(&data).unwrap()
+    );
+    assert_eq!(vec![vec!["a", "bc"], vec!["def", "ghij"]], data);
+}
+
+#[test]
+fn literal_column() {
+    let mut colonnade = Colonnade::new(3, 12).unwrap();
+    colonnade.columns[1].literal("=>");
+    assert_eq!(
+        vec![
+            "the  => mea-",
+            "red     ns  ",
+            "lig-    stop",
+            "ht          "
+        ],
+        colonnade
+            .tabulate([["the red light", "means stop"]])
+            .unwrap()
+    );
+}
+
+#[test]
+fn wrap_gutter() {
+    let mut colonnade = Colonnade::new(2, 12).unwrap();
+    colonnade.wrap_gutter(Some('>')).spaces_between_rows(1);
+    let data = [["a", "b c d e f g"], ["h", "i"]];
+    assert_eq!(
+        vec![
+            "  a     b c ",
+            ">       d e ",
+            ">       f g ",
+            "",
+            "  h     i   "
+        ],
+        colonnade.tabulate(data).unwrap()
+    );
+}
+
+#[test]
+fn diff() {
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    colonnade.columns[0].name("fruit");
+    colonnade.columns[1].name("n");
+    colonnade.header_row(true);
+    let old = [("apple", 3), ("banana", 5), ("cherry", 7)];
+    let new = [("apple", 3), ("cherry", 8), ("date", 1)];
+    assert_eq!(
+        vec![
+            "  fruit  n ",
+            "  apple   3",
+            "+ banana  5",
+            "~ cherry *7",
+            "- date    1",
+        ],
+        colonnade.tabulate_diff(new, old, 0).unwrap()
+    );
+    assert_eq!(
+        vec!["fruit  n", "apple  3", "cherry 8", "date   1"],
+        colonnade.tabulate(new).unwrap()
+    );
+}
+
+#[test]
+fn sort_by_column() {
+    use colonnade::Order;
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    colonnade.sort_by_column(0, Order::Asc).unwrap();
+    let data = [["b", "1"], ["10", "2"], ["a", "3"], ["9.5", "4"]];
+    assert_eq!(
+        vec!["9.5 4", "10  2", "a   3", "b   1"],
+        colonnade.tabulate(data).unwrap()
+    );
+    assert!(matches!(
+        colonnade.sort_by_column(2, Order::Desc),
+        Err(ColonnadeError::OutOfBounds(2, None))
+    ));
+}
+
+#[test]
+fn sort_by_keys() {
+    use colonnade::{Order, SortKey};
+    let mut colonnade = Colonnade::new(3, 80).unwrap();
+    colonnade
+        .sort_by(vec![
+            SortKey::new(0, Order::Desc),
+            SortKey::new(1, Order::Asc)
+                .comparator(|a, b| a.text().to_lowercase().cmp(&b.text().to_lowercase())),
+        ])
+        .unwrap();
+    let data = [
+        ["1", "b", "first"],
+        ["2", "B", "second"],
+        ["1", "A", "third"],
+        ["1", "b", "fourth"],
+    ];
+    assert_eq!(
+        vec!["2 B second", "1 A third ", "1 b first ", "1 b fourth"],
+        colonnade.tabulate(data).unwrap()
+    );
+}
+
+#[test]
+fn group_by() {
+    use colonnade::Order;
+    let mut colonnade = Colonnade::new(3, 80).unwrap();
+    colonnade
+        .group_by(Some(1))
+        .unwrap()
+        .sort_by_column(2, Order::Desc)
+        .unwrap();
+    let data = [
+        ["apple", "fruit", "3"],
+        ["kale", "vegetable", "1"],
+        ["pear", "fruit", "12"],
+    ];
+    assert_eq!(
+        vec![
+            "fruit    ",
+            "pear   12",
+            "apple  3 ",
+            "vegetable",
+            "kale   1 "
+        ],
+        colonnade.tabulate(data).unwrap()
+    );
+}
+
+#[test]
+fn aggregate() {
+    use colonnade::{Aggregate, Style};
+    let mut colonnade = Colonnade::new(3, 80).unwrap();
+    colonnade.total_style(Some(Style::new().bold(true)));
+    colonnade.columns[1].aggregate(Aggregate::Count);
+    colonnade.columns[2].aggregate(Aggregate::Max);
+    let data = [
+        ("apple", "red", 3.5),
+        ("kale", "", 1.0),
+        ("pear", "green", 2.0),
+    ];
+    assert_eq!(
+        vec![
+            "apple red   3.5",
+            "kale          1",
+            "pear  green   2",
+            "\u{1b}[1mtotal\u{1b}[0m \u{1b}[1m    2\u{1b}[0m \u{1b}[1m3.5\u{1b}[0m",
+        ],
+        colonnade.tabulate(data).unwrap()
+    );
+}
+
+#[test]
+fn column_order() {
+    let mut colonnade = Colonnade::new(3, 80).unwrap();
+    colonnade.column_order(&[2, 1, 0]).unwrap();
+    colonnade.columns[0].alignment(Alignment::Right);
+    colonnade.columns[1].left_margin(3);
+    let data = [["a", "bbb", "ccccc"], ["aaaa", "b", "c"]];
+    assert_eq!(
+        vec!["ccccc   bbb    a", "c       b   aaaa"],
+        colonnade.tabulate(data).unwrap()
+    );
+    assert!(matches!(
+        colonnade.column_order(&[3]),
+        Err(ColonnadeError::OutOfBounds(3, None))
+    ));
+    colonnade.column_order(&[]).unwrap();
+    assert_eq!(
+        vec!["   a   bbb ccccc", "aaaa   b   c    "],
+        colonnade.tabulate(data).unwrap()
+    );
+}
+
+#[test]
+fn push_and_remove_columns() {
+    use colonnade::Order;
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    colonnade.left_margin(2).unwrap();
+    colonnade.push_column().alignment(Alignment::Right);
+    colonnade.sort_by_column(2, Order::Desc).unwrap();
+    let data = [["a", "b", "1"], ["c", "d", "22"]];
+    assert_eq!(
+        vec!["  c  d 22", "  a  b  1"],
+        colonnade.tabulate(data).unwrap()
+    );
+    colonnade.remove_column(0).unwrap();
+    let data = [["b", "1"], ["d", "22"]];
+    assert_eq!(vec!["  d 22", "  b  1"], colonnade.tabulate(data).unwrap());
+    colonnade.remove_column(1).unwrap();
+    assert!(matches!(
+        colonnade.remove_column(0),
+        Err(ColonnadeError::InsufficientColumns)
+    ));
+}
+
+#[test]
+fn share_width() {
+    let mut colonnade = Colonnade::new(3, 80).unwrap();
+    colonnade.share_width(&[0, 2]).unwrap();
+    let data = [["a", "b", "cccc"], ["dd", "e", "f"]];
+    assert_eq!(
+        vec!["a    b cccc", "dd   e f   "],
+        colonnade.tabulate(data).unwrap()
+    );
+    colonnade.remove_column(1).unwrap();
+    let data = [["a", "cccc"], ["dd", "f"]];
+    assert_eq!(
+        vec!["a    cccc", "dd   f   "],
+        colonnade.tabulate(data).unwrap()
+    );
+}
+
+#[test]
+fn side_by_side() {
+    use colonnade::SideBySide;
+    let mut pair = SideBySide::new(
+        Colonnade::new(1, 80).unwrap(),
+        Colonnade::new(1, 80).unwrap(),
+    );
+    pair.right().header_row(true).columns[0].name("after");
+    let lines = pair
+        .tabulate([["x"], ["y z"]], [["1"], ["2"], ["3"]], 12)
+        .unwrap();
+    assert_eq!(
+        vec!["x    after", "y z  1    ", "     2    ", "     3    "],
+        lines
+    );
+}
+
+#[test]
+fn trim_trailing() {
+    let mut colonnade = Colonnade::new(3, 80).unwrap();
+    colonnade.trim_trailing(true).spaces_between_rows(1);
+    let data = [["1", "2", "3"], ["4", "55", ""]];
+    assert_eq!(
+        vec!["1 2  3", "", "4 55"],
+        colonnade.tabulate(data).unwrap()
+    );
+    let maceration = colonnade.macerate(data).unwrap();
+    assert_eq!(
+        vec![
+            (String::new(), "4".to_string()),
+            (" ".to_string(), "55".to_string()),
+            (" ".to_string(), " ".to_string())
+        ],
+        maceration[1][0]
+    );
+}
+
+#[test]
+fn pad_lines() {
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    colonnade
+        .pad_lines(true)
+        .trim_trailing(true)
+        .spaces_between_rows(1)
+        .max_rows(2);
+    let data = [["a", "bbb"], ["ccc", "d"], ["e", "f"]];
+    assert_eq!(
+        vec![
+            "a   bbb",
+            "       ",
+            "ccc d  ",
+            "… and 1",
+            "more   ",
+            "row    "
+        ],
+        colonnade.tabulate(data).unwrap()
+    );
+}
+
+#[test]
+fn separator() {
+    use colonnade::Separator;
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    colonnade
+        .spaces_between_rows(1)
+        .separator(Separator::Spaces);
+    let data = [["a", "bbb"], ["ccc", "d"]];
+    assert_eq!(
+        vec!["a   bbb", "       ", "ccc d  "],
+        colonnade.tabulate(data).unwrap()
+    );
+    colonnade.separator(Separator::Fill("=-".to_string()));
+    assert_eq!(
+        vec!["a   bbb", "=-=-=-=", "ccc d  "],
+        colonnade.tabulate(data).unwrap()
+    );
+    colonnade.separator(Separator::Empty);
+    assert_eq!(
+        vec![(String::new(), String::new())],
+        colonnade.macerate(data).unwrap()[0][1]
+    );
+}
+
+#[test]
+fn spaces_between_columns() {
+    let mut colonnade = Colonnade::new(3, 80).unwrap();
+    colonnade.spaces_between_columns(2).unwrap();
+    assert_eq!(
+        vec!["a  b  c"],
+        colonnade.tabulate([["a", "b", "c"]]).unwrap()
+    );
+    let mut colonnade = Colonnade::new(3, 10).unwrap();
+    assert!(matches!(
+        colonnade.spaces_between_columns(4),
+        Err(ColonnadeError::InsufficientSpace(_))
+    ));
+}
+
+#[test]
+fn right_margin() {
+    let mut colonnade = Colonnade::new(2, 100).unwrap();
+    colonnade.columns[0].right_margin(2);
+    colonnade.columns[1].right_margin(1);
+    let data = [["a", "b"], ["cc", "d"]];
+    assert_eq!(
+        vec!["a    b ", "cc   d "],
+        colonnade.tabulate(data).unwrap()
+    );
+    assert_eq!(
+        vec![vec![
+            ("".to_string(), "a ".to_string()),
+            ("  ".to_string(), "".to_string()),
+            (" ".to_string(), "b".to_string()),
+            (" ".to_string(), "".to_string()),
+        ]],
+        colonnade.macerate(&data[0..1]).unwrap()[0]
+    );
+}
+
+#[test]
+fn separator_rows() {
+    use colonnade::Separator;
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    colonnade.separator_rows(Some(Separator::Fill("-=".to_string())));
+    let data = vec![vec!["a", "bbb"], vec!["", " "], vec!["ccc", "d"], vec![]];
+    assert_eq!(
+        vec!["a   bbb", "-=-=-=-", "ccc d  ", "-=-=-=-"],
+        colonnade.tabulate(&data).unwrap()
+    );
+    colonnade.spaces_between_rows(1);
+    let maceration = colonnade.macerate(&data).unwrap();
+    assert_eq!(
+        vec![
+            ("-=-=-=-".to_string(), "".to_string()),
+            ("       ".to_string(), "".to_string())
+        ],
+        maceration[1]
+            .iter()
+            .map(|l| l[0].clone())
+            .collect::<Vec<_>>()
+    );
+    colonnade.separator_rows(None).spaces_between_rows(0);
+    assert_eq!(
+        vec!["a   bbb", "       ", "ccc d  "],
+        colonnade.tabulate(&data[0..3]).unwrap()
+    );
+}
+
+#[test]
+fn suppress_outer_padding() {
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    colonnade.padding_vertical(1).suppress_outer_padding(true);
+    colonnade.columns[1].vertical_alignment(VerticalAlignment::Bottom);
+    let data = [["a b", "c"], ["d", "e"]];
+    colonnade.fixed_width(1).unwrap();
+    assert_eq!(
+        vec!["a  ", "b c", "   ", "   ", "d e"],
+        colonnade.tabulate(data).unwrap()
+    );
+    colonnade.suppress_outer_padding(false);
+    assert_eq!(
+        vec!["   ", "a  ", "b c", "   ", "   ", "d e", "   "],
+        colonnade.tabulate(data).unwrap()
+    );
+}
+
+#[test]
+fn builder() {
+    use colonnade::{ColonnadeBuilder, ColumnConfig};
+    let mut colonnade = ColonnadeBuilder::new(3, 80)
+        .spaces_between_columns(2)
+        .headers(&["fruit", "count"])
+        .column(1, ColumnConfig::new().alignment(Alignment::Right))
+        .column(2, ColumnConfig::new().hidden(true))
+        .build()
+        .unwrap();
+    assert_eq!(
+        vec!["fruit  count", "apple      3"],
+        colonnade.tabulate([("apple", 3, "x")]).unwrap()
+    );
+    match ColonnadeBuilder::new(2, 80)
+        .column(2, ColumnConfig::new())
+        .build()
+    {
+        Err(ColonnadeError::OutOfBounds(2, None)) => (),
+        _ => panic!("expected OutOfBounds"),
+    }
+    match ColonnadeBuilder::new(2, 80)
+        .headers(&["fruit", "count", "color"])
+        .build()
+    {
+        Err(e @ ColonnadeError::OutOfBounds(2, Some(_))) => {
+            assert_eq!("OutOfBounds: there is no column 2 (color)", e.to_string())
+        }
+        _ => panic!("expected OutOfBounds"),
+    }
+    match ColonnadeBuilder::new(2, 80)
+        .column(1, ColumnConfig::new().min_width(5).max_width(3))
+        .build()
+    {
+        Err(ColonnadeError::MinGreaterThanMax(1, None)) => (),
+        _ => panic!("expected MinGreaterThanMax"),
+    }
+    // the configuration is only checked as a whole
+    let builder = ColonnadeBuilder::new(2, 10).left_margin(5);
+    match builder.build() {
+        Err(ColonnadeError::InsufficientSpace(_)) => (),
+        _ => panic!("expected InsufficientSpace"),
+    }
+    assert!(builder.viewport(12).build().is_ok());
+}
+
+#[test]
+fn typestate_column_config() {
+    use colonnade::{Bounded, ColonnadeBuilder, ColumnConfig, Fixed};
+    let fixed: ColumnConfig<Fixed> = ColumnConfig::new().priority(0).fixed_width(3);
+    let bounded: ColumnConfig<Bounded> = ColumnConfig::new().max_width(4).min_width(2);
+    let mut colonnade = ColonnadeBuilder::new(2, 80)
+        .column(0, fixed)
+        .column(1, bounded)
+        .build()
+        .unwrap();
+    assert_eq!(
+        vec!["a   bb  ", "    cc  ", "    dd  "],
+        colonnade.tabulate([["a", "bb cc dd"]]).unwrap()
+    );
+}
+
+#[cfg(all(feature = "serde", feature = "json"))]
+#[test]
+fn builder_from_config() {
+    use colonnade::{ColonnadeBuilder, ColumnConfig};
+    let config = r#"{
+        "columns": 2,
+        "width": 80,
+        "spaces_between_columns": 2,
+        "headers": ["fruit", "count"],
+        "column": [{ "index": 1, "alignment": "Right", "min_width": 6 }]
+    }"#;
+    let builder: ColonnadeBuilder = serde_json::from_str(config).unwrap();
+    let mut colonnade = builder.build().unwrap();
+    assert_eq!(
+        vec!["fruit   count", "apple       3"],
+        colonnade.tabulate([("apple", 3)]).unwrap()
+    );
+    let builder = ColonnadeBuilder::new(2, 40).column(0, ColumnConfig::new().fixed_width(5));
+    let round_trip: ColonnadeBuilder =
+        serde_json::from_str(&serde_json::to_string(&builder).unwrap()).unwrap();
+    assert_eq!(format!("{:?}", builder), format!("{:?}", round_trip));
+}
+
+#[test]
+fn configure_columns() {
+    let mut colonnade = Colonnade::new(3, 80).unwrap();
+    colonnade
+        .configure_columns(|i, column| {
+            column.name(&format!("c{}", i));
+            if i > 0 {
+                column.alignment(Alignment::Right).min_width(3)?;
+            }
+            Ok(())
+        })
+        .unwrap()
+        .header_row(true);
+    assert_eq!(
+        vec!["c0  c1  c2", "a    b   c"],
+        colonnade.tabulate([["a", "b", "c"]]).unwrap()
+    );
+    match colonnade.configure_columns(|_, column| {
+        column.max_width(1)?;
+        Ok(())
+    }) {
+        Err(ColonnadeError::MinGreaterThanMax(1, Some(name))) => assert_eq!("c1", name),
+        _ => panic!("expected MinGreaterThanMax"),
+    }
+    match colonnade.configure_columns(|_, column| {
+        column.padding_horizontal(30);
+        Ok(())
+    }) {
+        Err(ColonnadeError::InsufficientSpace(_)) => (),
+        _ => panic!("expected InsufficientSpace"),
+    }
+}
+
+#[test]
+fn errors_name_columns() {
+    let mut colonnade = Colonnade::new(2, 10).unwrap();
+    colonnade.columns[1].name("count").min_width(5).unwrap();
+    let error = colonnade.columns[1].max_width(3).unwrap_err();
+    assert_eq!(
+        "MinGreaterThanMax: the minimum width of column 1 (count) is greater than its maximum",
+        error.to_string()
+    );
+    match colonnade.viewport(4) {
+        Err(error @ ColonnadeError::InsufficientSpace(_)) => assert_eq!(
+            "InsufficientSpace: need at least 7 columns, have 4; the widest is column 1 (count) at 6",
+            error.to_string()
+        ),
+        result => panic!("expected insufficient space, got {:?}", result.err()),
+    }
+}
+
+#[test]
+fn presets() {
+    use colonnade::{ColonnadeBuilder, Preset, Separator};
+    let house_style = Preset::new()
+        .spaces_between_columns(2)
+        .spaces_between_rows(1)
+        .separator(Separator::Fill("-".to_string()))
+        .alignment(Alignment::Center);
+    let data = [["a", "bbb"], ["cc", "d"]];
+    let mut colonnade = Colonnade::with_preset(&house_style, 2, 80).unwrap();
+    assert_eq!(
+        vec!["a   bbb", "-------", "cc   d "],
+        colonnade.tabulate(data).unwrap()
+    );
+    let mut colonnade = ColonnadeBuilder::new(2, 80)
+        .preset(house_style.clone())
+        .column(0, colonnade::ColumnConfig::new().alignment(Alignment::Left))
+        .build()
+        .unwrap();
+    assert_eq!(
+        vec!["a   bbb", "-------", "cc   d "],
+        colonnade.tabulate(data).unwrap()
+    );
+    match Colonnade::with_preset(&house_style, 2, 3) {
+        Err(ColonnadeError::InsufficientSpace(_)) => (),
+        result => panic!("expected insufficient space, got {:?}", result.err()),
+    }
+}
+
+#[test]
+fn layout_kept_apart_from_specs() {
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    colonnade.columns[0].min_width(2).unwrap();
+    let data = [["a", "bbb bbb"]];
+    assert_eq!(vec!["a  bbb bbb"], colonnade.tabulate(data).unwrap());
+    assert_eq!(vec![2, 7], colonnade.layout().widths());
+    // a copy keeps the layout computed for the specification it shares
+    let mut copy = colonnade.clone();
+    assert_eq!(vec![2, 7], copy.layout().widths());
+    // and adapts to its own viewport without disturbing the original
+    copy.viewport(8).unwrap();
+    assert_eq!(vec!["a  bbb  ", "   bbb  "], copy.tabulate(data).unwrap());
+    assert_eq!(vec![2, 5], copy.layout().widths());
+    assert_eq!(vec![2, 7], colonnade.layout().widths());
+    // a change to a column's specification makes the layout stale
+    colonnade.columns[0].min_width(4).unwrap();
+    assert_eq!(vec!["a    bbb bbb"], colonnade.tabulate(data).unwrap());
+    assert_eq!(Some(4), colonnade.layout().width(0));
+}
+
+#[test]
+fn command_line() {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+    let tabulate = |args: &[&str], input: &str| {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_colonnade"))
+            .args(args)
+            .env_remove("COLUMNS")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(input.as_bytes())
+            .unwrap();
+        let output = child.wait_with_output().unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+    assert_eq!(
+        "name              size\nfoo              1,024\na \"quoted\" word      3\n",
+        tabulate(
+            &["--header", "--align", "left,right", "--gap", "2"],
+            "name,size\nfoo,\"1,024\"\n\"a \"\"quoted\"\" word\",3\n"
+        )
+    );
+    assert_eq!(
+        "a   b\n-----\nccc d\n",
+        tabulate(&["-r", "-", "-w", "20"], "a\tb\nccc\td\n")
+    );
+}
+
+#[test]
+fn elastic_tabstops() {
+    use colonnade::ElasticTabstops;
+    let text = "name\tsize\nfoo\t3\nlong name\t1024\n\nx\ty\tz";
+    assert_eq!(
+        vec![
+            "name      size",
+            "foo       3",
+            "long name 1024",
+            "",
+            "x y z"
+        ],
+        ElasticTabstops::new().tabulate(text, 80).unwrap()
+    );
+    let text = "a,bb,\nccc,d\n# comment\ne,f";
+    assert_eq!(
+        vec!["a    bb", "ccc  d", "# comment", "e  f"],
+        ElasticTabstops::new()
+            .delimiter(',')
+            .gap(2)
+            .tabulate(text, 80)
+            .unwrap()
+    );
+    // blocks wrap to the viewport
+    assert_eq!(
+        vec!["a one", "  two", "b c"],
+        ElasticTabstops::new()
+            .tabulate("a\tone two\nb\tc", 5)
+            .unwrap()
+    );
+}
+
+#[test]
+fn parse_aligned_text() {
+    use colonnade::parse_aligned;
+    let lines = vec![
+        "name         size",
+        "foo             3",
+        "",
+        "a long name  1024",
+    ];
+    assert_eq!(
+        vec![
+            vec!["name", "size"],
+            vec!["foo", "3"],
+            vec!["a long name", "1024"]
+        ],
+        parse_aligned(&lines, 2)
+    );
+    assert_eq!(
+        vec![vec!["a", "long", "name"], vec!["b", "c", "d"]],
+        parse_aligned(&["a long name", "b c    d"], 1)
+    );
+    // wrapped cells are reassembled so the table can be laid out again
+    let data = [
+        ["apple", "a red fruit that grows on trees"],
+        ["kiwi", "fuzzy"],
+    ];
+    let mut colonnade = Colonnade::new(2, 20).unwrap();
+    colonnade.spaces_between_columns(2).unwrap();
+    let lines = colonnade.tabulate(data).unwrap();
+    assert_eq!(6, lines.len());
+    let rows = parse_aligned(&lines, 2);
+    assert_eq!(
+        Colonnade::new(2, 40).unwrap().tabulate(data).unwrap(),
+        Colonnade::new(2, 40).unwrap().tabulate(&rows).unwrap()
+    );
+}
+
+#[test]
+fn tabulate_text() {
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    assert_eq!(
+        vec!["fruit      count", "apple, red 3    "],
+        colonnade
+            .tabulate_text("fruit,count\n\"apple, red\",3", ',', true)
+            .unwrap()
+    );
+    let mut colonnade = Colonnade::new(3, 80).unwrap();
+    assert_eq!(
+        vec!["\"a b\" c", "d  e  f"],
+        colonnade
+            .tabulate_text("\"a|b\"|c\nd|e|f\n", '|', false)
+            .unwrap()
+    );
+}
+
+#[test]
+fn auto_stream() {
+    use colonnade::{AutoStream, CellContext, Color, ColorChoice, HeatScale, Style, Styler};
+    use std::io::Write;
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    colonnade.columns[1].heat(HeatScale::new((0, 0, 255), (255, 0, 0)));
+    let lines = colonnade.tabulate([("a", 0), ("b", 10)]).unwrap();
+    let write = |choice| {
+        let mut buffer = vec![];
+        {
+            let mut out = AutoStream::new(&mut buffer, choice);
+            for line in &lines {
+                writeln!(out, "{}", line).unwrap();
+            }
+        }
+        String::from_utf8(buffer).unwrap()
+    };
+    assert_eq!("a  0\nb 10\n", write(ColorChoice::Never));
+    // a buffer is not a terminal
+    assert_eq!("a  0\nb 10\n", write(ColorChoice::Auto));
+    assert_eq!(lines.join("\n") + "\n", write(ColorChoice::AlwaysAnsi));
+    let style = Style::new()
+        .foreground(Color::Red)
+        .background(Color::Rgb(1, 2, 3))
+        .bold(true);
+    let converted: anstyle::Style = style.clone().into();
+    assert_eq!(
+        anstyle::Style::new()
+            .fg_color(Some(anstyle::AnsiColor::Red.into()))
+            .bg_color(Some(anstyle::RgbColor(1, 2, 3).into()))
+            .bold(),
+        converted
+    );
+    assert_eq!(style, Style::from(converted));
+    assert_eq!(
+        Color::Ansi256(9),
+        Color::from(anstyle::Color::from(anstyle::AnsiColor::BrightRed))
+    );
+    let ctx = CellContext {
+        row: 0,
+        column: 0,
+        header: false,
+    };
+    assert_eq!(
+        "\u{1b}[1mhot\u{1b}[0m",
+        anstyle::Style::new().bold().style(ctx, "hot")
+    );
+}
+
+#[test]
+fn render() {
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    colonnade.spaces_between_rows(1);
+    let table = colonnade
+        .render([["some", "words"], ["for", "example"]])
+        .unwrap();
+    assert_eq!(12, table.width());
+    assert_eq!(3, table.height());
+    assert_eq!("some words  \n\nfor  example", table.to_string());
+    assert_eq!(format!("{}", table), table.lines().join("\n"));
+    let lines: Vec<String> = table.into_iter().collect();
+    assert_eq!(vec!["some words  ", "", "for  example"], lines);
+}
+
+#[test]
+fn named_formats() {
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    colonnade.columns[0].name("fruit");
+    colonnade.columns[1].name("count");
+    colonnade.spaces_between_rows(1).left_margin(1).unwrap();
+    colonnade.format("plain").unwrap();
+    let data = [("apple", 3), ("fig", 10)];
+    assert_eq!(
+        vec!["apple   3", "fig    10"],
+        colonnade.tabulate(data).unwrap()
+    );
+    colonnade.format("simple").unwrap();
+    assert_eq!(
+        vec![
+            "fruit  count",
+            "-----  -----",
+            "apple      3",
+            "fig       10"
+        ],
+        colonnade.tabulate(data).unwrap()
+    );
+    colonnade.format("github").unwrap();
+    assert_eq!(
+        vec![
+            "| fruit | count |",
+            "|-------|-------|",
+            "| apple |     3 |",
+            "| fig   |    10 |",
+        ],
+        colonnade.tabulate(data).unwrap()
+    );
+    colonnade.format("grid").unwrap();
+    assert_eq!(
+        vec![
+            "+-------+-------+",
+            "| fruit | count |",
+            "+=======+=======+",
+            "| apple |     3 |",
+            "+-------+-------+",
+            "| fig   |    10 |",
+            "+-------+-------+",
+        ],
+        colonnade.tabulate(data).unwrap()
+    );
+    // a wrapped cell is framed on every line
+    let mut colonnade = Colonnade::new(2, 15).unwrap();
+    colonnade.format("grid").unwrap();
+    colonnade.header_row(false);
+    assert_eq!(
+        vec![
+            "+-----+-------+",
+            "| a   | bbbbb |",
+            "| bb  | c     |",
+            "+-----+-------+",
+        ],
+        colonnade.tabulate([["a bb", "bbbbb c"]]).unwrap()
+    );
+    match colonnade.format("fancy") {
+        Err(ColonnadeError::UnsupportedFormat(name)) => assert_eq!("fancy", name),
+        _ => panic!("expected an unsupported format"),
+    }
+    colonnade.format("plain").unwrap();
+    assert_eq!(vec!["a  bb"], colonnade.tabulate([["a", "bb"]]).unwrap());
+}
+
+#[test]
+fn render_snapshot() {
+    use colonnade::HeatScale;
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    colonnade.columns[1].heat(HeatScale::new((0, 0, 255), (255, 0, 0)));
+    colonnade.spaces_between_rows(1).pad_lines(true);
+    let data = [("a\u{A0}b", 0), ("c", 10)];
+    assert_eq!(
+        "a b  0\n\nc   10\n",
+        colonnade.render_snapshot(data).unwrap()
+    );
+    assert_eq!(
+        "",
+        colonnade.render_snapshot(Vec::<[&str; 2]>::new()).unwrap()
+    );
+}
+
+#[test]
+fn row_height() {
+    let mut colonnade = Colonnade::new(2, 11).unwrap();
+    colonnade.row_height(2);
+    colonnade.columns[1].vertical_alignment(VerticalAlignment::Bottom);
+    let data = [["a", "b"], ["", ""], ["c", "one two three four"]];
+    assert_eq!(
+        vec![
+            "a          ",
+            "    b      ",
+            "           ",
+            "           ",
+            "c   three  ",
+            "    four   ",
+        ],
+        colonnade.tabulate(data).unwrap()
+    );
+}
+
+#[test]
+fn cell_vertical_alignment() {
+    use colonnade::Cell;
+    let mut colonnade = Colonnade::new(3, 80).unwrap();
+    colonnade.vertical_alignment(VerticalAlignment::Bottom);
+    let data = vec![vec![
+        Cell::new("merged").vertical_alignment(VerticalAlignment::Middle),
+        Cell::new("top").vertical_alignment(VerticalAlignment::Top),
+        Cell::new("bottom"),
+    ]];
+    colonnade.columns[2].fixed_width(1).unwrap();
+    let lines = colonnade.tabulate(&data).unwrap();
+    assert_eq!(6, lines.len());
+    assert_eq!("       top b", lines[0]);
+    assert_eq!("merged     t", lines[2]);
+    assert_eq!("           m", lines[5]);
+}
+
+#[test]
+fn min_height_layout() {
+    use colonnade::LayoutStrategy;
+    let mut colonnade = Colonnade::new(3, 30).unwrap();
+    let data = [
+        [
+            "id",
+            "a fairly long description of the thing in question",
+            "notes about it",
+        ],
+        ["2", "short", "more notes which go on for a while"],
+    ];
+    assert_eq!(9, colonnade.tabulate(data).unwrap().len());
+    colonnade.layout_strategy(LayoutStrategy::MinHeight);
+    assert_eq!(
+        vec![
+            "id   a fairly long notes about",
+            "     description   it         ",
+            "     of the thing             ",
+            "     in question              ",
+            "2    short         more notes ",
+            "                   which go on",
+            "                   for a while",
+        ],
+        colonnade.tabulate(data).unwrap()
+    );
+    // data fitting at natural widths is laid out as before
+    colonnade.reset();
+    assert_eq!(
+        vec!["a b c"],
+        colonnade.tabulate([["a", "b", "c"]]).unwrap()
+    );
+}
+
+#[test]
+fn proportional_layout() {
+    use colonnade::LayoutStrategy;
+    let mut colonnade = Colonnade::new(3, 30).unwrap();
+    colonnade.layout_strategy(LayoutStrategy::Proportional);
+    let data = [
+        [
+            "id",
+            "a fairly long description of the thing in question",
+            "notes about it",
+        ],
+        ["2", "short", "more notes which go on for a while"],
+    ];
+    assert_eq!(
+        vec![
+            "id  a fairly long   notes     ",
+            "    description of  about it  ",
+            "    the thing in              ",
+            "    question                  ",
+            "2   short           more notes",
+            "                    which go  ",
+            "                    on for a  ",
+            "                    while     ",
+        ],
+        colonnade.tabulate(data).unwrap()
+    );
+}
+
+#[test]
+fn target_height() {
+    let data = [
+        [
+            "id",
+            "a fairly long description of the thing in question",
+            "notes about it",
+        ],
+        ["2", "short", "more notes which go on for a while"],
+    ];
+    let mut colonnade = Colonnade::new(3, 30).unwrap();
+    assert_eq!(None, colonnade.target_height_met());
+    colonnade.target_height(9);
+    assert_eq!(9, colonnade.tabulate(data).unwrap().len());
+    assert_eq!(Some(true), colonnade.target_height_met());
+    colonnade.target_height(8);
+    assert_eq!(7, colonnade.tabulate(data).unwrap().len());
+    assert_eq!(Some(true), colonnade.target_height_met());
+    colonnade.target_height(6);
+    assert_eq!(7, colonnade.tabulate(data).unwrap().len());
+    assert_eq!(Some(false), colonnade.target_height_met());
+    colonnade.spaces_between_rows(1).target_height(7);
+    assert_eq!(8, colonnade.tabulate(data).unwrap().len());
+    assert_eq!(Some(false), colonnade.target_height_met());
+}
+
+#[test]
+fn avoid_orphans() {
+    let mut colonnade = Colonnade::new(2, 15).unwrap();
+    colonnade.avoid_orphans(true).trim_trailing(true);
+    colonnade.columns[0].fixed_width(1).unwrap();
+    let data = [["1", "one two three four"], ["2", "five six"]];
+    assert_eq!(
+        vec!["1 one two", "  three four", "2 five six"],
+        colonnade.tabulate(data).unwrap()
+    );
+    colonnade.columns[1].avoid_orphans(false);
+    assert_eq!(
+        vec!["1 one two three", "  four", "2 five six"],
+        colonnade.tabulate(data).unwrap()
+    );
+}
+
+#[test]
+fn row_heights() {
+    let mut colonnade = Colonnade::new(2, 10).unwrap();
+    assert!(colonnade.row_heights().is_empty());
+    colonnade.header_row(true).max_rows(2);
+    colonnade.columns[0].name("k").padding_top(1);
+    let data = [["a", "b"], ["c", "several words here"], ["d", "e"]];
+    let lines = colonnade.tabulate(data).unwrap();
+    let heights = colonnade.row_heights();
+    assert_eq!(vec![2, 2, 3], heights);
+    // the note on omitted rows follows the rows
+    assert!(lines[heights.iter().sum::<usize>()].starts_with('…'));
+}
+
+#[test]
+fn row_spacing() {
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    colonnade.header_row(true);
+    colonnade.columns[0].name("fruit");
+    colonnade.columns[1].name("count");
+    colonnade.row_spacing(|previous, next| match (previous, next) {
+        (0, 1) => 1,
+        (_, 3) => 2,
+        _ => 0,
+    });
+    let data = [("apples", 3), ("pears", 2), ("total", 5)];
+    assert_eq!(
+        vec![
+            "fruit  count",
+            "",
+            "apples     3",
+            "pears      2",
+            "",
+            "",
+            "total      5",
+        ],
+        colonnade.tabulate(data).unwrap()
+    );
+    assert_eq!(vec![2, 1, 3, 1], colonnade.row_heights());
+    colonnade.spaces_between_rows(0);
+    assert_eq!(4, colonnade.tabulate(data).unwrap().len());
+}
+
+#[test]
+fn macerate_with() {
+    let mut colonnade = Colonnade::new(2, 8).unwrap();
+    colonnade.spaces_between_rows(1).max_rows(2);
+    let data = [["a", "b c d e"], ["f", "g"], ["h", "i"]];
+    let mut pieces = vec![];
+    let mut out = String::new();
+    colonnade
+        .macerate_with(data, &mut out, |row, column, piece, out| {
+            pieces.push((
+                row,
+                column,
+                piece.margin.to_string(),
+                piece.text.to_string(),
+            ));
+            write!(out, "{}[{}]", piece.margin, piece.text)
+        })
+        .unwrap();
+    let maceration = colonnade.macerate(data).unwrap();
+    let expected: Vec<_> = maceration
+        .iter()
+        .enumerate()
+        .flat_map(|(r, row)| {
+            row.iter().flat_map(move |line| {
+                line.iter()
+                    .enumerate()
+                    .map(move |(c, (m, t))| (r, c, m.clone(), t.clone()))
+            })
+        })
+        .collect();
+    assert_eq!(expected, pieces);
+    assert_eq!(
+        "[a   ] [b c]\n[    ] [d e]\n        []\n[f   ] [g  ]\n[… and 1]\n[more row]\n",
+        out
+    );
+    assert_eq!(vec![3, 1], colonnade.row_heights());
+}
+
+#[test]
+fn slack_in_margins() {
+    use colonnade::HeatScale;
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    colonnade.slack_in_margins(true);
+    colonnade.columns[0].alignment(Alignment::Center);
+    colonnade.columns[1].padding_horizontal(1).padding_top(1);
+    let data = [("a", "bb"), ("ccccc", "d")];
+    let piece = |margin: &str, text: &str| (margin.to_string(), text.to_string());
+    let maceration = colonnade.macerate(data).unwrap();
+    assert_eq!(
+        vec![
+            vec![piece("  ", "a"), piece("       ", ""), piece("", "")],
+            vec![piece("     ", ""), piece(" ", " bb "), piece("", "")],
+        ],
+        maceration[0]
+    );
+    assert_eq!(
+        vec![piece("     ", ""), piece(" ", " d "), piece(" ", "")],
+        maceration[1][1]
+    );
+    let lines = colonnade.tabulate(data).unwrap();
+    assert_eq!(
+        vec!["  a       ", "       bb ", "ccccc     ", "       d  "],
+        lines
+    );
+    // a background covers only the text
+    colonnade.columns[1].heat(HeatScale::new((0, 0, 0), (9, 9, 9)).background(true));
+    let lines = colonnade.tabulate([("a", 1), ("b", 10)]).unwrap();
+    assert_eq!("       \u{1b}[48;2;0;0;0m 1 \u{1b}[0m", lines[1]);
+}
+
+#[test]
+fn piece_widths() {
+    use colonnade::{Aggregate, Style};
+    let mut colonnade = Colonnade::new(2, 20).unwrap();
+    colonnade
+        .spaces_between_rows(1)
+        .total_style(Some(Style::new().bold(true)));
+    colonnade.columns[0].right_margin(2);
+    colonnade.columns[1].aggregate(Aggregate::Sum);
+    let mut pieces = vec![];
+    colonnade
+        .macerate_with([("αβγ", 1)], &mut String::new(), |row, _, piece, _| {
+            pieces.push((row, piece.width, piece.column_width));
+            Ok(())
+        })
+        .unwrap();
+    assert_eq!(
+        vec![
+            (0, 5, Some(5)),
+            (0, 0, None),
+            (0, 1, Some(1)),
+            (0, 0, None),
+            (1, 5, Some(5)),
+            (1, 0, None),
+            (1, 1, Some(1)),
+        ],
+        pieces
+    );
+}
+
+#[test]
+fn tabulate_styled() {
+    use colonnade::{CellContext, Style};
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    colonnade
+        .header_row(true)
+        .header_style(None)
+        .spaces_between_rows(1);
+    colonnade.columns[0].name("name");
+    colonnade.columns[1].name("size");
+    let data = [("a", 1), ("bb", 22)];
+    let bold = Style::new().bold(true);
+    let styler = |ctx: CellContext, text: &str| {
+        if ctx.header {
+            bold.paint(text)
+        } else {
+            format!("<{}{}:{}>", ctx.row, ctx.column, text)
+        }
+    };
+    assert_eq!(
+        vec![
+            "\u{1b}[1mname\u{1b}[0m \u{1b}[1msize\u{1b}[0m",
+            "",
+            "<10:a   > <11:   1>",
+            "",
+            "<20:bb  > <21:  22>",
+        ],
+        colonnade.tabulate_styled(data, &styler).unwrap()
+    );
+    assert_eq!(
+        colonnade.tabulate(data).unwrap(),
+        colonnade.tabulate_styled(data, &Style::new()).unwrap()
+    );
+}
+
+#[test]
+fn plain_text() {
+    use colonnade::{Aggregate, Cell, Style};
+    let mut colonnade = Colonnade::new(3, 80).unwrap();
+    colonnade
+        .total_style(Some(Style::new().bold(true)))
+        .plain_text(true);
+    colonnade.columns[1].aggregate(Aggregate::Sum);
+    colonnade.columns[2].computed(|row| Cell::new(format!("\u{1b}[32m{}\u{1b}[0m", row[0].text())));
+    let data = [("\u{1b}[31mfig\u{1b}[0m", 2), ("plum", 10)];
+    let expected = vec!["fig    2 fig ", "plum  10 plum", "total 12     "];
+    assert_eq!(expected, colonnade.tabulate(data).unwrap());
+    assert_eq!(
+        expected,
+        colonnade
+            .tabulate_styled(data, &Style::new().bold(true))
+            .unwrap()
+    );
+    colonnade.plain_text(false);
+    assert!(colonnade.tabulate(data).unwrap()[2].starts_with("\u{1b}[1mtotal\u{1b}[0m"));
+}
+
+#[test]
+fn hyperlink() {
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    colonnade.header_row(true);
+    colonnade.columns[0].name("file");
+    colonnade.columns[1].name("lines").hyperlink(|row, cell| {
+        if cell.text() == "0" {
+            None
+        } else {
+            Some(format!("file:///{}", row))
+        }
+    });
+    let data = [("a.rs", 120), ("b.rs", 0)];
+    assert_eq!(
+        vec![
+            "file lines",
+            "a.rs   \u{1b}]8;;file:///1\u{1b}\\120\u{1b}]8;;\u{1b}\\",
+            "b.rs     0",
+        ],
+        colonnade.tabulate(data).unwrap()
+    );
+    assert_eq!(10, colonnade.render(data).unwrap().width());
+    colonnade.plain_text(true);
+    assert_eq!(
+        vec!["file lines", "a.rs   120", "b.rs     0"],
+        colonnade.tabulate(data).unwrap()
+    );
+}
+
+#[test]
+fn header_style() {
+    use colonnade::{Color, Style};
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    colonnade.header_row(true);
+    colonnade.columns[0].name("name");
+    colonnade.columns[1].name("size");
+    let data = [("a", 1)];
+    let red = Style::new().foreground(Color::Red);
+    assert_eq!(
+        vec![
+            "\u{1b}[1m\u{1b}[4mname\u{1b}[0m \u{1b}[1m\u{1b}[4msize\u{1b}[0m",
+            "\u{1b}[31ma   \u{1b}[0m \u{1b}[31m   1\u{1b}[0m",
+        ],
+        colonnade.tabulate_styled(data, &red).unwrap()
+    );
+    assert_eq!(
+        vec!["name size", "a       1"],
+        colonnade.tabulate(data).unwrap()
+    );
+    colonnade.header_style(Some(Style::new().dim(true)));
+    assert_eq!(
+        "\u{1b}[2mname\u{1b}[0m \u{1b}[2msize\u{1b}[0m",
+        colonnade.tabulate_styled(data, &red).unwrap()[0]
+    );
+}
+
+#[test]
+fn piece_kinds() {
+    use colonnade::{PieceKind, Separator, Style};
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    colonnade
+        .spaces_between_rows(1)
+        .separator(Separator::Fill("=".to_string()))
+        .separator_rows(Some(Separator::Fill("-".to_string())))
+        .max_rows(3);
+    colonnade.columns[0].right_margin(1);
+    let data = [["a", "b"], ["", ""], ["c", "d"], ["e", "f"]];
+    let mut kinds = vec![];
+    colonnade
+        .macerate_with(data, &mut String::new(), |row, _, piece, _| {
+            kinds.push((row, piece.kind));
+            Ok(())
+        })
+        .unwrap();
+    assert_eq!(
+        vec![
+            (0, PieceKind::Cell),
+            (0, PieceKind::Margin),
+            (0, PieceKind::Cell),
+            (0, PieceKind::Separator),
+            (1, PieceKind::Rule),
+            (1, PieceKind::Separator),
+            (2, PieceKind::Cell),
+            (2, PieceKind::Margin),
+            (2, PieceKind::Cell),
+            (3, PieceKind::Note),
+        ],
+        kinds[..10]
+    );
+    assert!(kinds[10..].iter().all(|&k| k == (3, PieceKind::Note)));
+    colonnade.separator_style(Some(Style::new().dim(true)));
+    assert_eq!(
+        vec![
+            "a  b",
+            "\u{1b}[2m====\u{1b}[0m",
+            "\u{1b}[2m----\u{1b}[0m",
+            "\u{1b}[2m====\u{1b}[0m",
+            "c  d",
+        ],
+        colonnade.tabulate_styled(data, &Style::new()).unwrap()[..5]
+    );
+}
+
+#[test]
+fn layered_styles() {
+    use colonnade::{Cell, Color, Style};
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    colonnade
+        .default_style(Some(
+            Style::new()
+                .foreground(Color::White)
+                .background(Color::Black),
+        ))
+        .row_style(|i, _| {
+            if i == 1 {
+                Some(Style::new().foreground(Color::Yellow))
+            } else {
+                None
+            }
+        });
+    colonnade.columns[1].style(Some(Style::new().foreground(Color::Green).italic(true)));
+    let data = vec![
+        vec![Cell::new("a"), Cell::new("b")],
+        vec![
+            Cell::new("c"),
+            Cell::new("d").style(Style::new().foreground(Color::Red)),
+        ],
+    ];
+    assert_eq!(
+        vec![
+            "\u{1b}[37m\u{1b}[40ma\u{1b}[0m \u{1b}[3m\u{1b}[32m\u{1b}[40mb\u{1b}[0m",
+            "\u{1b}[33m\u{1b}[40mc\u{1b}[0m \u{1b}[3m\u{1b}[31m\u{1b}[40md\u{1b}[0m",
+        ],
+        colonnade.tabulate(&data).unwrap()
+    );
+}
+
+#[test]
+fn group_stripes() {
+    use colonnade::{Aggregate, Color, Style};
+    let mut colonnade = Colonnade::new(3, 80).unwrap();
+    colonnade
+        .group_by(Some(0))
+        .unwrap()
+        .group_stripes(vec![
+            Style::new().background(Color::Blue),
+            Style::new().background(Color::Black),
+        ])
+        .row_style(|_, row| {
+            if row[2].text() == "9" {
+                Some(Style::new().bold(true))
+            } else {
+                None
+            }
+        });
+    colonnade.columns[2].aggregate(Aggregate::Sum);
+    let data = [("a", "x", 1), ("b", "y", 9), ("a", "z", 2), ("c", "w", 3)];
+    let blue = |s: &str| format!("\u{1b}[44m{}\u{1b}[0m", s);
+    let black = |s: &str| format!("\u{1b}[40m{}\u{1b}[0m", s);
+    let bold = |s: &str| format!("\u{1b}[1m\u{1b}[40m{}\u{1b}[0m", s);
+    assert_eq!(
+        vec![
+            blue("a            "),
+            format!("{} {} {}", blue("        "), blue("x"), blue(" 1")),
+            format!("{} {} {}", blue("        "), blue("z"), blue(" 2")),
+            format!("{} {} {}", blue("subtotal"), blue(" "), blue(" 3")),
+            black("b            "),
+            format!("{} {} {}", bold("        "), bold("y"), bold(" 9")),
+            format!("{} {} {}", bold("subtotal"), bold(" "), bold(" 9")),
+            blue("c            "),
+            format!("{} {} {}", blue("        "), blue("w"), blue(" 3")),
+            format!("{} {} {}", blue("subtotal"), blue(" "), blue(" 3")),
+            "total      15".to_string(),
+        ],
+        colonnade.tabulate(data).unwrap()
+    );
+}
+
+#[test]
+fn char_width_overrides() {
+    use colonnade::Separator;
+    let mut colonnade = Colonnade::new(2, 7).unwrap();
+    colonnade
+        .override_char_width('\u{e000}'..='\u{e0ff}', 2)
+        .spaces_between_rows(1)
+        .separator(Separator::Fill("\u{e001}".to_string()));
+    let data = [["\u{e000}\u{e000}\u{e000}", "a"], ["b", "c"]];
+    assert_eq!(
+        vec![
+            "\u{e000}\u{e000}- a",
+            "\u{e000}     ",
+            "\u{e001}\u{e001}\u{e001} ",
+            "b     c",
+        ],
+        colonnade.tabulate(data).unwrap()
+    );
+    assert_eq!(7, colonnade.render(data).unwrap().width());
+    // the overrides belong to the one table
+    assert_eq!(
+        vec!["\u{e000}\u{e000}\u{e000} a", "b   c"],
+        Colonnade::new(2, 7).unwrap().tabulate(data).unwrap()
+    );
+}
+
+#[cfg(feature = "unicode-width")]
+#[test]
+fn width_profiles() {
+    use colonnade::WidthProfile;
+    let data = [
+        ["🚀", "a"],
+        ["\u{2764}\u{fe0f}", "b"],
+        ["👨\u{200d}👩\u{200d}👧", "c"],
+        ["漢", "d"],
+    ];
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    colonnade.width_profile(WidthProfile::named("Kitty").unwrap());
+    assert_eq!(
+        vec![
+            "🚀 a",
+            "\u{2764}\u{fe0f} b",
+            "👨\u{200d}👩\u{200d}👧 c",
+            "漢 d",
+        ],
+        colonnade.tabulate(data).unwrap()
+    );
+    colonnade.width_profile(WidthProfile::ITerm2);
+    assert_eq!(
+        vec![
+            "🚀     a",
+            "\u{2764}\u{fe0f}      b",
+            "👨\u{200d}👩\u{200d}👧 c",
+            "漢     d",
+        ],
+        colonnade.tabulate(data).unwrap()
+    );
+    colonnade.width_profile(WidthProfile::Legacy);
+    assert_eq!(
+        vec![
+            "🚀 a",
+            "\u{2764}\u{fe0f} b",
+            "👨\u{200d}👩\u{200d}👧 c",
+            "漢 d"
+        ],
+        colonnade.tabulate(data).unwrap()
+    );
+    assert_eq!(None, WidthProfile::named("vt100"));
+}
+
+#[test]
+fn bidi() {
+    use colonnade::Bidi;
+    let mut colonnade = Colonnade::new(3, 80).unwrap();
+    let data = [["1", "peace", "x"], ["2", "سلام", "y"], ["3", "שלום", "z"]];
+    assert_eq!(
+        vec!["1 peace x", "2 سلام  y", "3 שלום  z"],
+        colonnade.tabulate(data).unwrap()
+    );
+    colonnade.bidi(Bidi::Align);
+    assert_eq!(
+        vec!["1 peace x", "2  سلام y", "3  שלום z"],
+        colonnade.tabulate(data).unwrap()
+    );
+    colonnade.bidi(Bidi::Isolate);
+    let lines = colonnade.tabulate(data).unwrap();
+    assert_eq!(
+        "\u{2068}2\u{2069}  \u{2068}سلام\u{2069} \u{2068}y\u{2069}",
+        lines[1]
+    );
+    assert_eq!(9, colonnade.render(data).unwrap().width());
+    colonnade.columns[1].alignment(Alignment::Left);
+    assert_eq!(
+        "\u{2068}2\u{2069} \u{2068}سلام\u{2069}  \u{2068}y\u{2069}",
+        colonnade.tabulate(data).unwrap()[1]
+    );
+}
+
+#[test]
+fn mirrored() {
+    let mut colonnade = Colonnade::new(3, 80).unwrap();
+    colonnade.columns[0].left_margin(2);
+    colonnade.columns[1].right_margin(3);
+    let data = [["a", "bb", "c"], ["aa", "b", "cc"]];
+    assert_eq!(
+        vec!["  a  bb    c ", "  aa b     cc"],
+        colonnade.tabulate(data).unwrap()
+    );
+    colonnade.mirrored(true);
+    assert_eq!(
+        vec!["c     bb a   ", "cc    b  aa  "],
+        colonnade.tabulate(data).unwrap()
+    );
+    colonnade.column_order(&[2, 0]).unwrap();
+    assert_eq!(
+        vec!["a  c   ", "aa cc  "],
+        colonnade.tabulate(data).unwrap()
+    );
+}
+
+#[cfg(feature = "collation")]
+#[test]
+fn collation() {
+    use colonnade::{Order, SortKey};
+    use icu_collator::{Collator, CollatorOptions};
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    let data = [
+        ["Ähnlich", "1"],
+        ["zebra", "2"],
+        ["apple", "3"],
+        ["10", "4"],
+        ["9", "5"],
+    ];
+    colonnade.sort_by_column(0, Order::Asc).unwrap();
+    assert_eq!(
+        vec![
+            "9       5",
+            "10      4",
+            "apple   3",
+            "zebra   2",
+            "Ähnlich 1"
+        ],
+        colonnade.tabulate(data).unwrap()
+    );
+    let collator = Collator::try_new(&"de".parse().unwrap(), CollatorOptions::new()).unwrap();
+    colonnade
+        .sort_by(vec![SortKey::new(0, Order::Asc).collator(collator)])
+        .unwrap();
+    assert_eq!(
+        vec![
+            "9       5",
+            "10      4",
+            "Ähnlich 1",
+            "apple   3",
+            "zebra   2"
+        ],
+        colonnade.tabulate(data).unwrap()
+    );
+    let collator = Collator::try_new(&"sv".parse().unwrap(), CollatorOptions::new()).unwrap();
+    colonnade
+        .sort_by(vec![SortKey::new(0, Order::Desc).collator(collator)])
+        .unwrap();
+    assert_eq!(
+        vec![
+            "Ähnlich 1",
+            "zebra   2",
+            "apple   3",
+            "10      4",
+            "9       5"
+        ],
+        colonnade.tabulate(data).unwrap()
+    );
+}
+
+#[test]
+fn monotonic_growth() {
+    let mut colonnade = Colonnade::new(2, 12).unwrap();
+    colonnade.overflow_policy(OverflowPolicy::Grow);
+    assert_eq!(vec!["a 1"], colonnade.tabulate([["a", "1"]]).unwrap());
+    assert!(!colonnade.grew());
+    assert_eq!(
+        vec!["abcdef 12"],
+        colonnade.tabulate([["abcdef", "12"]]).unwrap()
+    );
+    assert!(colonnade.grew());
+    assert_eq!(vec!["a      1 "], colonnade.tabulate([["a", "1"]]).unwrap());
+    assert!(!colonnade.grew());
+    assert_eq!(
+        vec!["abcdefgh- 1 ", "ijkl        "],
+        colonnade.tabulate([["abcdefghijkl", "1"]]).unwrap()
+    );
+    assert!(colonnade.grew());
+    assert_eq!(vec![(0, 0)], colonnade.overflows());
+}
+
+#[test]
+fn auto_relayout() {
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    colonnade.auto_relayout(true);
+    assert_eq!(
+        vec!["name size"],
+        colonnade.tabulate([["name", "size"]]).unwrap()
+    );
+    assert_eq!(
+        vec!["a b c d 1"],
+        colonnade.tabulate([["a b c d", "1"]]).unwrap()
+    );
+    assert_eq!(
+        vec!["colonnade 1024"],
+        colonnade.tabulate([["colonnade", "1024"]]).unwrap()
+    );
+    colonnade.auto_relayout(false).reset();
+    colonnade.tabulate([["name", "size"]]).unwrap();
+    assert_eq!(
+        vec!["col- 1024", "onn-     ", "ade      "],
+        colonnade.tabulate([["colonnade", "1024"]]).unwrap()
+    );
+}
+
+#[test]
+fn max_width_fraction() {
+    let data = [["a column of text", "b"]];
+    let mut colonnade = Colonnade::new(2, 20).unwrap();
+    colonnade.columns[0].max_width_fraction(0.4);
+    assert_eq!(
+        vec!["a column b", "of text   "],
+        colonnade.tabulate(data).unwrap()
+    );
+    colonnade.columns[0].max_width(6).unwrap();
+    assert_eq!(
+        vec!["a      b", "column  ", "of      ", "text    "],
+        colonnade.tabulate(data).unwrap()
+    );
+    let mut colonnade = Colonnade::new(2, 40).unwrap();
+    colonnade.columns[0].max_width_fraction(0.4);
+    assert_eq!(
+        vec!["a column of text b"],
+        colonnade.tabulate(data).unwrap()
+    );
+    colonnade.columns[0].min_width(20).unwrap();
+    assert!(matches!(
+        colonnade.tabulate(data),
+        Err(ColonnadeError::MinGreaterThanMax(0, None))
+    ));
+}
+
+#[test]
+fn min_width_fraction() {
+    let mut colonnade = Colonnade::new(2, 20).unwrap();
+    colonnade.columns[0].min_width_fraction(0.25);
+    assert_eq!(vec!["a     b"], colonnade.tabulate([["a", "b"]]).unwrap());
+    colonnade.columns[0].min_width(6).unwrap();
+    assert_eq!(vec!["a      b"], colonnade.tabulate([["a", "b"]]).unwrap());
+    let mut colonnade = Colonnade::new(3, 20).unwrap();
+    for column in colonnade.columns.iter_mut() {
+        column.min_width_fraction(0.4);
+    }
+    assert!(matches!(
+        colonnade.spaces_between_columns(1),
+        Err(ColonnadeError::InsufficientSpace(_))
+    ));
+    assert!(matches!(
+        colonnade.tabulate([["a", "b", "c"]]),
+        Err(ColonnadeError::InsufficientSpace(_))
+    ));
+    colonnade.columns[2].max_width_fraction(0.2);
+    assert!(matches!(
+        colonnade.tabulate([["a", "b", "c"]]),
+        Err(ColonnadeError::MinGreaterThanMax(2, None))
+    ));
+}
+
+#[test]
+fn width_ratio() {
+    let data = [["let x = 1;", "the answer, or near it"]];
+    let named = |width| {
+        let mut colonnade = Colonnade::new(2, width).unwrap();
+        colonnade.columns[0].name("code");
+        colonnade.columns[1].name("comment");
+        colonnade.width_ratio("comment", "code", 2.0);
+        colonnade
+    };
+    let mut colonnade = named(80);
+    assert_eq!(
+        vec!["let x = 1; answer              "],
+        colonnade.tabulate([["let x = 1;", "answer"]]).unwrap()
+    );
+    // the ratio follows the columns when they are reordered
+    colonnade.column_order(&[1, 0]).unwrap();
+    assert_eq!(
+        vec!["answer               let x = 1;"],
+        colonnade.tabulate([["let x = 1;", "answer"]]).unwrap()
+    );
+    let mut colonnade = named(16);
+    assert_eq!(
+        vec!["let x the       ", "= 1;  answer, or", "      near it   "],
+        colonnade.tabulate(data).unwrap()
+    );
+    colonnade.columns[1].max_width(8).unwrap();
+    assert_eq!(
+        vec![
+            "let x = the     ",
+            "1;      answer, ",
+            "        or near ",
+            "        it      "
+        ],
+        colonnade.tabulate(data).unwrap()
+    );
+    colonnade.width_ratio("comment", "notes", 2.0);
+    assert!(matches!(
+        colonnade.tabulate(data),
+        Err(ColonnadeError::UnknownColumn(name)) if name == "notes"
+    ));
+    for (of, ratio) in [
+        ("comment", 2.0),
+        ("code", 0.0),
+        ("code", -1.0),
+        ("code", f64::NAN),
+    ] {
+        colonnade.width_ratio("comment", of, ratio);
+        assert!(matches!(
+            colonnade.tabulate(data),
+            Err(ColonnadeError::InvalidRatio(..))
+        ));
+    }
+}
+
+#[test]
+fn broken_lines() {
+    use colonnade::Cell;
+    let code = vec![
+        "fn main() {".to_string(),
+        "    go();".to_string(),
+        String::new(),
+        "}".to_string(),
+    ];
+    let data = vec![
+        vec![Cell::new("fn"), Cell::from(code)],
+        vec![Cell::new("x"), Cell::new("short")],
+    ];
+    let mut colonnade = Colonnade::new(2, 80).unwrap();
+    assert_eq!(
+        vec![
+            "fn fn main() {",
+            "       go();  ",
+            "              ",
+            "   }          ",
+            "x  short      ",
+        ],
+        colonnade.tabulate(&data).unwrap()
+    );
+    colonnade.columns[1].alignment(Alignment::Right);
+    assert_eq!(
+        vec![
+            "fn fn main() {",
+            "         go();",
+            "              ",
+            "             }",
+            "x        short",
+        ],
+        colonnade.tabulate(&data).unwrap()
+    );
+    let mut colonnade = Colonnade::new(2, 10).unwrap();
+    assert_eq!(
+        vec![
+            "fn  fn    ",
+            "    main()",
+            "    {     ",
+            "    go(); ",
+            "          ",
+            "    }     ",
+            "x   short ",
+        ],
+        colonnade.tabulate(&data).unwrap()
+    );
+}